@@ -1,99 +1,87 @@
-// TODO: simplify this and use typed command variants (see https://github.com/teloxide/teloxide/issues/152).
+use std::time::Duration;
 
 use teloxide::{
-    prelude::*, types::ChatPermissions, utils::command::BotCommand,
+    prelude::*,
+    types::ChatPermissions,
+    utils::command::{
+        Arguments, CommandInfo, Help, ParseError, RoleGuard, TypedCommand,
+    },
 };
 
 use futures::future;
 
-// Derive BotCommand to parse text with a command into this enumeration.
-//
-//  1. rename = "lowercase" turns all the commands into lowercase letters.
-//  2. `description = "..."` specifies a text before all the commands.
-//
-// That is, you can just call Command::descriptions() to get a description of
-// your commands in this format:
-// %GENERAL-DESCRIPTION%
-// %PREFIX%%COMMAND% - %DESCRIPTION%
-#[derive(BotCommand)]
-#[command(
-    rename = "lowercase",
-    description = "Use commands in format /%command% %num% %unit%"
-)]
+// Commands with typed arguments. A duration is written like 30s, 15m, 5h or
+// 2d; if it's omitted, a user is restricted forever.
 enum Command {
-    #[command(description = "kick user from chat.")]
     Kick,
-    #[command(description = "ban user in chat.")]
-    Ban,
-    #[command(description = "mute user in chat.")]
-    Mute,
-
-    Help,
+    Ban { duration: Option<Duration> },
+    Mute { duration: Option<Duration> },
 }
 
-// Calculates time of user restriction.
-fn calc_restrict_time(num: i32, unit: &str) -> Result<i32, &str> {
-    match unit {
-        "h" | "hours" => Ok(num * 3600),
-        "m" | "minutes" => Ok(num * 60),
-        "s" | "seconds" => Ok(num),
-        _ => Err("Allowed units: h, m, s"),
+impl TypedCommand for Command {
+    fn from_parts(
+        command: &str,
+        args: Arguments<'_>,
+    ) -> Result<Self, ParseError> {
+        match command {
+            "kick" => args.parse().map(|()| Command::Kick),
+            "ban" => args.parse().map(|(duration,)| Command::Ban { duration }),
+            "mute" => {
+                args.parse().map(|(duration,)| Command::Mute { duration })
+            }
+            _ => Err(ParseError::UnknownCommand(command.to_owned())),
+        }
     }
-}
 
-// Parse arguments after a command.
-fn parse_args(args: &[String]) -> Result<(i32, &str), &str> {
-    let num = match args.get(0) {
-        Some(s) => s,
-        None => return Err("Use command in format /%command% %num% %unit%"),
-    };
-    let unit = match args.get(1) {
-        Some(s) => s,
-        None => return Err("Use command in format /%command% %num% %unit%"),
-    };
-
-    match num.parse::<i32>() {
-        Ok(n) => Ok((n, unit)),
-        Err(_) => Err("input positive number!"),
+    // Used to answer /help, to fill the command menu and to check that only
+    // administrators use the commands.
+    fn commands() -> Vec<CommandInfo> {
+        vec![
+            CommandInfo::new("kick")
+                .description("kick user from chat.")
+                .admin_only(),
+            CommandInfo::new("ban")
+                .args_schema("[duration]")
+                .description("ban user in chat.")
+                .admin_only(),
+            CommandInfo::new("mute")
+                .args_schema("[duration]")
+                .description("mute user in chat.")
+                .admin_only(),
+            CommandInfo::new("help")
+                .args_schema("[command]")
+                .description("show this text."),
+        ]
     }
 }
 
-// Parse arguments into a user restriction duration.
-fn parse_time_restrict(args: &[String]) -> Result<i32, &str> {
-    let (num, unit) = parse_args(args)?;
-    calc_restrict_time(num, unit)
-}
+type Cx = UpdateWithCx<Message>;
 
-type Cx = DispatcherHandlerCx<Message>;
+// Calculates a date until which a user is restricted.
+fn until_date(cx: &Cx, duration: Duration) -> i32 {
+    (cx.update.date + duration.as_secs() as i64) as i32
+}
 
 // Mute a user with a replied message.
-async fn mute_user(cx: &Cx, args: &[String]) -> ResponseResult<()> {
+async fn mute_user(cx: &Cx, duration: Option<Duration>) -> ResponseResult<()> {
     match cx.update.reply_to_message() {
-        Some(msg1) => match parse_time_restrict(args) {
-            // Mute user temporarily...
-            Ok(time) => {
-                cx.bot
-                    .restrict_chat_member(
-                        cx.update.chat_id(),
-                        msg1.from().expect("Must be MessageKind::Common").id,
-                        ChatPermissions::default(),
-                    )
-                    .until_date(cx.update.date + time)
-                    .send()
-                    .await?;
-            }
-            // ...or permanently
-            Err(_) => {
-                cx.bot
-                    .restrict_chat_member(
-                        cx.update.chat_id(),
-                        msg1.from().unwrap().id,
-                        ChatPermissions::default(),
-                    )
-                    .send()
-                    .await?;
-            }
-        },
+        Some(message) => {
+            let user_id =
+                message.from().expect("Must be MessageKind::Common").id;
+            let request = cx.bot.restrict_chat_member(
+                cx.update.chat_id(),
+                user_id,
+                ChatPermissions::default(),
+            );
+
+            // Mute user temporarily or permanently.
+            match duration {
+                Some(duration) => {
+                    request.until_date(until_date(cx, duration)).send().await?
+                }
+                None => request.send().await?,
+            };
+        }
         None => {
             cx.reply_to("Use this command in reply to another message")
                 .send()
@@ -123,31 +111,21 @@ async fn kick_user(cx: &Cx) -> ResponseResult<()> {
 }
 
 // Ban a user with replied message.
-async fn ban_user(cx: &Cx, args: &[String]) -> ResponseResult<()> {
+async fn ban_user(cx: &Cx, duration: Option<Duration>) -> ResponseResult<()> {
     match cx.update.reply_to_message() {
-        Some(message) => match parse_time_restrict(args) {
-            // Mute user temporarily...
-            Ok(time) => {
-                cx.bot
-                    .kick_chat_member(
-                        cx.update.chat_id(),
-                        message.from().expect("Must be MessageKind::Common").id,
-                    )
-                    .until_date(cx.update.date + time)
-                    .send()
-                    .await?;
-            }
-            // ...or permanently
-            Err(_) => {
-                cx.bot
-                    .kick_chat_member(
-                        cx.update.chat_id(),
-                        message.from().unwrap().id,
-                    )
-                    .send()
-                    .await?;
-            }
-        },
+        Some(message) => {
+            let user_id =
+                message.from().expect("Must be MessageKind::Common").id;
+            let request = cx.bot.kick_chat_member(cx.update.chat_id(), user_id);
+
+            // Ban user temporarily or permanently.
+            match duration {
+                Some(duration) => {
+                    request.until_date(until_date(cx, duration)).send().await?
+                }
+                None => request.send().await?,
+            };
+        }
         None => {
             cx.reply_to("Use this command in a reply to another message!")
                 .send()
@@ -158,27 +136,36 @@ async fn ban_user(cx: &Cx, args: &[String]) -> ResponseResult<()> {
 }
 
 async fn action(
-    cx: DispatcherHandlerCx<Message>,
-    command: Command,
-    args: &[String],
+    cx: Cx,
+    command: Result<Command, ParseError>,
 ) -> ResponseResult<()> {
     match command {
-        Command::Help => {
-            cx.answer(Command::descriptions()).send().await.map(|_| ())?
+        Ok(Command::Kick) => kick_user(&cx).await?,
+        Ok(Command::Ban { duration }) => ban_user(&cx, duration).await?,
+        Ok(Command::Mute { duration }) => mute_user(&cx, duration).await?,
+        // Tell a user what is wrong with the command.
+        Err(error) => {
+            cx.reply_to(error.to_string()).send().await.map(|_| ())?
         }
-        Command::Kick => kick_user(&cx).await?,
-        Command::Ban => ban_user(&cx, args).await?,
-        Command::Mute => mute_user(&cx, args).await?,
     };
 
     Ok(())
 }
 
 async fn handle_commands(rx: DispatcherHandlerRx<Message>) {
+    let bot_name: &str = panic!("Insert here your bot's name");
+
     rx.filter(|cx| future::ready(cx.update.chat.is_group()))
-        .commands::<Command, &str>(panic!("Insert here your bot's name"))
-        .for_each_concurrent(None, |(cx, command, args)| async move {
-            action(cx, command, &args).await.log_on_error().await;
+        .typed_commands_with_guard::<Command, _>(
+            bot_name,
+            RoleGuard::of::<Command>(),
+            Some(
+                Help::of::<Command>()
+                    .header("Use commands in reply to another message:"),
+            ),
+        )
+        .for_each_concurrent(None, |(cx, command)| async move {
+            action(cx, command).await.log_on_error().await;
         })
         .await;
 }
@@ -193,6 +180,11 @@ async fn run() {
     log::info!("Starting admin_bot!");
 
     let bot = Bot::from_env();
+    bot.set_my_commands(Help::of::<Command>().bot_commands())
+        .send()
+        .await
+        .log_on_error()
+        .await;
 
     Dispatcher::new(bot).messages_handler(handle_commands).dispatch().await
 }
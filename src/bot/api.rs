@@ -11,19 +11,22 @@ use crate::{
         GetUserProfilePhotos, GetWebhookInfo, KickChatMember, LeaveChat,
         PinChatMessage, PromoteChatMember, RestrictChatMember, SendAnimation,
         SendAudio, SendChatAction, SendChatActionKind, SendContact,
-        SendDocument, SendGame, SendInvoice, SendLocation, SendMediaGroup,
-        SendMessage, SendPhoto, SendPoll, SendSticker, SendVenue, SendVideo,
-        SendVideoNote, SendVoice, SetChatAdministratorCustomTitle,
-        SetChatDescription, SetChatPermissions, SetChatPhoto,
+        SendDocument, SendGame, SendInvoice, SendLocation, SendLongMessage,
+        SendMediaGroup, SendMessage, SendPhoto, SendPoll, SendSticker,
+        SendVenue, SendVideo, SendVideoNote, SendVoice,
+        SetChatAdministratorCustomTitle, SetChatDescription,
+        SetChatPermissions, SetChatPhoto,
         SetChatStickerSet, SetChatTitle, SetGameScore, SetStickerPositionInSet,
         SetWebhook, StopMessageLiveLocation, StopPoll, UnbanChatMember,
         UnpinChatMessage, UploadStickerFile,SetGuildCredit, DeleteGuildCredit,
+        GetGuildCredit,
         GetPrivateChat, GetGuildRoles, GetGuildMembers, SearchGuildMember, SetMemberRoles,
-        GetRoleMembers,
+        GetRoleMembers, GetGuild, GetGuildChannels, SetMyCommands,
+        GetMyCommands,
     },
     types::{
         ChatId, ChatOrInlineMessage, ChatPermissions, InlineQueryResult,
-        InputFile, InputMedia, LabeledPrice,GuildCredit
+        InputFile, InputMedia, LabeledPrice,GuildCredit, BotCommand,
     },
     Bot,
 };
@@ -123,6 +126,29 @@ impl Bot {
         SendMessage::new(Arc::clone(self), chat_id, text)
     }
 
+    /// Sends a text of any length, splitting it into several messages if it's
+    /// too long for one.
+    ///
+    /// See [`SendLongMessage`] for how the text is split.
+    ///
+    /// # Params
+    ///   - `chat_id`: Unique identifier for the target chat or username of the
+    ///     target supergroup or channel (in the format `@channelusername`).
+    ///   - `text`: Text to be sent.
+    ///
+    /// [`SendLongMessage`]: crate::requests::SendLongMessage
+    pub fn send_long_message<C, T>(
+        self: &Arc<Bot>,
+        chat_id: C,
+        text: T,
+    ) -> SendLongMessage
+    where
+        C: Into<ChatId>,
+        T: Into<String>,
+    {
+        SendLongMessage::new(Arc::clone(self), chat_id, text)
+    }
+
     /// Use this method to forward messages of any kind.
     ///
     /// [`The official docs`](https://core.telegram.org/bots/api#forwardmessage).
@@ -1622,6 +1648,24 @@ impl Bot {
         DeleteGuildCredit::new(Arc::clone(self), chat_id, guild_id, user_id)
     }
 
+    /// Use this method to get a guild credit of a user.
+    ///
+    /// # Params
+    ///   - `chat_id`: Unique identifier for the target chat.
+    ///   - `guild_id`: Unique identifier for the target guild.
+    ///   - `user_id`: Unique identifier of the target user.
+    pub fn get_guild_credit<C>(
+        self: &Arc<Bot>,
+        chat_id: Option<C>,
+        guild_id: Option<i64>,
+        user_id: i64,
+    ) -> GetGuildCredit
+        where
+            C: Into<ChatId>,
+    {
+        GetGuildCredit::new(Arc::clone(self), chat_id, guild_id, user_id)
+    }
+
     pub fn get_private_chat(
         self: &Arc<Bot>,
         user_id: i64,
@@ -1629,6 +1673,28 @@ impl Bot {
         GetPrivateChat::new(Arc::clone(self), user_id)
     }
 
+    /// Use this method to get up to date information about a guild.
+    ///
+    /// # Params
+    ///   - `guild_id`: Unique identifier for the target guild.
+    pub fn get_guild(
+        self: &Arc<Bot>,
+        guild_id: i64,
+    ) -> GetGuild {
+        GetGuild::new(Arc::clone(self), guild_id)
+    }
+
+    /// Use this method to get all channels of a guild.
+    ///
+    /// # Params
+    ///   - `guild_id`: Unique identifier for the target guild.
+    pub fn get_guild_channels(
+        self: &Arc<Bot>,
+        guild_id: i64,
+    ) -> GetGuildChannels {
+        GetGuildChannels::new(Arc::clone(self), guild_id)
+    }
+
     pub fn get_guild_roles(
         self: &Arc<Bot>,
         guild_id: i64,
@@ -1678,6 +1744,32 @@ impl Bot {
         GetRoleMembers::new(Arc::clone(self), guild_id, role_id)
     }
 
+    /// Use this method to change the list of the bot's commands.
+    ///
+    /// [The official docs](https://core.telegram.org/bots/api#setmycommands).
+    ///
+    /// # Params
+    ///   - `commands`: A list of bot commands to be set as the list of the
+    ///     bot's commands. At most 100 commands can be specified.
+    ///
+    /// To publish commands of a [`TypedCommand`], see [`Help::bot_commands`].
+    ///
+    /// [`TypedCommand`]: crate::utils::command::TypedCommand
+    /// [`Help::bot_commands`]: crate::utils::command::Help::bot_commands
+    pub fn set_my_commands<C>(self: &Arc<Bot>, commands: C) -> SetMyCommands
+    where
+        C: Into<Vec<BotCommand>>,
+    {
+        SetMyCommands::new(Arc::clone(self), commands)
+    }
+
+    /// Use this method to get the current list of the bot's commands.
+    ///
+    /// [The official docs](https://core.telegram.org/bots/api#getmycommands).
+    pub fn get_my_commands(self: &Arc<Bot>) -> GetMyCommands {
+        GetMyCommands::new(Arc::clone(self))
+    }
+
 
 
 }
@@ -1,11 +1,15 @@
+use std::{path::Path, sync::Arc};
 use tokio::io::AsyncWrite;
 
-#[cfg(feature = "unstable-stream")]
-use ::{bytes::Bytes, tokio::stream::Stream};
-
-#[cfg(feature = "unstable-stream")]
-use crate::net::download_file_stream;
-use crate::{bot::Bot, net::download_file, DownloadError};
+use crate::{
+    bot::Bot,
+    net::{
+        download_file_stream_with, download_file_to_path_with,
+        download_file_with, write_stream, DownloadOptions, FileStream,
+    },
+    requests::Request,
+    DownloadError,
+};
 
 impl Bot {
     /// Download a file from Telegram into `destination`.
@@ -41,26 +45,129 @@ impl Bot {
     where
         D: AsyncWrite + Unpin,
     {
-        download_file(&self.client, &self.token, path, destination).await
+        download_file_with(self.transport(), &self.token, path, destination)
+            .await
     }
 
-    /// Download a file from Telegram.
+    /// Download a file from Telegram as a stream of chunks.
     ///
     /// `path` can be obtained from the [`Bot::get_file`].
     ///
     /// To download into [`AsyncWrite`] (e.g. [`tokio::fs::File`]), see
     /// [`Bot::download_file`].
     ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use teloxide::{requests::Request, Bot};
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let bot = Bot::new("TOKEN");
+    ///
+    /// let file = bot.get_file("*file_id*").send().await?;
+    /// let mut stream = bot.download_file_stream(&file.file_path).await?;
+    /// while let Some(chunk) = stream.next().await {
+    ///     println!("Received {} bytes", chunk?.len());
+    /// }
+    /// # Ok(()) }
+    /// ```
+    ///
     /// [`Bot::get_file`]: crate::bot::Bot::get_file
     /// [`AsyncWrite`]: tokio::io::AsyncWrite
     /// [`tokio::fs::File`]: tokio::fs::File
     /// [`Bot::download_file`]: crate::Bot::download_file
-    #[cfg(feature = "unstable-stream")]
     pub async fn download_file_stream(
         &self,
         path: &str,
-    ) -> Result<impl Stream<Item = Result<Bytes, reqwest::Error>>, reqwest::Error>
+    ) -> Result<FileStream, DownloadError> {
+        download_file_stream_with(
+            self.transport(),
+            &self.token,
+            path,
+            &DownloadOptions::default(),
+        )
+        .await
+    }
+
+    /// Download a file from Telegram into a file at `destination`.
+    ///
+    /// The file is written into a temporary file in the same directory and
+    /// then renamed, so `destination` never contains a partially downloaded
+    /// file, even if downloading fails. Returns a size of the downloaded file.
+    ///
+    /// `path` can be obtained from the [`Bot::get_file`].
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use teloxide::{net::DownloadOptions, requests::Request, Bot};
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let bot = Bot::new("TOKEN");
+    ///
+    /// let file = bot.get_file("*file_id*").send().await?;
+    /// let options = DownloadOptions::new().max_size(10 * 1024 * 1024);
+    /// bot.download_file_to_path(&file.file_path, "photo.png", &options)
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`Bot::get_file`]: crate::bot::Bot::get_file
+    pub async fn download_file_to_path<P>(
+        &self,
+        path: &str,
+        destination: P,
+        options: &DownloadOptions,
+    ) -> Result<u64, DownloadError>
+    where
+        P: AsRef<Path>,
     {
-        download_file_stream(&self.client, &self.token, path).await
+        download_file_to_path_with(
+            self.transport(),
+            &self.token,
+            path,
+            destination.as_ref(),
+            options,
+        )
+        .await
+    }
+
+    /// Download a file with the specified `file_id` into memory.
+    ///
+    /// Calls [`Bot::get_file`] first; if a size of the file is known and
+    /// exceeds [`DownloadOptions::max_size`], the file is not downloaded at
+    /// all.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// use teloxide::{net::DownloadOptions, Bot};
+    ///
+    /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    /// let bot = Bot::new("TOKEN");
+    ///
+    /// let options = DownloadOptions::new().max_size(1024 * 1024);
+    /// let bytes = bot.download_file_by_id("*file_id*", &options).await?;
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`Bot::get_file`]: crate::bot::Bot::get_file
+    /// [`DownloadOptions::max_size`]: crate::net::DownloadOptions::max_size
+    pub async fn download_file_by_id<F>(
+        self: &Arc<Self>,
+        file_id: F,
+        options: &DownloadOptions,
+    ) -> Result<Vec<u8>, DownloadError>
+    where
+        F: Into<String>,
+    {
+        let file = self.get_file(file_id).send().await?;
+        options.check_size(u64::from(file.file_size))?;
+
+        let stream = self.download_file_stream(&file.file_path).await?;
+        let mut bytes = Vec::with_capacity(file.file_size as usize);
+        write_stream(stream, &mut bytes, options).await?;
+        Ok(bytes)
     }
 }
@@ -1,5 +1,16 @@
-use reqwest::Client;
-use std::sync::Arc;
+use crate::{
+    instrumentation,
+    net::{self, RequestObserver, ReqwestTransport, Transport},
+    requests::ResponseResult,
+    utils::file_id_cache::FileIdCache,
+};
+use reqwest::{multipart::Form, Client};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::HashSet,
+    fmt,
+    sync::{Arc, Mutex},
+};
 
 mod api;
 mod download;
@@ -9,6 +20,38 @@ mod download;
 pub struct Bot {
     token: String,
     client: Client,
+
+    /// IDs of callback queries which must be answered automatically if a
+    /// handler doesn't answer them.
+    pending_callback_queries: Arc<Mutex<HashSet<String>>>,
+
+    observer: Option<Observer>,
+    transport: TransportHandle,
+    file_id_cache: Option<FileIdCache>,
+}
+
+#[derive(Clone)]
+struct Observer(Arc<dyn RequestObserver>);
+
+impl fmt::Debug for Observer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RequestObserver")
+    }
+}
+
+#[derive(Clone)]
+struct TransportHandle(Arc<dyn Transport>);
+
+impl Default for TransportHandle {
+    fn default() -> Self {
+        Self(Arc::new(ReqwestTransport::default()))
+    }
+}
+
+impl fmt::Debug for TransportHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Transport")
+    }
 }
 
 impl Bot {
@@ -57,11 +100,57 @@ impl Bot {
     where
         S: Into<String>,
     {
-        Arc::new(Self { token: token.into(), client })
+        Arc::new(Self {
+            token: token.into(),
+            transport: TransportHandle(Arc::new(ReqwestTransport::new(
+                client.clone(),
+            ))),
+            client,
+            pending_callback_queries: Arc::default(),
+            observer: None,
+            file_id_cache: None,
+        })
     }
-}
 
+    /// Returns a copy of this bot passing all its requests to `observer`.
+    ///
+    /// For a logger of requests, see [`RedactingLogger`].
+    ///
+    /// [`RedactingLogger`]: crate::net::RedactingLogger
+    pub fn with_observer<O>(&self, observer: O) -> Arc<Self>
+    where
+        O: RequestObserver + 'static,
+    {
+        Arc::new(Self {
+            observer: Some(Observer(Arc::new(observer))),
+            ..self.clone()
+        })
+    }
 
+    /// Returns a copy of this bot sending all its requests and downloading
+    /// files with `transport` instead of [`ReqwestTransport`].
+    ///
+    /// [`ReqwestTransport`]: crate::net::ReqwestTransport
+    pub fn with_transport<T>(&self, transport: T) -> Arc<Self>
+    where
+        T: Transport + 'static,
+    {
+        Arc::new(Self {
+            transport: TransportHandle(Arc::new(transport)),
+            ..self.clone()
+        })
+    }
+
+    /// Returns a copy of this bot sending IDs of files which it has already
+    /// uploaded instead of uploading them again.
+    ///
+    /// See the [`file_id_cache`] module.
+    ///
+    /// [`file_id_cache`]: crate::utils::file_id_cache
+    pub fn with_file_id_cache(&self, cache: FileIdCache) -> Arc<Self> {
+        Arc::new(Self { file_id_cache: Some(cache), ..self.clone() })
+    }
+}
 
 impl Bot {
     // TODO: const fn
@@ -69,8 +158,79 @@ impl Bot {
         &self.token
     }
 
+    /// A client used by [`ReqwestTransport`], unless another transport is set
+    /// with [`Bot::with_transport`].
+    ///
+    /// [`ReqwestTransport`]: crate::net::ReqwestTransport
     // TODO: const fn
     pub fn client(&self) -> &Client {
         &self.client
     }
+
+    /// A cache set with [`Bot::with_file_id_cache`].
+    pub fn file_id_cache(&self) -> Option<&FileIdCache> {
+        self.file_id_cache.as_ref()
+    }
+
+    pub(crate) async fn execute_json<T, P>(
+        &self,
+        method_name: &str,
+        params: &P,
+    ) -> ResponseResult<T>
+    where
+        T: DeserializeOwned,
+        P: Serialize,
+    {
+        net::request_json_with(
+            self.transport(),
+            &self.token,
+            method_name,
+            params,
+            self.observer(),
+        )
+        .await
+    }
+
+    pub(crate) async fn execute_multipart<T>(
+        &self,
+        method_name: &str,
+        params: Form,
+    ) -> ResponseResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        net::request_multipart_with(
+            self.transport(),
+            &self.token,
+            method_name,
+            params,
+            self.observer(),
+        )
+        .await
+    }
+
+    pub(crate) fn transport(&self) -> &dyn Transport {
+        &*self.transport.0
+    }
+
+    fn observer(&self) -> Option<&dyn RequestObserver> {
+        self.observer.as_ref().map(|Observer(observer)| &**observer)
+    }
+
+    /// Marks a callback query as waiting for an answer.
+    pub(crate) fn track_callback_query(&self, id: String) {
+        let mut pending = self.pending_callback_queries.lock().unwrap();
+        pending.insert(id);
+        instrumentation::pending_callback_queries(pending.len());
+    }
+
+    /// Marks a callback query as answered.
+    ///
+    /// Returns `true` if the query was waiting for an answer.
+    pub(crate) fn untrack_callback_query(&self, id: &str) -> bool {
+        let mut pending = self.pending_callback_queries.lock().unwrap();
+        let removed = pending.remove(id);
+        instrumentation::pending_callback_queries(pending.len());
+        removed
+    }
 }
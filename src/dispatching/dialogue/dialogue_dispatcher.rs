@@ -89,8 +89,9 @@ where
             let storage = Arc::clone(&storage);
             let handler = Arc::clone(&handler);
             let senders = Arc::clone(&senders);
+            let span = cx.span.clone();
 
-            async move {
+            span.instrument(async move {
                 let chat_id = cx.update.chat_id();
 
                 let dialogue = Arc::clone(&storage)
@@ -120,7 +121,7 @@ where
                         // the beginning of this async block).
                     }
                 }
-            }
+            })
         }));
 
         tx
@@ -259,9 +260,8 @@ mod tests {
                 MyUpdate::new(3, 1611),
             ]
             .into_iter()
-            .map(|update| UpdateWithCx {
-                update,
-                bot: Bot::new("Doesn't matter here"),
+            .map(|update| {
+                UpdateWithCx::new(Bot::new("Doesn't matter here"), update)
             })
             .collect::<Vec<UpdateWithCx<MyUpdate>>>(),
         );
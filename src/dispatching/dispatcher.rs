@@ -4,14 +4,16 @@ use crate::{
         UpdateWithCx,
     },
     error_handlers::{ErrorHandler, LoggingErrorHandler},
+    instrumentation::{self, UpdateSpan},
+    requests::Request,
     types::{
         CallbackQuery, ChosenInlineResult, InlineQuery, Message, Poll,
-        PollAnswer, PreCheckoutQuery, ShippingQuery, UpdateKind,
+        PollAnswer, PreCheckoutQuery, ShippingQuery, Update, UpdateKind,
     },
     Bot,
 };
 use futures::StreamExt;
-use std::{fmt::Debug, sync::Arc};
+use std::{fmt::Debug, sync::Arc, time::Duration};
 use tokio::sync::mpsc;
 
 type Tx<Upd> = Option<mpsc::UnboundedSender<UpdateWithCx<Upd>>>;
@@ -20,8 +22,8 @@ type Tx<Upd> = Option<mpsc::UnboundedSender<UpdateWithCx<Upd>>>;
 mod macros {
     /// Pushes an update to a queue.
     macro_rules! send {
-        ($bot:expr, $tx:expr, $update:expr, $variant:expr) => {
-            send($bot, $tx, $update, stringify!($variant));
+        ($bot:expr, $tx:expr, $update:expr, $span:expr, $variant:expr) => {
+            send($bot, $tx, $update, $span, stringify!($variant));
         };
     }
 }
@@ -30,14 +32,14 @@ fn send<'a, Upd>(
     bot: &'a Arc<Bot>,
     tx: &'a Tx<Upd>,
     update: Upd,
+    span: UpdateSpan,
     variant: &'static str,
 ) where
     Upd: Debug,
 {
     if let Some(tx) = tx {
-        if let Err(error) =
-            tx.send(UpdateWithCx { bot: Arc::clone(&bot), update })
-        {
+        let cx = UpdateWithCx { bot: Arc::clone(&bot), update, span };
+        if let Err(error) = tx.send(cx) {
             log::error!(
                 "The RX part of the {} channel is closed, but an update is \
                  received.\nError:{}\n",
@@ -48,6 +50,28 @@ fn send<'a, Upd>(
     }
 }
 
+/// Answers a callback query with an empty answer after `deadline` unless it's
+/// answered before.
+fn auto_answer(bot: &Arc<Bot>, id: &str, deadline: Duration) {
+    bot.track_callback_query(id.to_owned());
+
+    let bot = Arc::clone(bot);
+    let id = id.to_owned();
+    tokio::spawn(async move {
+        tokio::time::delay_for(deadline).await;
+
+        if bot.untrack_callback_query(&id) {
+            log::debug!("Automatically answering the callback query {}", id);
+            if let Err(error) = bot.answer_callback_query(id).send().await {
+                log::error!(
+                    "Cannot automatically answer a callback query: {:?}",
+                    error
+                );
+            }
+        }
+    });
+}
+
 /// One dispatcher to rule them all.
 ///
 /// See [the module-level documentation for the design
@@ -66,6 +90,8 @@ pub struct Dispatcher {
     pre_checkout_queries_queue: Tx<PreCheckoutQuery>,
     polls_queue: Tx<Poll>,
     poll_answers_queue: Tx<PollAnswer>,
+
+    callback_queries_answer_deadline: Option<Duration>,
 }
 
 impl Dispatcher {
@@ -85,6 +111,7 @@ impl Dispatcher {
             pre_checkout_queries_queue: None,
             polls_queue: None,
             poll_answers_queue: None,
+            callback_queries_answer_deadline: None,
         }
     }
 
@@ -165,6 +192,20 @@ impl Dispatcher {
         self
     }
 
+    /// Answers callback queries automatically if a handler hasn't answered
+    /// them within `deadline`.
+    ///
+    /// A callback query is considered answered once
+    /// [`Bot::answer_callback_query`] is sent for it. Until then, the user's
+    /// client shows a progress bar, so keep `deadline` short.
+    ///
+    /// [`Bot::answer_callback_query`]: crate::Bot::answer_callback_query
+    #[must_use]
+    pub fn auto_answer_callback_queries(mut self, deadline: Duration) -> Self {
+        self.callback_queries_answer_deadline = Some(deadline);
+        self
+    }
+
     #[must_use]
     pub fn shipping_queries_handler<H>(mut self, h: H) -> Self
     where
@@ -246,98 +287,179 @@ impl Dispatcher {
                         }
                     };
 
-                    match update.kind {
-                        UpdateKind::Message(message) => {
-                            send!(
-                                &self.bot,
-                                &self.messages_queue,
-                                message,
-                                UpdateKind::Message
-                            );
-                        }
-                        UpdateKind::EditedMessage(message) => {
-                            send!(
-                                &self.bot,
-                                &self.edited_messages_queue,
-                                message,
-                                UpdateKind::EditedMessage
-                            );
-                        }
-                        UpdateKind::ChannelPost(post) => {
-                            send!(
-                                &self.bot,
-                                &self.channel_posts_queue,
-                                post,
-                                UpdateKind::ChannelPost
-                            );
-                        }
-                        UpdateKind::EditedChannelPost(post) => {
-                            send!(
-                                &self.bot,
-                                &self.edited_channel_posts_queue,
-                                post,
-                                UpdateKind::EditedChannelPost
-                            );
-                        }
-                        UpdateKind::InlineQuery(query) => {
-                            send!(
-                                &self.bot,
-                                &self.inline_queries_queue,
-                                query,
-                                UpdateKind::InlineQuery
-                            );
-                        }
-                        UpdateKind::ChosenInlineResult(result) => {
-                            send!(
-                                &self.bot,
-                                &self.chosen_inline_results_queue,
-                                result,
-                                UpdateKind::ChosenInlineResult
-                            );
-                        }
-                        UpdateKind::CallbackQuery(query) => {
-                            send!(
-                                &self.bot,
-                                &self.callback_queries_queue,
-                                query,
-                                UpdateKind::CallbackQuer
-                            );
-                        }
-                        UpdateKind::ShippingQuery(query) => {
-                            send!(
-                                &self.bot,
-                                &self.shipping_queries_queue,
-                                query,
-                                UpdateKind::ShippingQuery
-                            );
-                        }
-                        UpdateKind::PreCheckoutQuery(query) => {
-                            send!(
-                                &self.bot,
-                                &self.pre_checkout_queries_queue,
-                                query,
-                                UpdateKind::PreCheckoutQuery
-                            );
-                        }
-                        UpdateKind::Poll(poll) => {
-                            send!(
-                                &self.bot,
-                                &self.polls_queue,
-                                poll,
-                                UpdateKind::Poll
-                            );
-                        }
-                        UpdateKind::PollAnswer(answer) => {
-                            send!(
-                                &self.bot,
-                                &self.poll_answers_queue,
-                                answer,
-                                UpdateKind::PollAnswer
-                            );
-                        }
-                    }
+                    let span =
+                        instrumentation::UpdateLabels::new(&update).into_span();
+                    self.dispatch_update(update, span);
                 }
             })
             .await
     }
+
+    fn dispatch_update(&self, update: Update, span: UpdateSpan) {
+        match update.kind {
+            UpdateKind::Message(message) => {
+                send!(
+                    &self.bot,
+                    &self.messages_queue,
+                    message,
+                    span,
+                    UpdateKind::Message
+                );
+            }
+            UpdateKind::EditedMessage(message) => {
+                send!(
+                    &self.bot,
+                    &self.edited_messages_queue,
+                    message,
+                    span,
+                    UpdateKind::EditedMessage
+                );
+            }
+            UpdateKind::ChannelPost(post) => {
+                send!(
+                    &self.bot,
+                    &self.channel_posts_queue,
+                    post,
+                    span,
+                    UpdateKind::ChannelPost
+                );
+            }
+            UpdateKind::EditedChannelPost(post) => {
+                send!(
+                    &self.bot,
+                    &self.edited_channel_posts_queue,
+                    post,
+                    span,
+                    UpdateKind::EditedChannelPost
+                );
+            }
+            UpdateKind::InlineQuery(query) => {
+                send!(
+                    &self.bot,
+                    &self.inline_queries_queue,
+                    query,
+                    span,
+                    UpdateKind::InlineQuery
+                );
+            }
+            UpdateKind::ChosenInlineResult(result) => {
+                send!(
+                    &self.bot,
+                    &self.chosen_inline_results_queue,
+                    result,
+                    span,
+                    UpdateKind::ChosenInlineResult
+                );
+            }
+            UpdateKind::CallbackQuery(query) => {
+                if let (Some(deadline), Some(_)) = (
+                    self.callback_queries_answer_deadline,
+                    &self.callback_queries_queue,
+                ) {
+                    auto_answer(&self.bot, &query.id, deadline);
+                }
+                send!(
+                    &self.bot,
+                    &self.callback_queries_queue,
+                    query,
+                    span,
+                    UpdateKind::CallbackQuer
+                );
+            }
+            UpdateKind::ShippingQuery(query) => {
+                send!(
+                    &self.bot,
+                    &self.shipping_queries_queue,
+                    query,
+                    span,
+                    UpdateKind::ShippingQuery
+                );
+            }
+            UpdateKind::PreCheckoutQuery(query) => {
+                send!(
+                    &self.bot,
+                    &self.pre_checkout_queries_queue,
+                    query,
+                    span,
+                    UpdateKind::PreCheckoutQuery
+                );
+            }
+            UpdateKind::Poll(poll) => {
+                send!(
+                    &self.bot,
+                    &self.polls_queue,
+                    poll,
+                    span,
+                    UpdateKind::Poll
+                );
+            }
+            UpdateKind::PollAnswer(answer) => {
+                send!(
+                    &self.bot,
+                    &self.poll_answers_queue,
+                    answer,
+                    span,
+                    UpdateKind::PollAnswer
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::Replay;
+
+    const ANSWERS: &str = r#"
+{"kind":"request","method_name":"answerCallbackQuery","params":{"callback_query_id":"1"},"response":null}
+{"kind":"request","method_name":"answerCallbackQuery","params":{"callback_query_id":"1"},"response":"{\"ok\":true,\"result\":true}"}
+"#;
+
+    /// Lets spawned tasks run until `replay` gets all the requests.
+    async fn finish(replay: &Replay) {
+        for _ in 0..100 {
+            if replay.is_finished() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        replay.assert_finished();
+    }
+
+    #[tokio::test]
+    async fn auto_answers_after_deadline() {
+        tokio::time::pause();
+        let replay = Replay::from_reader(ANSWERS.trim().as_bytes()).unwrap();
+        let bot = Bot::new("").with_transport(Arc::clone(&replay));
+
+        // The first answer fails, so the query is still answered
+        // automatically.
+        auto_answer(&bot, "1", Duration::from_secs(10));
+        assert!(bot.answer_callback_query("1").send().await.is_err());
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        finish(&replay).await;
+        assert!(!bot.untrack_callback_query("1"));
+    }
+
+    #[tokio::test]
+    async fn answered_queries_are_not_auto_answered() {
+        tokio::time::pause();
+        let replay = Replay::from_reader(
+            ANSWERS.trim().lines().nth(1).unwrap().as_bytes(),
+        )
+        .unwrap();
+        let bot = Bot::new("").with_transport(Arc::clone(&replay));
+
+        auto_answer(&bot, "1", Duration::from_secs(10));
+        bot.answer_callback_query("1").send().await.unwrap();
+
+        // An unexpected automatic answer would fail `assert_finished`.
+        tokio::time::advance(Duration::from_secs(10)).await;
+        for _ in 0..100 {
+            tokio::task::yield_now().await;
+        }
+        replay.assert_finished();
+    }
 }
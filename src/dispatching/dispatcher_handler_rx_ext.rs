@@ -1,7 +1,17 @@
 use crate::{
-    prelude::UpdateWithCx, types::Message, utils::command::BotCommand,
+    dispatching::{media_group, MediaGroup},
+    prelude::UpdateWithCx,
+    types::{CallbackQuery, Message},
+    utils::{
+        callback_data::{CallbackData, CallbackStorage},
+        command::{
+            self, BotCommand, CommandParser, Help, ParseError, RoleGuard,
+            TypedCommand,
+        },
+    },
 };
 use futures::{stream::BoxStream, Stream, StreamExt};
+use std::{sync::Arc, time::Duration};
 
 /// An extension trait to be used with [`DispatcherHandlerRx`].
 ///
@@ -24,6 +34,146 @@ pub trait DispatcherHandlerRxExt {
         Self: Stream<Item = UpdateWithCx<Message>>,
         C: BotCommand,
         N: Into<String> + Send;
+
+    /// Extracts only commands of type `C` from this stream of arbitrary
+    /// messages.
+    ///
+    /// Commands can be addressed to the bot with a leading mention (see
+    /// [`TypedCommand::parse_message`]). Messages which are not commands or
+    /// are addressed to other bots are skipped. Other errors (e.g.
+    /// [`ParseError::UnknownCommand`]) are passed through, so that a handler
+    /// can reply with them.
+    ///
+    /// [`TypedCommand::parse_message`]:
+    /// crate::utils::command::TypedCommand::parse_message
+    /// [`ParseError::UnknownCommand`]:
+    /// crate::utils::command::ParseError::UnknownCommand
+    fn typed_commands<C, N>(
+        self,
+        bot_name: N,
+    ) -> BoxStream<'static, (UpdateWithCx<Message>, Result<C, ParseError>)>
+    where
+        Self: Stream<Item = UpdateWithCx<Message>>,
+        C: TypedCommand + Send + 'static,
+        N: Into<String> + Send;
+
+    /// Like [`DispatcherHandlerRxExt::typed_commands`], but answers
+    /// `/help [command]` with `help` instead of passing it through.
+    ///
+    /// ## Examples
+    /// ```no_run
+    /// # use teloxide::utils::command::{Arguments, ParseError};
+    /// use teloxide::{
+    ///     prelude::*,
+    ///     utils::command::{CommandInfo, Help, HelpFormat, TypedCommand},
+    /// };
+    ///
+    /// enum Command {
+    ///     Roll(u8),
+    /// }
+    ///
+    /// impl TypedCommand for Command {
+    ///     // ...
+    /// #    fn from_parts(
+    /// #        command: &str,
+    /// #        args: Arguments<'_>,
+    /// #    ) -> Result<Self, ParseError> {
+    /// #        args.parse().map(|(max,)| Command::Roll(max))
+    /// #    }
+    ///
+    ///     fn commands() -> Vec<CommandInfo> {
+    ///         vec![
+    ///             CommandInfo::new("help").args_schema("[command]"),
+    ///             CommandInfo::new("roll")
+    ///                 .description("roll a dice.")
+    ///                 .args_schema("<max>"),
+    ///         ]
+    ///     }
+    /// }
+    ///
+    /// # async fn run(rx: DispatcherHandlerRx<Message>) {
+    /// let help = Help::of::<Command>().format(HelpFormat::Html);
+    /// rx.typed_commands_with_help::<Command, _>("MyBot", help)
+    ///     .for_each(|(cx, command)| async move { /* ... */ })
+    ///     .await;
+    /// # }
+    /// ```
+    fn typed_commands_with_help<C, N>(
+        self,
+        bot_name: N,
+        help: Help,
+    ) -> BoxStream<'static, (UpdateWithCx<Message>, Result<C, ParseError>)>
+    where
+        Self: Stream<Item = UpdateWithCx<Message>>,
+        C: TypedCommand + Send + 'static,
+        N: Into<String> + Send;
+
+    /// Like [`DispatcherHandlerRxExt::typed_commands`], but skips commands
+    /// whose callers are not allowed to use them by `guard`.
+    ///
+    /// If `help` is set, `/help [command]` is answered with it like in
+    /// [`DispatcherHandlerRxExt::typed_commands_with_help`].
+    ///
+    /// See [`RoleGuard`] for an example.
+    ///
+    /// [`RoleGuard`]: crate::utils::command::RoleGuard
+    fn typed_commands_with_guard<C, N>(
+        self,
+        bot_name: N,
+        guard: RoleGuard,
+        help: Option<Help>,
+    ) -> BoxStream<'static, (UpdateWithCx<Message>, Result<C, ParseError>)>
+    where
+        Self: Stream<Item = UpdateWithCx<Message>>,
+        C: TypedCommand + Send + 'static,
+        N: Into<String> + Send;
+
+    /// Collects messages sent together as an album into one [`MediaGroup`].
+    ///
+    /// An album is emitted when no messages of it came for `window` (about a
+    /// second is usually enough). Other messages are emitted at once as
+    /// groups of one message, so they can outrun albums sent before them.
+    ///
+    /// ## Examples
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use teloxide::prelude::*;
+    ///
+    /// # async fn run(rx: DispatcherHandlerRx<Message>) {
+    /// rx.media_groups(Duration::from_secs(1))
+    ///     .for_each(|cx| async move {
+    ///         let count = cx.update.messages.len();
+    ///         cx.answer(format!("Got {} files", count)).send().await.ok();
+    ///     })
+    ///     .await;
+    /// # }
+    /// ```
+    fn media_groups(
+        self,
+        window: Duration,
+    ) -> BoxStream<'static, UpdateWithCx<MediaGroup>>
+    where
+        Self: Stream<Item = UpdateWithCx<Message>>;
+
+    /// Extracts only callback queries with data of type `D` together with
+    /// the decoded data.
+    fn callback_data<D>(
+        self,
+    ) -> BoxStream<'static, (UpdateWithCx<CallbackQuery>, D)>
+    where
+        Self: Stream<Item = UpdateWithCx<CallbackQuery>>,
+        D: CallbackData + Send + 'static;
+
+    /// Extracts only callback queries with data of type `D` together with
+    /// the decoded data, loading data from `storage` if needed.
+    fn callback_data_with<D, S>(
+        self,
+        storage: Arc<S>,
+    ) -> BoxStream<'static, (UpdateWithCx<CallbackQuery>, D)>
+    where
+        Self: Stream<Item = UpdateWithCx<CallbackQuery>>,
+        D: CallbackData + Send + 'static,
+        S: CallbackStorage + ?Sized + 'static;
 }
 
 impl<T> DispatcherHandlerRxExt for T
@@ -68,4 +218,174 @@ where
             }
         }))
     }
+
+    fn typed_commands<C, N>(
+        self,
+        bot_name: N,
+    ) -> BoxStream<'static, (UpdateWithCx<Message>, Result<C, ParseError>)>
+    where
+        Self: Stream<Item = UpdateWithCx<Message>>,
+        C: TypedCommand + Send + 'static,
+        N: Into<String> + Send,
+    {
+        let bot_name = bot_name.into();
+
+        Box::pin(self.filter_map(move |cx| {
+            let result = match C::parse_message(&cx.update, &bot_name) {
+                Err(ParseError::NotACommand)
+                | Err(ParseError::WrongBotName(_)) => None,
+                result => Some((cx, result)),
+            };
+            async move { result }
+        }))
+    }
+
+    fn typed_commands_with_help<C, N>(
+        self,
+        bot_name: N,
+        help: Help,
+    ) -> BoxStream<'static, (UpdateWithCx<Message>, Result<C, ParseError>)>
+    where
+        Self: Stream<Item = UpdateWithCx<Message>>,
+        C: TypedCommand + Send + 'static,
+        N: Into<String> + Send,
+    {
+        typed_commands(
+            self,
+            command::parser::<C, _>(bot_name),
+            Some(help),
+            None,
+        )
+    }
+
+    fn typed_commands_with_guard<C, N>(
+        self,
+        bot_name: N,
+        guard: RoleGuard,
+        help: Option<Help>,
+    ) -> BoxStream<'static, (UpdateWithCx<Message>, Result<C, ParseError>)>
+    where
+        Self: Stream<Item = UpdateWithCx<Message>>,
+        C: TypedCommand + Send + 'static,
+        N: Into<String> + Send,
+    {
+        typed_commands(
+            self,
+            command::parser::<C, _>(bot_name),
+            help,
+            Some(guard),
+        )
+    }
+
+    fn media_groups(
+        self,
+        window: Duration,
+    ) -> BoxStream<'static, UpdateWithCx<MediaGroup>>
+    where
+        Self: Stream<Item = UpdateWithCx<Message>>,
+    {
+        let groups = media_group::group_by_key(
+            self,
+            window,
+            |cx: &UpdateWithCx<Message>| {
+                cx.update.media_group_id().map(ToOwned::to_owned)
+            },
+        );
+
+        Box::pin(groups.map(|group| {
+            let bot = Arc::clone(&group[0].bot);
+            let span = group[0].span.clone();
+            let id = group[0].update.media_group_id().map(ToOwned::to_owned);
+            let mut messages: Vec<_> =
+                group.into_iter().map(|cx| cx.update).collect();
+            messages.sort_by_key(|message| message.id);
+
+            UpdateWithCx { bot, update: MediaGroup { id, messages }, span }
+        }))
+    }
+
+    fn callback_data<D>(
+        self,
+    ) -> BoxStream<'static, (UpdateWithCx<CallbackQuery>, D)>
+    where
+        Self: Stream<Item = UpdateWithCx<CallbackQuery>>,
+        D: CallbackData + Send + 'static,
+    {
+        Box::pin(self.filter_map(|cx| async move {
+            let data = D::decode(cx.update.data.as_ref()?)?;
+            Some((cx, data))
+        }))
+    }
+
+    fn callback_data_with<D, S>(
+        self,
+        storage: Arc<S>,
+    ) -> BoxStream<'static, (UpdateWithCx<CallbackQuery>, D)>
+    where
+        Self: Stream<Item = UpdateWithCx<CallbackQuery>>,
+        D: CallbackData + Send + 'static,
+        S: CallbackStorage + ?Sized + 'static,
+    {
+        Box::pin(self.filter_map(move |cx| {
+            let data = cx
+                .update
+                .data
+                .as_ref()
+                .and_then(|data| D::decode_with(data, &*storage));
+            async move { Some((cx, data?)) }
+        }))
+    }
+}
+
+/// Parses each message of `messages` once, answering `/help` with `help` and
+/// checking callers with `guard`, if they are set.
+fn typed_commands<S, C>(
+    messages: S,
+    parser: CommandParser,
+    help: Option<Help>,
+    guard: Option<RoleGuard>,
+) -> BoxStream<'static, (UpdateWithCx<Message>, Result<C, ParseError>)>
+where
+    S: Stream<Item = UpdateWithCx<Message>> + Send + 'static,
+    C: TypedCommand + Send + 'static,
+{
+    let parser = Arc::new(parser);
+    let help = help.map(Arc::new);
+    let guard = guard.map(Arc::new);
+
+    Box::pin(messages.filter_map(move |cx| {
+        let parser = Arc::clone(&parser);
+        let help = help.clone();
+        let guard = guard.clone();
+
+        async move {
+            let command = match parser.parse_message(&cx.update) {
+                Err(ParseError::NotACommand)
+                | Err(ParseError::WrongBotName(_)) => return None,
+                Err(error) => return Some((cx, Err(error))),
+                Ok(command) => command,
+            };
+
+            if let Some(guard) = &guard {
+                if !guard.check(&cx, &command.name).await {
+                    return None;
+                }
+            }
+
+            if let (Some(help), "help") = (&help, command.name.as_ref()) {
+                let topic = command
+                    .args
+                    .parse::<(Option<String>,)>()
+                    .ok()
+                    .and_then(|(topic,)| topic);
+                if let Err(error) = help.reply(&cx, topic.as_deref()).await {
+                    log::error!("Cannot answer /help: {}", error);
+                }
+                return None;
+            }
+
+            let command = command::from_parsed(command);
+            Some((cx, command))
+        }
+    }))
 }
@@ -0,0 +1,412 @@
+use crate::{
+    dispatching::{
+        dialogue::GetChatId, DispatcherHandler, DispatcherHandlerRx,
+        UpdateWithCx,
+    },
+    error_handlers::{ErrorHandler, LoggingErrorHandler},
+    instrumentation,
+    requests::ResponseResult,
+    RequestError,
+};
+use futures::{future::BoxFuture, StreamExt};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::mpsc;
+
+/// A [`DispatcherHandler`] calling an asynchronous function for every update.
+///
+/// Unlike a plain closure consuming [`DispatcherHandlerRx`], `Handler` decides
+/// how updates are executed on its own:
+///
+///  - [`Handler::sequential`] handles updates one by one.
+///  - [`Handler::concurrent`] handles up to `limit` updates at a time.
+///  - [`Handler::per_chat`] handles updates from the same chat sequentially,
+///    but concurrently from different chats, like [`DialogueDispatcher`].
+///
+/// Errors returned by the function are passed to an error handler,
+/// [`LoggingErrorHandler`] by default.
+///
+/// ## Examples
+/// ```no_run
+/// use std::sync::Arc;
+/// use teloxide::{dispatching::Handler, prelude::*};
+///
+/// # async fn run(bot: Arc<Bot>) {
+/// Dispatcher::new(bot)
+///     .messages_handler(Handler::per_chat(|cx: UpdateWithCx<Message>| {
+///         async move {
+///             cx.answer_str("pong").await?;
+///             Ok(())
+///         }
+///     }))
+///     .dispatch()
+///     .await;
+/// # }
+/// ```
+///
+/// [`DialogueDispatcher`]: crate::dispatching::dialogue::DialogueDispatcher
+pub struct Handler<Upd, F, Eh> {
+    f: Arc<F>,
+    mode: Mode<Upd>,
+    error_handler: Arc<Eh>,
+}
+
+enum Mode<Upd> {
+    Concurrent(Option<usize>),
+    PerChat(fn(&Upd) -> i64),
+}
+
+impl<Upd, F, Fut> Handler<Upd, F, LoggingErrorHandler>
+where
+    F: Fn(UpdateWithCx<Upd>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ResponseResult<()>> + Send + 'static,
+{
+    /// Handles updates one by one, in the order they were received.
+    #[must_use]
+    pub fn sequential(f: F) -> Self {
+        Self::new(f, Mode::Concurrent(Some(1)))
+    }
+
+    /// Handles up to `limit` updates at a time, or any number of updates if
+    /// `limit` is `None`.
+    ///
+    /// A limit of 0 is treated as no limit.
+    #[must_use]
+    pub fn concurrent<L>(limit: L, f: F) -> Self
+    where
+        L: Into<Option<usize>>,
+    {
+        Self::new(f, Mode::Concurrent(limit.into()))
+    }
+
+    fn new(f: F, mode: Mode<Upd>) -> Self {
+        Self {
+            f: Arc::new(f),
+            mode,
+            error_handler: LoggingErrorHandler::with_custom_text(
+                "An error from a handler",
+            ),
+        }
+    }
+}
+
+impl<Upd, F, Fut> Handler<Upd, F, LoggingErrorHandler>
+where
+    F: Fn(UpdateWithCx<Upd>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ResponseResult<()>> + Send + 'static,
+    Upd: GetChatId,
+{
+    /// Handles updates from the same chat sequentially, but concurrently from
+    /// different chats.
+    #[must_use]
+    pub fn per_chat(f: F) -> Self {
+        Self::new(f, Mode::PerChat(Upd::chat_id))
+    }
+}
+
+impl<Upd, F, Eh> Handler<Upd, F, Eh> {
+    /// Sets an error handler of errors returned by the function.
+    #[must_use]
+    pub fn error_handler<Eh2>(self, val: Arc<Eh2>) -> Handler<Upd, F, Eh2>
+    where
+        Eh2: ErrorHandler<RequestError>,
+    {
+        Handler { f: self.f, mode: self.mode, error_handler: val }
+    }
+}
+
+impl<Upd, F, Fut, Eh> DispatcherHandler<Upd> for Handler<Upd, F, Eh>
+where
+    F: Fn(UpdateWithCx<Upd>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ResponseResult<()>> + Send + 'static,
+    Eh: ErrorHandler<RequestError> + Send + Sync + 'static,
+    Upd: Send + 'static,
+{
+    fn handle(self, updates: DispatcherHandlerRx<Upd>) -> BoxFuture<'static, ()>
+    where
+        UpdateWithCx<Upd>: Send + 'static,
+    {
+        let Self { f, mode, error_handler } = self;
+
+        match mode {
+            Mode::Concurrent(limit) => {
+                Box::pin(updates.for_each_concurrent(limit, move |cx| {
+                    call(Arc::clone(&f), Arc::clone(&error_handler), cx)
+                }))
+            }
+            Mode::PerChat(chat_id) => {
+                let mut chats = ChatQueues::new(f, error_handler);
+
+                Box::pin(updates.for_each(move |cx| {
+                    chats.push(chat_id(&cx.update), cx);
+                    async {}
+                }))
+            }
+        }
+    }
+}
+
+/// The TX part of an unbounded asynchronous MPSC channel, whose RX part is
+/// handled sequentially by a spawned task, and a number of updates which are
+/// in the channel or being handled.
+type ChatQueue<Upd> =
+    (mpsc::UnboundedSender<UpdateWithCx<Upd>>, Arc<AtomicUsize>);
+
+/// Queues of updates from different chats, handled concurrently.
+///
+/// A queue is dropped, and its task finishes, once all its updates are
+/// handled.
+struct ChatQueues<Upd, F, Eh> {
+    f: Arc<F>,
+    error_handler: Arc<Eh>,
+    queues: HashMap<i64, ChatQueue<Upd>>,
+    // Chats whose queues have become empty. A queue is dropped only by
+    // `push`, so that no update is sent to a finished task.
+    idle_tx: mpsc::UnboundedSender<i64>,
+    idle_rx: mpsc::UnboundedReceiver<i64>,
+    // A number of updates in all the queues.
+    queued: Arc<AtomicUsize>,
+}
+
+impl<Upd, F, Fut, Eh> ChatQueues<Upd, F, Eh>
+where
+    F: Fn(UpdateWithCx<Upd>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ResponseResult<()>> + Send + 'static,
+    Eh: ErrorHandler<RequestError> + Send + Sync + 'static,
+    Upd: Send + 'static,
+{
+    fn new(f: Arc<F>, error_handler: Arc<Eh>) -> Self {
+        let (idle_tx, idle_rx) = mpsc::unbounded_channel();
+        Self {
+            f,
+            error_handler,
+            queues: HashMap::new(),
+            idle_tx,
+            idle_rx,
+            queued: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    fn push(&mut self, chat_id: i64, cx: UpdateWithCx<Upd>) {
+        self.drop_idle();
+        instrumentation::handler_queue_depth(
+            self.queued.fetch_add(1, Ordering::SeqCst) + 1,
+        );
+
+        if !self.queues.contains_key(&chat_id) {
+            let queue = self.spawn(chat_id);
+            self.queues.insert(chat_id, queue);
+        }
+        let (tx, pending) = &self.queues[&chat_id];
+        pending.fetch_add(1, Ordering::SeqCst);
+        if tx.send(cx).is_err() {
+            panic!("We are not dropping a receiver or call .close() on it");
+        }
+    }
+
+    fn drop_idle(&mut self) {
+        while let Ok(chat_id) = self.idle_rx.try_recv() {
+            // New updates could come after the queue had become empty.
+            let idle = match self.queues.get(&chat_id) {
+                Some((_, pending)) => pending.load(Ordering::SeqCst) == 0,
+                None => false,
+            };
+            if idle {
+                self.queues.remove(&chat_id);
+            }
+        }
+    }
+
+    fn spawn(&self, chat_id: i64) -> ChatQueue<Upd> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let pending = Arc::new(AtomicUsize::new(0));
+
+        let f = Arc::clone(&self.f);
+        let error_handler = Arc::clone(&self.error_handler);
+        let queued = Arc::clone(&self.queued);
+        let idle_tx = self.idle_tx.clone();
+        let task_pending = Arc::clone(&pending);
+        tokio::spawn(rx.for_each(move |cx| {
+            instrumentation::handler_queue_depth(
+                queued.fetch_sub(1, Ordering::SeqCst) - 1,
+            );
+
+            let call = call(Arc::clone(&f), Arc::clone(&error_handler), cx);
+            let pending = Arc::clone(&task_pending);
+            let idle_tx = idle_tx.clone();
+            async move {
+                call.await;
+                if pending.fetch_sub(1, Ordering::SeqCst) == 1 {
+                    let _ = idle_tx.send(chat_id);
+                }
+            }
+        }));
+
+        (tx, pending)
+    }
+}
+
+async fn call<Upd, F, Fut, Eh>(
+    f: Arc<F>,
+    error_handler: Arc<Eh>,
+    cx: UpdateWithCx<Upd>,
+) where
+    F: Fn(UpdateWithCx<Upd>) -> Fut,
+    Fut: Future<Output = ResponseResult<()>>,
+    Eh: ErrorHandler<RequestError>,
+{
+    let span = cx.span.clone();
+    span.instrument(async move {
+        if let Err(error) = f(cx).await {
+            error_handler.handle_error(error).await;
+        }
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::Bot;
+    use tokio::time::{delay_for, Duration};
+
+    #[derive(Debug)]
+    struct MyUpdate {
+        chat_id: i64,
+        unique_number: u32,
+    }
+
+    impl GetChatId for MyUpdate {
+        fn chat_id(&self) -> i64 {
+            self.chat_id
+        }
+    }
+
+    fn updates(updates: Vec<(i64, u32)>) -> DispatcherHandlerRx<MyUpdate> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        for (chat_id, unique_number) in updates {
+            let update = MyUpdate { chat_id, unique_number };
+            tx.send(UpdateWithCx::new(Bot::new("Doesn't matter"), update))
+                .unwrap();
+        }
+        rx
+    }
+
+    #[tokio::test]
+    async fn per_chat() {
+        // Delays below run in the virtual time.
+        tokio::time::pause();
+        let (handled_tx, mut handled_rx) = mpsc::unbounded_channel();
+
+        let handler = Handler::per_chat(move |cx: UpdateWithCx<MyUpdate>| {
+            let handled_tx = handled_tx.clone();
+            async move {
+                let MyUpdate { chat_id, unique_number } = cx.update;
+                delay_for(Duration::from_millis(
+                    100 - unique_number as u64 * 10,
+                ))
+                .await;
+                handled_tx.send((chat_id, unique_number)).unwrap();
+                Ok(())
+            }
+        });
+
+        handler
+            .handle(updates(vec![(0, 1), (1, 2), (0, 3), (1, 4), (0, 5)]))
+            .await;
+
+        let mut seqs = vec![Vec::new(); 2];
+        for _ in 0..5 {
+            let (chat_id, unique_number) = handled_rx.recv().await.unwrap();
+            seqs[chat_id as usize].push(unique_number);
+        }
+        assert_eq!(seqs, vec![vec![1, 3, 5], vec![2, 4]]);
+    }
+
+    #[tokio::test]
+    async fn idle_queues_are_dropped() {
+        let (handled_tx, mut handled_rx) = mpsc::unbounded_channel();
+        let f = move |cx: UpdateWithCx<MyUpdate>| {
+            handled_tx.send(cx.update.unique_number).unwrap();
+            async { Ok(()) }
+        };
+        let mut chats =
+            ChatQueues::new(Arc::new(f), LoggingErrorHandler::new());
+        let update = |chat_id, unique_number| {
+            UpdateWithCx::new(
+                Bot::new("Doesn't matter"),
+                MyUpdate { chat_id, unique_number },
+            )
+        };
+
+        chats.push(0, update(0, 1));
+        // The task of chat 0 has marked it idle in the same poll.
+        assert_eq!(handled_rx.recv().await, Some(1));
+
+        chats.push(1, update(1, 2));
+        assert_eq!(chats.queues.keys().collect::<Vec<_>>(), vec![&1]);
+        assert_eq!(handled_rx.recv().await, Some(2));
+
+        // A new queue is created for chat 0.
+        chats.push(0, update(0, 3));
+        assert_eq!(handled_rx.recv().await, Some(3));
+    }
+
+    #[tokio::test]
+    async fn concurrency_limit() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+
+        let handler = {
+            let running = Arc::clone(&running);
+            let max_running = Arc::clone(&max_running);
+            Handler::concurrent(2, move |_: UpdateWithCx<MyUpdate>| {
+                let running = Arc::clone(&running);
+                let max_running = Arc::clone(&max_running);
+                async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now, Ordering::SeqCst);
+                    delay_for(Duration::from_millis(50)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    Ok(())
+                }
+            })
+        };
+
+        handler.handle(updates((0..6).map(|i| (0, i)).collect())).await;
+
+        assert_eq!(max_running.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn errors_are_handled() {
+        let errors = Arc::new(AtomicUsize::new(0));
+
+        let handler =
+            Handler::sequential(|cx: UpdateWithCx<MyUpdate>| async move {
+                if cx.update.unique_number % 2 == 0 {
+                    Err(RequestError::RetryAfter(1))
+                } else {
+                    Ok(())
+                }
+            })
+            .error_handler({
+                let errors = Arc::clone(&errors);
+                Arc::new(move |_: RequestError| {
+                    errors.fetch_add(1, Ordering::SeqCst);
+                    async {}
+                })
+            });
+
+        handler.handle(updates((0..5).map(|i| (0, i)).collect())).await;
+
+        assert_eq!(errors.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,164 @@
+use std::{collections::HashMap, sync::Arc};
+
+use futures::{future, Stream, StreamExt};
+use tokio::{
+    sync::mpsc,
+    time::{delay_until, Duration, Instant},
+};
+
+use crate::{dispatching::dialogue::GetChatId, types::Message};
+
+/// Messages sent together as an album, collected by
+/// [`DispatcherHandlerRxExt::media_groups`].
+///
+/// [`DispatcherHandlerRxExt::media_groups`]:
+/// crate::dispatching::DispatcherHandlerRxExt::media_groups
+#[derive(Debug, Clone)]
+pub struct MediaGroup {
+    /// An identifier of the album, or `None` for a message which is not a part
+    /// of an album.
+    pub id: Option<String>,
+
+    /// Messages of the album in the order they were sent. Never empty.
+    pub messages: Vec<Message>,
+}
+
+impl MediaGroup {
+    /// The first message of the album.
+    pub fn first(&self) -> &Message {
+        &self.messages[0]
+    }
+
+    /// A caption of the album.
+    ///
+    /// Clients attach a caption of an album to one of its messages, usually
+    /// the first one.
+    pub fn caption(&self) -> Option<&str> {
+        self.messages.iter().find_map(Message::caption)
+    }
+}
+
+impl GetChatId for MediaGroup {
+    fn chat_id(&self) -> i64 {
+        self.first().chat.id
+    }
+}
+
+type Pending<T> = Arc<std::sync::Mutex<HashMap<String, (Instant, Vec<T>)>>>;
+
+/// Groups items of `stream` with the same key, emitting a group when no items
+/// of it came for `window`.
+///
+/// Items without a key are emitted at once as groups of one item.
+pub(crate) fn group_by_key<S, T, F>(
+    stream: S,
+    window: Duration,
+    key: F,
+) -> mpsc::UnboundedReceiver<Vec<T>>
+where
+    S: Stream<Item = T> + Send + 'static,
+    T: Send + 'static,
+    F: Fn(&T) -> Option<String> + Send + 'static,
+{
+    let (tx, rx) = mpsc::unbounded_channel();
+    let pending: Pending<T> = Arc::default();
+
+    tokio::spawn(stream.for_each(move |item| {
+        let key = match key(&item) {
+            Some(key) => key,
+            None => {
+                let _ = tx.send(vec![item]);
+                return future::ready(());
+            }
+        };
+
+        let deadline = Instant::now() + window;
+        let mut groups = pending.lock().unwrap();
+        match groups.get_mut(&key) {
+            Some((group_deadline, items)) => {
+                *group_deadline = deadline;
+                items.push(item);
+            }
+            None => {
+                groups.insert(key.clone(), (deadline, vec![item]));
+                tokio::spawn(flush(Arc::clone(&pending), key, tx.clone()));
+            }
+        }
+
+        future::ready(())
+    }));
+
+    rx
+}
+
+/// Emits a group with `key` when its deadline passes.
+async fn flush<T>(
+    pending: Pending<T>,
+    key: String,
+    tx: mpsc::UnboundedSender<Vec<T>>,
+) {
+    loop {
+        let deadline = match pending.lock().unwrap().get(&key) {
+            Some((deadline, _)) => *deadline,
+            None => return,
+        };
+        delay_until(deadline).await;
+
+        let items = {
+            let mut groups = pending.lock().unwrap();
+            match groups.get(&key) {
+                // New items came while waiting.
+                Some((deadline, _)) if *deadline > Instant::now() => None,
+                _ => groups.remove(&key).map(|(_, items)| items),
+            }
+        };
+        if let Some(items) = items {
+            let _ = tx.send(items);
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures::stream;
+    use tokio::time::{self, delay_for};
+
+    #[tokio::test]
+    async fn groups() {
+        // With the paused clock, time advances to the next timer at once, so
+        // the test doesn't depend on the real time.
+        time::pause();
+        let (tx, rx) = mpsc::unbounded_channel();
+        let key =
+            |(key, _): &(Option<&'static str>, u32)| key.map(String::from);
+        let mut groups = group_by_key(rx, Duration::from_millis(100), key);
+
+        tx.send((Some("a"), 1)).unwrap();
+        tx.send((None, 2)).unwrap();
+        tx.send((Some("b"), 3)).unwrap();
+        delay_for(Duration::from_millis(50)).await;
+        tx.send((Some("a"), 4)).unwrap();
+        drop(tx);
+
+        let numbers = |group: Vec<(Option<&'static str>, u32)>| {
+            group.into_iter().map(|(_, number)| number).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(groups.next().await.unwrap()), vec![2]);
+        assert_eq!(numbers(groups.next().await.unwrap()), vec![3]);
+        assert_eq!(numbers(groups.next().await.unwrap()), vec![1, 4]);
+        assert!(groups.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn empty() {
+        let mut groups = group_by_key(
+            stream::empty::<u32>(),
+            Duration::from_millis(100),
+            |_| None,
+        );
+        assert!(groups.next().await.is_none());
+    }
+}
@@ -81,12 +81,16 @@ pub mod dialogue;
 mod dispatcher;
 mod dispatcher_handler;
 mod dispatcher_handler_rx_ext;
+mod handler;
+mod media_group;
 pub mod update_listeners;
 mod update_with_cx;
 
 pub use dispatcher::Dispatcher;
 pub use dispatcher_handler::DispatcherHandler;
 pub use dispatcher_handler_rx_ext::DispatcherHandlerRxExt;
+pub use handler::Handler;
+pub use media_group::MediaGroup;
 use tokio::sync::mpsc::UnboundedReceiver;
 pub use update_with_cx::UpdateWithCx;
 
@@ -1,17 +1,24 @@
 use crate::{
-    dispatching::dialogue::GetChatId,
+    dispatching::{dialogue::GetChatId, MediaGroup},
     requests::{
-        DeleteMessage, EditMessageCaption, EditMessageText, ForwardMessage,
+        AnswerCallbackQuery, DeleteMessage, EditMessageCaption,
+        EditMessageReplyMarkup, EditMessageText, ForwardMessage,
         PinChatMessage, Request, ResponseResult, SendAnimation, SendAudio,
-        SendContact, SendDocument, SendLocation, SendMediaGroup, SendMessage,
-        SendPhoto, SendSticker, SendVenue, SendVideo, SendVideoNote, SendVoice,
-        SetGuildCredit, DeleteGuildCredit, GetPrivateChat, GetGuildRoles, GetGuildMembers,
-        SetMemberRoles, GetChatMember, GetRoleMembers,
+        SendContact, SendDocument, SendLocation, SendLongMessage,
+        SendMediaGroup, SendMessage, SendPhoto, SendSticker, SendVenue,
+        SendVideo, SendVideoNote, SendVoice,
+        SetGuildCredit, DeleteGuildCredit, GetGuildCredit, GetPrivateChat, GetGuildRoles, GetGuildMembers,
+        SetMemberRoles, GetChatMember, GetRoleMembers, GetGuild, GetGuildChannels,
     },
-    types::{ChatId, ChatOrInlineMessage, InputFile, InputMedia, Message, GuildCredit},
+    types::{
+        CallbackQuery, ChatId, ChatOrInlineMessage, GuildCredit, InputFile,
+        InputMedia, Message,
+    },
+    instrumentation::UpdateSpan,
+    utils::formatted::FormattedText,
     Bot,
 };
-use std::sync::Arc;
+use std::{future::Future, sync::Arc};
 use crate::requests::{SearchGuildMember, SearchGuildMemberByUsername};
 
 /// A [`Dispatcher`]'s handler's context of a bot and an update.
@@ -19,11 +26,36 @@ use crate::requests::{SearchGuildMember, SearchGuildMemberByUsername};
 /// See [the module-level documentation for the design
 /// overview](crate::dispatching).
 ///
+/// Outside of the crate, create it with [`UpdateWithCx::new`].
+///
 /// [`Dispatcher`]: crate::dispatching::Dispatcher
+/// [`UpdateWithCx::new`]: crate::dispatching::UpdateWithCx::new
 #[derive(Debug)]
 pub struct UpdateWithCx<Upd> {
     pub bot: Arc<Bot>,
     pub update: Upd,
+    pub(crate) span: UpdateSpan,
+}
+
+impl<Upd> UpdateWithCx<Upd> {
+    pub fn new(bot: Arc<Bot>, update: Upd) -> Self {
+        Self { bot, update, span: UpdateSpan::default() }
+    }
+
+    /// Executes `fut` in the `update` span of the update, if the `tracing`
+    /// feature is enabled.
+    ///
+    /// [`Handler`] does it automatically, this is for handlers consuming
+    /// [`DispatcherHandlerRx`] on their own.
+    ///
+    /// [`Handler`]: crate::dispatching::Handler
+    /// [`DispatcherHandlerRx`]: crate::dispatching::DispatcherHandlerRx
+    pub async fn instrument<Fut>(&self, fut: Fut) -> Fut::Output
+    where
+        Fut: Future,
+    {
+        self.span.clone().instrument(fut).await
+    }
 }
 
 impl<Upd> GetChatId for UpdateWithCx<Upd>
@@ -59,6 +91,24 @@ impl UpdateWithCx<Message> {
             .reply_to_message_id(self.update.id)
     }
 
+    /// Answers with a text rendered for its parse mode, see
+    /// [`FormattedText`].
+    ///
+    /// [`FormattedText`]: crate::utils::formatted::FormattedText
+    pub fn answer_formatted(&self, text: FormattedText) -> SendMessage {
+        self.bot.send_message(self.chat_id(), "").formatted(text)
+    }
+
+    /// Answers with a text of any length, see [`SendLongMessage`].
+    ///
+    /// [`SendLongMessage`]: crate::requests::SendLongMessage
+    pub fn answer_long<T>(&self, text: T) -> SendLongMessage
+    where
+        T: Into<String>,
+    {
+        self.bot.send_long_message(self.chat_id(), text)
+    }
+
     pub fn answer_photo(&self, photo: InputFile) -> SendPhoto {
         self.bot.send_photo(self.update.chat.id, photo)
     }
@@ -181,10 +231,27 @@ impl UpdateWithCx<Message> {
         self.bot.delete_guild_credit(Some(self.update.chat.id), guild_id, user_id)
     }
 
+    pub fn get_guild_credit(&self, guild_id: Option<i64>, user_id: i64) -> GetGuildCredit {
+        self.bot.get_guild_credit(Some(self.update.chat.id), guild_id, user_id)
+    }
+
     pub fn get_private_chat(&self, user_id: i64) -> GetPrivateChat {
         self.bot.get_private_chat(user_id)
     }
 
+    /// Returns a request for the guild of the current chat, if any.
+    pub fn get_guild(&self) -> Option<GetGuild> {
+        self.update.chat.guild().map(|guild_id| self.bot.get_guild(guild_id))
+    }
+
+    /// Returns a request for the channels of the current chat's guild, if any.
+    pub fn get_guild_channels(&self) -> Option<GetGuildChannels> {
+        self.update
+            .chat
+            .guild()
+            .map(|guild_id| self.bot.get_guild_channels(guild_id))
+    }
+
     pub fn get_guild_roles(&self, guild_id: i64) -> GetGuildRoles {
         self.bot.get_guild_roles(guild_id)
     }
@@ -213,3 +280,63 @@ impl UpdateWithCx<Message> {
         self.bot.get_role_members(guild_id, role_id)
     }
 }
+
+impl UpdateWithCx<CallbackQuery> {
+    /// Answers the callback query without a notification.
+    pub fn answer(&self) -> AnswerCallbackQuery {
+        self.bot.answer_callback_query(self.update.id.clone())
+    }
+
+    /// Answers the callback query with an alert showing `text`.
+    pub fn answer_alert<T>(&self, text: T) -> AnswerCallbackQuery
+    where
+        T: Into<String>,
+    {
+        self.answer().text(text).show_alert(true)
+    }
+
+    /// Edits the text of the message with the callback button that
+    /// originated the query.
+    ///
+    /// Returns `None` if the message isn't available.
+    pub fn edit_origin_text<T>(&self, text: T) -> Option<EditMessageText>
+    where
+        T: Into<String>,
+    {
+        let origin = self.update.chat_or_inline_message()?;
+        Some(self.bot.edit_message_text(origin, text))
+    }
+
+    /// Edits the reply markup of the message with the callback button that
+    /// originated the query.
+    ///
+    /// Returns `None` if the message isn't available.
+    pub fn edit_origin_markup(&self) -> Option<EditMessageReplyMarkup> {
+        let origin = self.update.chat_or_inline_message()?;
+        Some(self.bot.edit_message_reply_markup(origin))
+    }
+}
+
+impl UpdateWithCx<MediaGroup> {
+    pub fn answer<T>(&self, text: T) -> SendMessage
+    where
+        T: Into<String>,
+    {
+        self.bot.send_message(self.chat_id(), text)
+    }
+
+    /// Replies to the first message of the album.
+    pub fn reply_to<T>(&self, text: T) -> SendMessage
+    where
+        T: Into<String>,
+    {
+        self.answer(text).reply_to_message_id(self.update.first().id)
+    }
+
+    pub fn answer_media_group<T>(&self, media_group: T) -> SendMediaGroup
+    where
+        T: Into<Vec<InputMedia>>,
+    {
+        self.bot.send_media_group(self.chat_id(), media_group)
+    }
+}
@@ -12,6 +12,19 @@ pub enum DownloadError {
 
     #[error("An I/O error: {0}")]
     Io(#[source] std::io::Error),
+
+    /// The file is larger than a limit set with [`DownloadOptions::max_size`].
+    ///
+    /// [`DownloadOptions::max_size`]: crate::net::DownloadOptions::max_size
+    #[error("The file is larger than {limit} bytes")]
+    #[from(ignore)]
+    TooLarge { limit: u64 },
+
+    /// An error of [`Bot::get_file`] made before downloading a file.
+    ///
+    /// [`Bot::get_file`]: crate::Bot::get_file
+    #[error("A request error: {0}")]
+    Request(#[source] RequestError),
 }
 
 //</editor-fold>
@@ -38,6 +51,18 @@ pub enum RequestError {
 
     #[error("An error while parsing JSON: {0}")]
     InvalidJson(#[source] serde_json::Error),
+
+    /// An error while reading a file to be uploaded or an error of a custom
+    /// [`Transport`].
+    ///
+    /// [`Transport`]: crate::net::Transport
+    #[error("An I/O error: {0}")]
+    Io(#[source] std::io::Error),
+
+    /// A request cannot be made with the given parameters, e.g. a file to be
+    /// uploaded is empty or too large.
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
 }
 
 //</editor-fold>
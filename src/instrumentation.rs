@@ -0,0 +1,441 @@
+//! Optional tracing spans and metrics.
+//!
+//! With the `tracing` feature enabled, every API request is executed in a
+//! `request` span (fields `method`, `status`, `error`, `latency_ms`) and every
+//! update received by [`Dispatcher`] is handled in an `update` span (fields
+//! `id`, `kind`, `guild`, `chat`). The span covers handling of the update by
+//! [`Handler`] and [`DialogueDispatcher`], other handlers can enter it with
+//! [`UpdateWithCx::instrument`].
+//!
+//! With the `metrics` feature enabled, the following metrics are reported
+//! through the [`metrics`] facade:
+//!
+//!  - `teloxide_requests_total` (counter, labels `method`).
+//!  - `teloxide_request_errors_total` (counter, labels `method`, `kind`).
+//!  - `teloxide_request_retries_total` (counter).
+//!  - `teloxide_request_duration_ns` (histogram, labels `method`).
+//!  - `teloxide_updates_total` (counter, labels `kind`).
+//!  - `teloxide_handler_queue_depth` (gauge): updates waiting in the per-chat
+//!    queues of [`Handler::per_chat`].
+//!  - `teloxide_pending_callback_queries` (gauge): callback queries waiting
+//!    for an automatic answer.
+//!
+//! Without these features, all the functions here do nothing.
+//!
+//! [`Dispatcher`]: crate::dispatching::Dispatcher
+//! [`Handler`]: crate::dispatching::Handler
+//! [`DialogueDispatcher`]: crate::dispatching::dialogue::DialogueDispatcher
+//! [`UpdateWithCx::instrument`]:
+//! crate::dispatching::UpdateWithCx::instrument
+//! [`Handler::per_chat`]: crate::dispatching::Handler::per_chat
+//! [`metrics`]: https://docs.rs/metrics
+
+use crate::{
+    requests::ResponseResult,
+    types::{Update, UpdateKind},
+};
+use std::{future::Future, time::Instant};
+
+/// Executes `fut`, a request of `method`, recording its span and metrics.
+pub(crate) async fn request<T, Fut>(method: &str, fut: Fut) -> ResponseResult<T>
+where
+    Fut: Future<Output = ResponseResult<T>>,
+{
+    #[cfg(feature = "tracing")]
+    let span = tracing::debug_span!(
+        "request",
+        method,
+        status = tracing::field::Empty,
+        error = tracing::field::Empty,
+        latency_ms = tracing::field::Empty
+    );
+    #[cfg(feature = "tracing")]
+    let fut = tracing::Instrument::instrument(fut, span.clone());
+
+    let start = Instant::now();
+    let result = fut.await;
+    let latency = start.elapsed();
+
+    #[cfg(feature = "tracing")]
+    {
+        span.record("latency_ms", &(latency.as_millis() as u64));
+        match &result {
+            Ok(_) => {
+                span.record("status", &"ok");
+            }
+            Err(error) => {
+                span.record("status", &"error");
+                span.record("error", &tracing::field::display(error));
+            }
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    {
+        metrics::counter!(
+            "teloxide_requests_total",
+            1,
+            "method" => method.to_owned()
+        );
+        metrics::timing!(
+            "teloxide_request_duration_ns",
+            latency,
+            "method" => method.to_owned()
+        );
+        if let Err(error) = &result {
+            metrics::counter!(
+                "teloxide_request_errors_total",
+                1,
+                "method" => method.to_owned(),
+                "kind" => error_kind(error)
+            );
+        }
+    }
+
+    #[cfg(not(any(feature = "tracing", feature = "metrics")))]
+    let _ = (method, latency);
+
+    result
+}
+
+/// Records a request repeated after [`RequestError::RetryAfter`].
+///
+/// [`RequestError::RetryAfter`]: crate::RequestError::RetryAfter
+pub(crate) fn retry() {
+    #[cfg(feature = "metrics")]
+    metrics::counter!("teloxide_request_retries_total", 1);
+}
+
+/// Sets a number of updates waiting in the per-chat queues of handlers.
+pub(crate) fn handler_queue_depth(depth: usize) {
+    #[cfg(feature = "metrics")]
+    metrics::gauge!("teloxide_handler_queue_depth", depth as i64);
+
+    #[cfg(not(feature = "metrics"))]
+    let _ = depth;
+}
+
+/// Sets a number of callback queries waiting for an automatic answer.
+pub(crate) fn pending_callback_queries(count: usize) {
+    #[cfg(feature = "metrics")]
+    metrics::gauge!("teloxide_pending_callback_queries", count as i64);
+
+    #[cfg(not(feature = "metrics"))]
+    let _ = count;
+}
+
+/// Labels of an update received by [`Dispatcher`].
+///
+/// [`Dispatcher`]: crate::dispatching::Dispatcher
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(
+    not(any(feature = "tracing", feature = "metrics")),
+    allow(dead_code)
+)]
+pub(crate) struct UpdateLabels {
+    id: i64,
+    kind: &'static str,
+    guild_id: Option<i64>,
+    chat_id: Option<i64>,
+}
+
+impl UpdateLabels {
+    pub(crate) fn new(update: &Update) -> Self {
+        let (kind, message) = match &update.kind {
+            UpdateKind::Message(message) => ("message", Some(message)),
+            UpdateKind::EditedMessage(message) => {
+                ("edited_message", Some(message))
+            }
+            UpdateKind::ChannelPost(post) => ("channel_post", Some(post)),
+            UpdateKind::EditedChannelPost(post) => {
+                ("edited_channel_post", Some(post))
+            }
+            UpdateKind::InlineQuery(_) => ("inline_query", None),
+            UpdateKind::ChosenInlineResult(_) => ("chosen_inline_result", None),
+            UpdateKind::CallbackQuery(query) => {
+                ("callback_query", query.message.as_ref())
+            }
+            UpdateKind::ShippingQuery(_) => ("shipping_query", None),
+            UpdateKind::PreCheckoutQuery(_) => ("pre_checkout_query", None),
+            UpdateKind::Poll(_) => ("poll", None),
+            UpdateKind::PollAnswer(_) => ("poll_answer", None),
+        };
+
+        Self {
+            id: update.id,
+            kind,
+            guild_id: message.and_then(|message| message.chat.guild_id),
+            chat_id: message.map(|message| message.chat.id),
+        }
+    }
+
+    /// Records the update and creates its span.
+    pub(crate) fn into_span(self) -> UpdateSpan {
+        #[cfg(feature = "metrics")]
+        metrics::counter!("teloxide_updates_total", 1, "kind" => self.kind);
+
+        UpdateSpan {
+            #[cfg(feature = "tracing")]
+            span: Some(tracing::debug_span!(
+                "update",
+                id = self.id,
+                kind = self.kind,
+                guild = ?self.guild_id,
+                chat = ?self.chat_id
+            )),
+        }
+    }
+}
+
+/// A span of an update, carried by [`UpdateWithCx`] to a handler of the
+/// update.
+///
+/// [`UpdateWithCx`]: crate::dispatching::UpdateWithCx
+#[derive(Debug, Clone, Default)]
+pub(crate) struct UpdateSpan {
+    /// `None` for updates which didn't come from [`Dispatcher`].
+    ///
+    /// [`Dispatcher`]: crate::dispatching::Dispatcher
+    #[cfg(feature = "tracing")]
+    span: Option<tracing::Span>,
+}
+
+impl UpdateSpan {
+    /// Executes `fut` in the span.
+    pub(crate) async fn instrument<Fut>(self, fut: Fut) -> Fut::Output
+    where
+        Fut: Future,
+    {
+        #[cfg(feature = "tracing")]
+        {
+            if let Some(span) = self.span {
+                return tracing::Instrument::instrument(fut, span).await;
+            }
+        }
+
+        fut.await
+    }
+}
+
+/// A low-cardinality name of an error, used as a label of metrics.
+#[cfg(feature = "metrics")]
+fn error_kind(error: &crate::RequestError) -> &'static str {
+    match error {
+        crate::RequestError::ApiError { .. } => "api",
+        crate::RequestError::MigrateToChatId(_) => "migrate_to_chat_id",
+        crate::RequestError::RetryAfter(_) => "retry_after",
+        crate::RequestError::NetworkError(_) => "network",
+        crate::RequestError::InvalidJson(_) => "invalid_json",
+        crate::RequestError::Io(_) => "io",
+        crate::RequestError::InvalidInput(_) => "invalid_input",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message_update() -> Update {
+        serde_json::from_str(
+            r#"{
+                "update_id":1,
+                "message":{
+                    "message_id":2,
+                    "from":{"id":3,"is_bot":false,"first_name":"Ferris"},
+                    "chat":{
+                        "id":3,
+                        "guild_id":7,
+                        "first_name":"Ferris",
+                        "type":"private"
+                    },
+                    "date":0,
+                    "text":"hello"
+                }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn labels() {
+        let labels = UpdateLabels::new(&message_update());
+
+        assert_eq!(labels.id, 1);
+        assert_eq!(labels.kind, "message");
+        assert_eq!(labels.guild_id, Some(7));
+        assert_eq!(labels.chat_id, Some(3));
+    }
+
+    #[cfg(feature = "tracing")]
+    mod spans {
+        use super::*;
+        use crate::{
+            dispatching::{DispatcherHandler, Handler, UpdateWithCx},
+            types::Message,
+            Bot,
+        };
+        use std::{
+            fmt::{self, Write},
+            sync::{Arc, Mutex},
+        };
+        use tokio::sync::mpsc;
+        use tracing::{
+            field::{Field, Visit},
+            span, Event, Metadata, Subscriber,
+        };
+
+        /// A span formatted as `name field=value ...`.
+        struct Fields(String);
+
+        impl Visit for Fields {
+            fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+                write!(self.0, " {}={:?}", field.name(), value).unwrap();
+            }
+        }
+
+        /// Records spans which events are emitted in.
+        #[derive(Clone, Default)]
+        struct Recorder {
+            spans: Arc<Mutex<Vec<String>>>,
+            entered: Arc<Mutex<Vec<usize>>>,
+            events: Arc<Mutex<Vec<Option<String>>>>,
+        }
+
+        impl Subscriber for Recorder {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, attrs: &span::Attributes<'_>) -> span::Id {
+                let mut fields = Fields(attrs.metadata().name().to_owned());
+                attrs.record(&mut fields);
+
+                let mut spans = self.spans.lock().unwrap();
+                spans.push(fields.0);
+                span::Id::from_u64(spans.len() as u64)
+            }
+
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+            fn event(&self, _: &Event<'_>) {
+                let current = self.entered.lock().unwrap().last().copied();
+                let span = current
+                    .map(|id| self.spans.lock().unwrap()[id - 1].clone());
+                self.events.lock().unwrap().push(span);
+            }
+
+            fn enter(&self, id: &span::Id) {
+                self.entered.lock().unwrap().push(id.into_u64() as usize);
+            }
+
+            fn exit(&self, _: &span::Id) {
+                self.entered.lock().unwrap().pop();
+            }
+        }
+
+        #[tokio::test]
+        async fn handler_runs_in_update_span() {
+            let recorder = Recorder::default();
+            let _guard = tracing::subscriber::set_default(recorder.clone());
+
+            let update = message_update();
+            let span = UpdateLabels::new(&update).into_span();
+            let message = match update.kind {
+                UpdateKind::Message(message) => message,
+                _ => unreachable!(),
+            };
+            let (tx, rx) = mpsc::unbounded_channel();
+            let bot = Bot::new("Doesn't matter");
+            tx.send(UpdateWithCx { bot, update: message, span }).unwrap();
+            drop(tx);
+
+            Handler::sequential(|_: UpdateWithCx<Message>| async {
+                tokio::task::yield_now().await;
+                tracing::info!("handling");
+                Ok(())
+            })
+            .handle(rx)
+            .await;
+
+            assert_eq!(
+                *recorder.events.lock().unwrap(),
+                vec![Some(
+                    "update id=1 kind=\"message\" guild=Some(7) chat=Some(3)"
+                        .to_owned()
+                )]
+            );
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    mod metrics_keys {
+        use super::*;
+        use crate::RequestError;
+        use metrics::{Key, Recorder};
+        use std::sync::Mutex;
+
+        lazy_static::lazy_static! {
+            /// Recorded metrics, formatted as `name{label=value,...}`.
+            static ref KEYS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        }
+
+        struct KeysRecorder;
+
+        impl KeysRecorder {
+            fn push(key: Key) {
+                let labels: Vec<_> = key
+                    .labels()
+                    .map(|label| format!("{}={}", label.key(), label.value()))
+                    .collect();
+                let key = format!("{}{{{}}}", key.name(), labels.join(","));
+                KEYS.lock().unwrap().push(key);
+            }
+        }
+
+        impl Recorder for KeysRecorder {
+            fn increment_counter(&self, key: Key, _: u64) {
+                Self::push(key);
+            }
+
+            fn update_gauge(&self, key: Key, _: i64) {
+                Self::push(key);
+            }
+
+            fn record_histogram(&self, key: Key, _: u64) {
+                Self::push(key);
+            }
+        }
+
+        #[tokio::test]
+        async fn labels_of_metrics() {
+            metrics::set_recorder(&KeysRecorder).unwrap();
+
+            request("getMe", async { Ok(()) }).await.unwrap();
+            request::<(), _>("sendMessage", async {
+                Err(RequestError::RetryAfter(1))
+            })
+            .await
+            .unwrap_err();
+            UpdateLabels::new(&message_update()).into_span();
+
+            let keys = KEYS.lock().unwrap();
+            let expected = [
+                "teloxide_requests_total{method=getMe}",
+                "teloxide_request_duration_ns{method=getMe}",
+                "teloxide_request_errors_total{method=sendMessage,\
+                 kind=retry_after}",
+                "teloxide_updates_total{kind=message}",
+            ];
+            for key in expected.iter() {
+                assert!(
+                    keys.iter().any(|k| k == key),
+                    "{} isn't recorded: {:?}",
+                    key,
+                    keys
+                );
+            }
+        }
+    }
+}
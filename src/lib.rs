@@ -4,7 +4,15 @@
 //!
 //! For a high-level overview, see [our GitHub repository](https://github.com/teloxide/teloxide).
 //!
+//! ## Cargo features
+//!  - `tracing` — emit [`tracing`] spans for API requests and dispatched
+//!    updates.
+//!  - `metrics` — report request, error, retry and update counters, request
+//!    latencies and queue depths through the [`metrics`] facade.
+//!
 //! [Telegram bots]: https://telegram.org/blog/bot-revolution
+//! [`tracing`]: https://docs.rs/tracing
+//! [`metrics`]: https://docs.rs/metrics
 //! [`async`/`.await`]: https://rust-lang.github.io/async-book/01_getting_started/01_chapter.html
 //! [Rust]: https://www.rust-lang.org/
 
@@ -27,6 +35,7 @@ pub mod net;
 mod bot;
 pub mod dispatching;
 pub mod error_handlers;
+mod instrumentation;
 mod logging;
 pub mod prelude;
 pub mod requests;
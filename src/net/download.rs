@@ -1,8 +1,18 @@
+use futures::{future, StreamExt};
 use reqwest::Client;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use crate::errors::DownloadError;
 
+use super::{FileStream, ReqwestTransport, Transport};
 
 pub async fn download_file<D>(
     client: &Client,
@@ -10,39 +20,268 @@ pub async fn download_file<D>(
     path: &str,
     destination: &mut D,
 ) -> Result<(), DownloadError>
-    where
-        D: AsyncWrite + Unpin,
+where
+    D: AsyncWrite + Unpin,
 {
-    let mut res = client
-        .get(&super::file_url(token, path))
-        .send()
-        .await?
-        .error_for_status()?;
+    let transport = ReqwestTransport::new(client.clone());
+    download_file_with(&transport, token, path, destination).await
+}
 
-    while let Some(chunk) = res.chunk().await? {
-        destination.write_all(&chunk).await?;
+pub async fn download_file_stream(
+    client: &Client,
+    token: &str,
+    path: &str,
+    options: &DownloadOptions,
+) -> Result<FileStream, DownloadError> {
+    let transport = ReqwestTransport::new(client.clone());
+    download_file_stream_with(&transport, token, path, options).await
+}
+
+/// Options of downloading a file.
+///
+/// ## Examples
+/// ```
+/// use teloxide::net::DownloadOptions;
+///
+/// let options = DownloadOptions::new()
+///     .max_size(20 * 1024 * 1024)
+///     .on_progress(|downloaded| log::debug!("{} bytes", downloaded));
+/// ```
+#[derive(Clone, Default)]
+pub struct DownloadOptions {
+    max_size: Option<u64>,
+    on_progress: Option<Arc<dyn Fn(u64) + Send + Sync>>,
+}
+
+impl DownloadOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stops downloading with [`DownloadError::TooLarge`] if the file is
+    /// larger than `bytes`.
+    ///
+    /// [`DownloadError::TooLarge`]: crate::DownloadError::TooLarge
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
     }
 
+    /// Calls `f` with a number of downloaded bytes after every received chunk.
+    pub fn on_progress<F>(mut self, f: F) -> Self
+    where
+        F: Fn(u64) + Send + Sync + 'static,
+    {
+        self.on_progress = Some(Arc::new(f));
+        self
+    }
+
+    /// Fails if `size` exceeds the limit.
+    pub(crate) fn check_size(&self, size: u64) -> Result<(), DownloadError> {
+        match self.max_size {
+            Some(limit) if size > limit => {
+                Err(DownloadError::TooLarge { limit })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Debug for DownloadOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DownloadOptions")
+            .field("max_size", &self.max_size)
+            .field("on_progress", &self.on_progress.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+/// Like [`download_file`], but downloads the file with `transport`.
+pub(crate) async fn download_file_with<D>(
+    transport: &dyn Transport,
+    token: &str,
+    path: &str,
+    destination: &mut D,
+) -> Result<(), DownloadError>
+where
+    D: AsyncWrite + Unpin,
+{
+    let stream = transport.get_stream(token, path).await?;
+    write_stream(stream, destination, &DownloadOptions::default()).await?;
     Ok(())
 }
 
-#[cfg(feature = "unstable-stream")]
-pub async fn download_file_stream(
-    client: &Client,
+/// Like [`download_file_stream`], but downloads the file with `transport`.
+pub(crate) async fn download_file_stream_with(
+    transport: &dyn Transport,
     token: &str,
     path: &str,
-) -> Result<impl Stream<Item=reqwest::Result<Bytes>>, reqwest::Error> {
-    let res = client
-        .get(&super::file_url(TELEGRAM_API_URL, token, path))
-        .send()
-        .await?
-        .error_for_status()?;
-
-    Ok(futures::stream::unfold(res, |mut res| async {
-        match res.chunk().await {
-            Err(err) => Some((Err(err), res)),
-            Ok(Some(c)) => Some((Ok(c), res)),
-            Ok(None) => None,
+    options: &DownloadOptions,
+) -> Result<FileStream, DownloadError> {
+    let stream = transport.get_stream(token, path).await?;
+    Ok(limit_stream(stream, options.clone()))
+}
+
+/// Downloads a file into `destination` through a temporary file in the same
+/// directory, so that `destination` never contains a partially downloaded
+/// file.
+///
+/// Returns a size of the file.
+pub(crate) async fn download_file_to_path_with(
+    transport: &dyn Transport,
+    token: &str,
+    path: &str,
+    destination: &Path,
+    options: &DownloadOptions,
+) -> Result<u64, DownloadError> {
+    let temp = temp_path(destination);
+
+    let result = async {
+        let stream = transport.get_stream(token, path).await?;
+        let mut file = tokio::fs::File::create(&temp).await?;
+        let size = write_stream(stream, &mut file, options).await?;
+        file.sync_all().await?;
+        drop(file);
+
+        tokio::fs::rename(&temp, destination).await?;
+        Ok::<_, DownloadError>(size)
+    }
+    .await;
+
+    if result.is_err() {
+        if let Err(error) = tokio::fs::remove_file(&temp).await {
+            log::debug!("Cannot remove {}: {}", temp.display(), error);
+        }
+    }
+    result
+}
+
+/// Writes `stream` into `destination`, returning a number of written bytes.
+pub(crate) async fn write_stream<D>(
+    mut stream: FileStream,
+    destination: &mut D,
+    options: &DownloadOptions,
+) -> Result<u64, DownloadError>
+where
+    D: AsyncWrite + Unpin,
+{
+    let mut size = 0;
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        size += chunk.len() as u64;
+        options.check_size(size)?;
+
+        destination.write_all(&chunk).await?;
+        if let Some(on_progress) = &options.on_progress {
+            on_progress(size);
         }
+    }
+
+    destination.flush().await?;
+    Ok(size)
+}
+
+/// Applies `options` to chunks of `stream`, which ends after the first error.
+fn limit_stream(stream: FileStream, options: DownloadOptions) -> FileStream {
+    Box::pin(stream.scan((0, false), move |(size, failed), chunk| {
+        if *failed {
+            return future::ready(None);
+        }
+
+        let chunk = chunk.and_then(|chunk| {
+            *size += chunk.len() as u64;
+            options.check_size(*size)?;
+            if let Some(on_progress) = &options.on_progress {
+                on_progress(*size);
+            }
+            Ok(chunk)
+        });
+        *failed = chunk.is_err();
+        future::ready(Some(chunk))
     }))
 }
+
+/// A unique path of a temporary file next to `destination`.
+fn temp_path(destination: &Path) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let name = destination.file_name().unwrap_or_default().to_string_lossy();
+    destination.with_file_name(format!(
+        ".{}.{}-{}.part",
+        name,
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use futures::stream;
+    use std::sync::Mutex;
+
+    fn chunks(chunks: &[&'static str]) -> FileStream {
+        let chunks: Vec<_> =
+            chunks.iter().map(|chunk| Ok(Bytes::from(*chunk))).collect();
+        Box::pin(stream::iter(chunks))
+    }
+
+    #[tokio::test]
+    async fn progress() {
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let options = DownloadOptions::new().on_progress({
+            let progress = Arc::clone(&progress);
+            move |size| progress.lock().unwrap().push(size)
+        });
+
+        let mut bytes = Vec::new();
+        let size = write_stream(chunks(&["ab", "cde"]), &mut bytes, &options)
+            .await
+            .unwrap();
+
+        assert_eq!(size, 5);
+        assert_eq!(bytes, b"abcde");
+        assert_eq!(*progress.lock().unwrap(), vec![2, 5]);
+    }
+
+    #[tokio::test]
+    async fn max_size() {
+        let options = DownloadOptions::new().max_size(4);
+
+        let mut bytes = Vec::new();
+        match write_stream(chunks(&["ab", "cde"]), &mut bytes, &options).await {
+            Err(DownloadError::TooLarge { limit: 4 }) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(bytes, b"ab");
+    }
+
+    #[tokio::test]
+    async fn limited_stream() {
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let options = DownloadOptions::new().max_size(4).on_progress({
+            let progress = Arc::clone(&progress);
+            move |size| progress.lock().unwrap().push(size)
+        });
+
+        let mut stream = limit_stream(chunks(&["ab", "cde", "f"]), options);
+        assert_eq!(stream.next().await.unwrap().unwrap(), "ab");
+        match stream.next().await {
+            Some(Err(DownloadError::TooLarge { limit: 4 })) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(stream.next().await.is_none());
+        assert_eq!(*progress.lock().unwrap(), vec![2]);
+    }
+
+    #[test]
+    fn temp_paths() {
+        let first = temp_path(Path::new("/tmp/files/photo.jpg"));
+        let second = temp_path(Path::new("/tmp/files/photo.jpg"));
+
+        assert_ne!(first, second);
+        assert_eq!(first.parent(), Some(Path::new("/tmp/files")));
+        assert!(first.to_string_lossy().ends_with(".part"));
+    }
+}
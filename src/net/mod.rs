@@ -1,15 +1,26 @@
-#[cfg(feature = "unstable-stream")]
-pub use download::download_file_stream;
-
 pub use self::{
-    download::download_file,
+    download::{download_file, download_file_stream, DownloadOptions},
+    observer::{ObservedRequest, RedactingLogger, RequestObserver},
+    replay::{Recorder, Replay},
     request::{request_json, request_multipart},
     telegram_response::TelegramResponse,
+    transport::{FileStream, ReqwestTransport, Transport},
+};
+
+pub(crate) use self::{
+    download::{
+        download_file_stream_with, download_file_to_path_with,
+        download_file_with, write_stream,
+    },
+    request::{request_json_with, request_multipart_with},
 };
 
 mod download;
+mod observer;
+mod replay;
 mod request;
 mod telegram_response;
+mod transport;
 
 use std::env;
 
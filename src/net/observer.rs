@@ -0,0 +1,168 @@
+use serde_json::Value;
+use std::{collections::HashSet, time::Duration};
+
+/// A request made by [`Bot`], passed to a [`RequestObserver`] after a response
+/// is received.
+///
+/// [`Bot`]: crate::Bot
+#[derive(Debug, Clone, Copy)]
+pub struct ObservedRequest<'a> {
+    /// A name of the called method, e.g. `sendMessage`.
+    pub method_name: &'a str,
+
+    /// Serialized JSON parameters, or `None` for requests with files (sent as
+    /// `multipart/form-data`).
+    pub params: Option<&'a str>,
+
+    /// A raw body of the response, or `None` if a response wasn't received
+    /// because of a network error.
+    pub response: Option<&'a str>,
+
+    /// Time from sending the request to receiving the whole response.
+    pub elapsed: Duration,
+}
+
+/// An observer of raw API traffic of [`Bot`], set by [`Bot::with_observer`].
+///
+/// Useful for debugging, audits and recording fixtures. For a logger which
+/// hides secrets, see [`RedactingLogger`].
+///
+/// [`Bot`]: crate::Bot
+/// [`Bot::with_observer`]: crate::Bot::with_observer
+pub trait RequestObserver: Send + Sync {
+    fn observe(&self, request: &ObservedRequest<'_>);
+}
+
+impl<F> RequestObserver for F
+where
+    F: Fn(&ObservedRequest<'_>) + Send + Sync,
+{
+    fn observe(&self, request: &ObservedRequest<'_>) {
+        self(request)
+    }
+}
+
+/// A [`RequestObserver`] logging requests with the `debug` level.
+///
+/// The bot's token and values of the `user_token` fields (see
+/// [`User::user_token`]) are replaced with `***`. Other fields can be hidden
+/// with [`RedactingLogger::redact_field`].
+///
+/// ## Examples
+/// ```
+/// use teloxide::{net::RedactingLogger, Bot};
+///
+/// let token = "123:secret";
+/// let bot = Bot::new(token)
+///     .with_observer(RedactingLogger::new(token).redact_field("phone"));
+/// ```
+///
+/// [`User::user_token`]: crate::types::User::user_token
+#[derive(Debug, Clone)]
+pub struct RedactingLogger {
+    token: String,
+    fields: HashSet<String>,
+}
+
+impl RedactingLogger {
+    const MASK: &'static str = "***";
+
+    /// Creates a logger hiding `token`.
+    pub fn new<T>(token: T) -> Self
+    where
+        T: Into<String>,
+    {
+        let mut fields = HashSet::new();
+        fields.insert("user_token".to_owned());
+        Self { token: token.into(), fields }
+    }
+
+    /// Hides values of fields named `name` (at any depth) as well.
+    pub fn redact_field<N>(mut self, name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        self.fields.insert(name.into());
+        self
+    }
+
+    /// Hides the secrets in `text`, usually a JSON document.
+    pub fn redact(&self, text: &str) -> String {
+        let text = match serde_json::from_str::<Value>(text) {
+            Ok(mut value) => {
+                self.redact_value(&mut value);
+                value.to_string()
+            }
+            Err(_) => text.to_owned(),
+        };
+
+        if self.token.is_empty() {
+            text
+        } else {
+            text.replace(&self.token, Self::MASK)
+        }
+    }
+
+    fn redact_value(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (name, value) in map.iter_mut() {
+                    if self.fields.contains(name) && !value.is_null() {
+                        *value = Value::String(Self::MASK.to_owned());
+                    } else {
+                        self.redact_value(value);
+                    }
+                }
+            }
+            Value::Array(values) => {
+                values.iter_mut().for_each(|value| self.redact_value(value))
+            }
+            _ => {}
+        }
+    }
+}
+
+impl RequestObserver for RedactingLogger {
+    fn observe(&self, request: &ObservedRequest<'_>) {
+        log::debug!(
+            "{} ({:?}): params={}, response={}",
+            request.method_name,
+            request.elapsed,
+            request.params.map_or("<multipart>".into(), |p| self.redact(p)),
+            request
+                .response
+                .map_or("<network error>".into(), |r| self.redact(r)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_token_and_fields() {
+        let logger = RedactingLogger::new("123:secret").redact_field("phone");
+
+        let redacted = logger.redact(
+            r#"{"ok":true,"result":{"user":{"id":1,"user_token":"abc","phone":"+1"},"members":[{"user_token":"def"},{"user_token":null}],"text":"token is 123:secret"}}"#,
+        );
+        let value: Value = serde_json::from_str(&redacted).unwrap();
+
+        assert_eq!(value["result"]["user"]["user_token"], "***");
+        assert_eq!(value["result"]["user"]["phone"], "***");
+        assert_eq!(value["result"]["user"]["id"], 1);
+        assert_eq!(value["result"]["members"][0]["user_token"], "***");
+        assert_eq!(value["result"]["members"][1]["user_token"], Value::Null);
+        assert_eq!(value["result"]["text"], "token is ***");
+    }
+
+    #[test]
+    fn redacts_non_json() {
+        let logger = RedactingLogger::new("123:secret");
+        assert_eq!(
+            logger.redact("<html>123:secret</html>"),
+            "<html>***</html>"
+        );
+    }
+}
@@ -0,0 +1,433 @@
+use crate::{
+    dispatching::update_listeners::UpdateListener,
+    net::{
+        FileStream, ObservedRequest, RedactingLogger, RequestObserver,
+        Transport,
+    },
+    types::Update,
+    DownloadError, RequestError,
+};
+use futures::stream;
+use reqwest::multipart::Form;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// A line of a recording.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Entry {
+    Request {
+        method_name: String,
+        params: Option<Value>,
+        response: Option<String>,
+    },
+    Update {
+        update: Value,
+    },
+}
+
+/// A [`RequestObserver`] writing requests of [`Bot`] as JSON lines, to be
+/// replayed later by [`Replay`].
+///
+/// Updates received with `getUpdates` are recorded as part of its responses.
+/// Updates received otherwise (e.g. with webhooks) can be recorded with
+/// [`Recorder::record_update`].
+///
+/// ## Examples
+/// ```no_run
+/// use teloxide::{net::Recorder, prelude::*};
+///
+/// # async fn run() -> std::io::Result<()> {
+/// let recorder = Recorder::create("session.jsonl")?;
+/// let bot = Bot::from_env().with_observer(recorder);
+/// // Use `bot` as usual...
+/// # Ok(()) }
+/// ```
+///
+/// [`Bot`]: crate::Bot
+#[derive(Clone)]
+pub struct Recorder {
+    out: Arc<Mutex<Box<dyn Write + Send>>>,
+    redactor: Option<RedactingLogger>,
+}
+
+impl Recorder {
+    /// Creates a recorder writing to a new file at `path`.
+    pub fn create<P>(path: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+
+    /// Creates a recorder writing to `out`.
+    pub fn new<W>(out: W) -> Self
+    where
+        W: Write + Send + 'static,
+    {
+        Self { out: Arc::new(Mutex::new(Box::new(out))), redactor: None }
+    }
+
+    /// Hides secrets in recorded requests with `redactor`.
+    ///
+    /// Note that requests are replayed with the hidden values.
+    pub fn redact_with(mut self, redactor: RedactingLogger) -> Self {
+        self.redactor = Some(redactor);
+        self
+    }
+
+    /// Records a raw update which is received without [`Bot`].
+    ///
+    /// [`Bot`]: crate::Bot
+    pub fn record_update(&self, update: &Value) {
+        let update = match &self.redactor {
+            Some(redactor) => {
+                serde_json::from_str(&redactor.redact(&update.to_string()))
+                    .unwrap_or_else(|_| update.clone())
+            }
+            None => update.clone(),
+        };
+        self.write(&Entry::Update { update });
+    }
+
+    fn redact(&self, text: &str) -> String {
+        match &self.redactor {
+            Some(redactor) => redactor.redact(text),
+            None => text.to_owned(),
+        }
+    }
+
+    fn write(&self, entry: &Entry) {
+        let mut out = self.out.lock().unwrap();
+        let result = serde_json::to_writer(&mut *out, entry)
+            .map_err(io::Error::from)
+            .and_then(|()| writeln!(out))
+            .and_then(|()| out.flush());
+
+        if let Err(error) = result {
+            log::error!("Cannot record a request: {}", error);
+        }
+    }
+}
+
+impl RequestObserver for Recorder {
+    fn observe(&self, request: &ObservedRequest<'_>) {
+        let params = request.params.map(|params| {
+            let params = self.redact(params);
+            serde_json::from_str(&params).unwrap_or(Value::String(params))
+        });
+
+        self.write(&Entry::Request {
+            method_name: request.method_name.to_owned(),
+            params,
+            response: request.response.map(|response| self.redact(response)),
+        });
+    }
+}
+
+/// A request expected by [`Replay`].
+#[derive(Debug)]
+struct Expected {
+    method_name: String,
+    params: Option<Value>,
+    response: Option<String>,
+}
+
+/// A session recorded by [`Recorder`], replayed without network access.
+///
+/// [`Replay::updates`] yields the recorded updates, and `Replay` used as a
+/// [`Transport`] answers requests with the recorded responses, in the recorded
+/// order. Unexpected requests fail with [`RequestError::ApiError`]
+/// and are reported by [`Replay::assert_finished`].
+///
+/// ## Examples
+/// ```no_run
+/// use std::time::Duration;
+/// use teloxide::{net::Replay, prelude::*};
+///
+/// # async fn run() -> std::io::Result<()> {
+/// let replay = Replay::open("tests/fixtures/session.jsonl")?;
+/// let bot = Bot::new("").with_transport(replay.clone());
+///
+/// Dispatcher::new(bot)
+///     .messages_handler(|rx: DispatcherHandlerRx<Message>| {
+///         rx.for_each(|cx| async move {
+///             cx.answer_str("pong").await.log_on_error().await;
+///         })
+///     })
+///     .dispatch_with_listener(replay.updates(), LoggingErrorHandler::new())
+///     .await;
+///
+/// replay.wait_finished(Duration::from_secs(5)).await;
+/// # Ok(()) }
+/// ```
+///
+/// [`RequestError::ApiError`]: crate::RequestError::ApiError
+#[derive(Debug)]
+pub struct Replay {
+    updates: Vec<Value>,
+    requests: Mutex<VecDeque<Expected>>,
+    unexpected: Mutex<Vec<String>>,
+}
+
+impl Replay {
+    /// Reads a recording from a file at `path`.
+    pub fn open<P>(path: P) -> io::Result<Arc<Self>>
+    where
+        P: AsRef<Path>,
+    {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads a recording from `reader`.
+    pub fn from_reader<R>(reader: R) -> io::Result<Arc<Self>>
+    where
+        R: BufRead,
+    {
+        let mut updates = Vec::new();
+        let mut requests = VecDeque::new();
+
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match serde_json::from_str(&line).map_err(io::Error::from)? {
+                Entry::Update { update } => updates.push(update),
+                Entry::Request { method_name, response, .. }
+                    if method_name == "getUpdates" =>
+                {
+                    updates.extend(
+                        response
+                            .and_then(|r| received_updates(&r))
+                            .unwrap_or_default(),
+                    )
+                }
+                Entry::Request { method_name, params, response } => requests
+                    .push_back(Expected { method_name, params, response }),
+            }
+        }
+
+        Ok(Arc::new(Self {
+            updates,
+            requests: Mutex::new(requests),
+            unexpected: Mutex::new(Vec::new()),
+        }))
+    }
+
+    /// Yields the recorded updates.
+    pub fn updates(&self) -> impl UpdateListener<serde_json::Error> {
+        stream::iter(
+            self.updates.iter().map(Update::try_parse).collect::<Vec<_>>(),
+        )
+    }
+
+    /// Returns `true` if all the recorded requests are made.
+    pub fn is_finished(&self) -> bool {
+        self.requests.lock().unwrap().is_empty()
+    }
+
+    /// Panics if there were unexpected requests or some recorded requests are
+    /// not made.
+    pub fn assert_finished(&self) {
+        let unexpected = self.unexpected.lock().unwrap();
+        let requests = self.requests.lock().unwrap();
+
+        if !unexpected.is_empty() || !requests.is_empty() {
+            panic!(
+                "The replay has failed.\nUnexpected requests: {:#?}\nMissing \
+                 requests: {:#?}",
+                unexpected,
+                requests
+                    .iter()
+                    .map(|expected| &expected.method_name)
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    /// Waits until all the recorded requests are made, but no longer than
+    /// `timeout`, and calls [`Replay::assert_finished`].
+    ///
+    /// Useful when requests are made by handlers still running after
+    /// dispatching is finished.
+    pub async fn wait_finished(&self, timeout: Duration) {
+        let deadline = Instant::now() + timeout;
+        while !self.is_finished() && Instant::now() < deadline {
+            tokio::time::delay_for(Duration::from_millis(10)).await;
+        }
+        self.assert_finished();
+    }
+
+    /// Returns a raw response to a request, or an error response if the
+    /// request is unexpected.
+    fn respond(&self, method_name: &str, params: Option<&str>) -> String {
+        let mut requests = self.requests.lock().unwrap();
+
+        let matches = match requests.front() {
+            Some(expected) => {
+                expected.method_name == method_name
+                    && (expected.params.is_none()
+                        || expected.params
+                            == params
+                                .and_then(|p| serde_json::from_str(p).ok()))
+            }
+            None => false,
+        };
+
+        if !matches {
+            let description = format!(
+                "Unexpected request {} with {}",
+                method_name,
+                params.unwrap_or("multipart parameters")
+            );
+            self.unexpected.lock().unwrap().push(description.clone());
+            return error_response(&description);
+        }
+
+        let expected = requests.pop_front().unwrap();
+        expected.response.unwrap_or_else(|| {
+            error_response("The recorded request has failed")
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Transport for Replay {
+    async fn send_json(
+        &self,
+        _token: &str,
+        method_name: &str,
+        params: String,
+    ) -> Result<String, RequestError> {
+        Ok(self.respond(method_name, Some(&params)))
+    }
+
+    async fn send_multipart(
+        &self,
+        _token: &str,
+        method_name: &str,
+        _params: Form,
+    ) -> Result<String, RequestError> {
+        Ok(self.respond(method_name, None))
+    }
+
+    async fn get_stream(
+        &self,
+        _token: &str,
+        path: &str,
+    ) -> Result<FileStream, DownloadError> {
+        let description = format!("Unexpected download of {}", path);
+        self.unexpected.lock().unwrap().push(description.clone());
+        Err(DownloadError::Io(io::Error::new(
+            io::ErrorKind::Other,
+            description,
+        )))
+    }
+}
+
+/// Extracts updates from a raw response to `getUpdates`.
+fn received_updates(response: &str) -> Option<Vec<Value>> {
+    match serde_json::from_str::<Value>(response).ok()?.get_mut("result")? {
+        Value::Array(updates) => Some(std::mem::take(updates)),
+        _ => None,
+    }
+}
+
+fn error_response(description: &str) -> String {
+    serde_json::json!({
+        "ok": false,
+        "error_code": 500,
+        "description": description,
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECORDING: &str = r#"
+{"kind":"request","method_name":"getUpdates","params":{"offset":0},"response":"{\"ok\":true,\"result\":[{\"update_id\":1},{\"update_id\":2}]}"}
+{"kind":"request","method_name":"sendMessage","params":{"chat_id":1,"text":"hi"},"response":"{\"ok\":true,\"result\":true}"}
+{"kind":"update","update":{"update_id":3}}
+{"kind":"request","method_name":"deleteMessage","params":{"chat_id":1,"message_id":2},"response":null}
+"#;
+
+    #[test]
+    fn reads_updates() {
+        let replay = Replay::from_reader(RECORDING.trim().as_bytes()).unwrap();
+        let ids: Vec<_> =
+            replay.updates.iter().map(|u| u["update_id"].clone()).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn answers_requests_in_order() {
+        let replay = Replay::from_reader(RECORDING.trim().as_bytes()).unwrap();
+
+        assert_eq!(
+            replay.respond("sendMessage", Some(r#"{"text":"hi","chat_id":1}"#)),
+            r#"{"ok":true,"result":true}"#
+        );
+        assert!(replay
+            .respond("deleteMessage", Some(r#"{"chat_id":1,"message_id":2}"#))
+            .contains(r#""ok":false"#));
+        assert!(replay.is_finished());
+        replay.assert_finished();
+    }
+
+    #[test]
+    #[should_panic(expected = "Unexpected requests")]
+    fn reports_unexpected_requests() {
+        let replay = Replay::from_reader(RECORDING.trim().as_bytes()).unwrap();
+
+        let response =
+            replay.respond("sendMessage", Some(r#"{"chat_id":1,"text":"?"}"#));
+        assert!(response.contains("Unexpected request sendMessage"));
+        replay.assert_finished();
+    }
+
+    #[test]
+    fn records_requests() {
+        #[derive(Clone, Default)]
+        struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+        impl Write for Buffer {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let buffer = Buffer::default();
+        let recorder = Recorder::new(buffer.clone())
+            .redact_with(RedactingLogger::new("123:secret"));
+        recorder.observe(&ObservedRequest {
+            method_name: "getMe",
+            params: Some("{}"),
+            response: Some(r#"{"ok":true,"result":{"user_token":"t"}}"#),
+            elapsed: Duration::from_millis(1),
+        });
+
+        let recorded =
+            String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let replay = Replay::from_reader(recorded.as_bytes()).unwrap();
+        assert_eq!(
+            replay.respond("getMe", Some("{}")),
+            r#"{"ok":true,"result":{"user_token":"***"}}"#
+        );
+    }
+}
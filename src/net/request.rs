@@ -1,9 +1,13 @@
-use reqwest::{multipart::Form, Client, Response};
+use reqwest::{multipart::Form, Client};
 use serde::{de::DeserializeOwned, Serialize};
+use std::time::Instant;
 
-use crate::{requests::ResponseResult, RequestError};
+use crate::{instrumentation, requests::ResponseResult, RequestError};
 
-use super::TelegramResponse;
+use super::{
+    ObservedRequest, RequestObserver, ReqwestTransport, TelegramResponse,
+    Transport,
+};
 
 pub async fn request_multipart<T>(
     client: &Client,
@@ -11,17 +15,11 @@ pub async fn request_multipart<T>(
     method_name: &str,
     params: Form,
 ) -> ResponseResult<T>
-    where
-        T: DeserializeOwned,
+where
+    T: DeserializeOwned,
 {
-    let response = client
-        .post(&super::method_url(token, method_name))
-        .multipart(params)
-        .send()
-        .await
-        .map_err(RequestError::NetworkError)?;
-
-    process_response(response).await
+    let transport = ReqwestTransport::new(client.clone());
+    request_multipart_with(&transport, token, method_name, params, None).await
 }
 
 pub async fn request_json<T, P>(
@@ -30,50 +28,94 @@ pub async fn request_json<T, P>(
     method_name: &str,
     params: &P,
 ) -> ResponseResult<T>
-    where
-        T: DeserializeOwned + Serialize + std::fmt::Debug + Clone,
-        P: Serialize,
+where
+    T: DeserializeOwned,
+    P: Serialize,
 {
-    let response = client
-        .post(&super::method_url(token, method_name))
-        .json(params)
-        .send()
-        .await
-        .map_err(RequestError::NetworkError)?;
+    let transport = ReqwestTransport::new(client.clone());
+    request_json_with(&transport, token, method_name, params, None).await
+}
 
-    match process_response(response).await {
-        Ok(v) => {
-            // let req = serde_json::to_string(&params).unwrap();
-            // let res = serde_json::to_string(&v).unwrap();
-            // println!("edwin 43, {} req, {}", method_name, req);
-            // println!("edwin 44, {} res, {}", method_name, res);
-            Ok(v)
-        }
-        Err(err) => {
-            Err(err)
-        }
-    }
+/// Like [`request_multipart`], but sends the request with `transport` and
+/// passes it to `observer`.
+///
+/// Multipart parameters are not passed to the observer.
+pub(crate) async fn request_multipart_with<T>(
+    transport: &dyn Transport,
+    token: &str,
+    method_name: &str,
+    params: Form,
+    observer: Option<&dyn RequestObserver>,
+) -> ResponseResult<T>
+where
+    T: DeserializeOwned,
+{
+    instrumentation::request(method_name, async {
+        let start = Instant::now();
+        let body = transport.send_multipart(token, method_name, params).await;
+        observe(observer, method_name, None, &body, start);
+        parse_response(method_name, &body?)
+    })
+    .await
 }
 
-async fn process_response<T>(response: Response) -> ResponseResult<T>
-    where
-        T: DeserializeOwned,
+/// Like [`request_json`], but sends the request with `transport` and passes
+/// it to `observer`.
+pub(crate) async fn request_json_with<T, P>(
+    transport: &dyn Transport,
+    token: &str,
+    method_name: &str,
+    params: &P,
+    observer: Option<&dyn RequestObserver>,
+) -> ResponseResult<T>
+where
+    T: DeserializeOwned,
+    P: Serialize,
 {
-    let url = response.url().to_string();
-    let s = &response.text().await.map_err(RequestError::NetworkError)?;
+    let params =
+        serde_json::to_string(params).map_err(RequestError::InvalidJson)?;
 
-    match serde_json::from_str::<TelegramResponse<T>>(s) {
-        Ok(v) => {
-            // log::debug!("url={}, body={}", url, s);
-            v.into() }
+    instrumentation::request(method_name, async {
+        let start = Instant::now();
+        let body =
+            transport.send_json(token, method_name, params.clone()).await;
+        observe(observer, method_name, Some(&params), &body, start);
+        parse_response(method_name, &body?)
+    })
+    .await
+}
+
+fn observe(
+    observer: Option<&dyn RequestObserver>,
+    method_name: &str,
+    params: Option<&str>,
+    body: &Result<String, RequestError>,
+    start: Instant,
+) {
+    if let Some(observer) = observer {
+        observer.observe(&ObservedRequest {
+            method_name,
+            params,
+            response: body.as_ref().ok().map(String::as_str),
+            elapsed: start.elapsed(),
+        });
+    }
+}
+
+fn parse_response<T>(method_name: &str, body: &str) -> ResponseResult<T>
+where
+    T: DeserializeOwned,
+{
+    match serde_json::from_str::<TelegramResponse<T>>(body) {
+        Ok(v) => v.into(),
         Err(err) => {
-            log::error!("url={}, body={}, err={}", url, s, err.to_string());
+            log::error!(
+                "method={}, body={}, err={}",
+                method_name,
+                body,
+                err.to_string()
+            );
             Err(RequestError::InvalidJson(err))
         }
     }
-
-    // let x = serde_json::from_str::<TelegramResponse<T>>(s)
-    //     .map_err(RequestError::InvalidJson)?
-    //     .into();
-    // x
 }
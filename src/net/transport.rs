@@ -0,0 +1,215 @@
+use bytes::Bytes;
+use futures::{stream::BoxStream, TryStreamExt};
+use reqwest::{header::CONTENT_TYPE, multipart::Form, Client};
+use std::sync::Arc;
+
+use crate::{DownloadError, RequestError};
+
+/// A stream of chunks of a downloaded file.
+pub type FileStream = BoxStream<'static, Result<Bytes, DownloadError>>;
+
+/// A way of sending requests and downloading files, used by [`Bot`].
+///
+/// [`ReqwestTransport`] is used by default. Implement this trait to use
+/// another HTTP client, to wrap a transport with middleware (retrying,
+/// throttling, caching), or to answer requests without network access (see
+/// [`Replay`]). Set a transport with [`Bot::with_transport`].
+///
+/// Transports deal with raw bodies: parameters are already serialized and
+/// responses are parsed by the caller. Errors not related to [`reqwest`] can
+/// be reported as [`RequestError::Io`] and [`DownloadError::Io`].
+///
+/// ## Examples
+/// A transport logging slow requests:
+/// ```
+/// use reqwest::multipart::Form;
+/// use std::time::{Duration, Instant};
+/// use teloxide::{
+///     net::{FileStream, ReqwestTransport, Transport},
+///     Bot, DownloadError, RequestError,
+/// };
+///
+/// struct SlowRequestsLogger<T>(T);
+///
+/// #[async_trait::async_trait]
+/// impl<T> Transport for SlowRequestsLogger<T>
+/// where
+///     T: Transport,
+/// {
+///     async fn send_json(
+///         &self,
+///         token: &str,
+///         method_name: &str,
+///         params: String,
+///     ) -> Result<String, RequestError> {
+///         let start = Instant::now();
+///         let response = self.0.send_json(token, method_name, params).await;
+///         if start.elapsed() > Duration::from_secs(1) {
+///             log::warn!("{} is slow", method_name);
+///         }
+///         response
+///     }
+///
+///     async fn send_multipart(
+///         &self,
+///         token: &str,
+///         method_name: &str,
+///         params: Form,
+///     ) -> Result<String, RequestError> {
+///         self.0.send_multipart(token, method_name, params).await
+///     }
+///
+///     async fn get_stream(
+///         &self,
+///         token: &str,
+///         path: &str,
+///     ) -> Result<FileStream, DownloadError> {
+///         self.0.get_stream(token, path).await
+///     }
+/// }
+///
+/// let bot = Bot::new("TOKEN")
+///     .with_transport(SlowRequestsLogger(ReqwestTransport::default()));
+/// ```
+///
+/// [`Bot`]: crate::Bot
+/// [`Bot::with_transport`]: crate::Bot::with_transport
+/// [`Replay`]: crate::net::Replay
+/// [`RequestError::Io`]: crate::RequestError::Io
+/// [`DownloadError::Io`]: crate::DownloadError::Io
+#[async_trait::async_trait]
+pub trait Transport: Send + Sync {
+    /// Calls `method_name` with `params` serialized into JSON, returning a raw
+    /// body of the response.
+    async fn send_json(
+        &self,
+        token: &str,
+        method_name: &str,
+        params: String,
+    ) -> Result<String, RequestError>;
+
+    /// Calls `method_name` with `multipart/form-data` `params`, returning a
+    /// raw body of the response.
+    async fn send_multipart(
+        &self,
+        token: &str,
+        method_name: &str,
+        params: Form,
+    ) -> Result<String, RequestError>;
+
+    /// Downloads a file at `path`, which is obtained from [`Bot::get_file`].
+    ///
+    /// [`Bot::get_file`]: crate::Bot::get_file
+    async fn get_stream(
+        &self,
+        token: &str,
+        path: &str,
+    ) -> Result<FileStream, DownloadError>;
+}
+
+#[async_trait::async_trait]
+impl<T> Transport for Arc<T>
+where
+    T: Transport + ?Sized,
+{
+    async fn send_json(
+        &self,
+        token: &str,
+        method_name: &str,
+        params: String,
+    ) -> Result<String, RequestError> {
+        (**self).send_json(token, method_name, params).await
+    }
+
+    async fn send_multipart(
+        &self,
+        token: &str,
+        method_name: &str,
+        params: Form,
+    ) -> Result<String, RequestError> {
+        (**self).send_multipart(token, method_name, params).await
+    }
+
+    async fn get_stream(
+        &self,
+        token: &str,
+        path: &str,
+    ) -> Result<FileStream, DownloadError> {
+        (**self).get_stream(token, path).await
+    }
+}
+
+/// The default [`Transport`], sending requests to the Bot API server with
+/// [`reqwest::Client`].
+///
+/// The server URL can be changed with the `TELEGRAM_API_URL` environmental
+/// variable.
+///
+/// [`reqwest::Client`]: https://docs.rs/reqwest/0.10.1/reqwest/struct.Client.html
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+}
+
+#[async_trait::async_trait]
+impl Transport for ReqwestTransport {
+    async fn send_json(
+        &self,
+        token: &str,
+        method_name: &str,
+        params: String,
+    ) -> Result<String, RequestError> {
+        self.client
+            .post(&super::method_url(token, method_name))
+            .header(CONTENT_TYPE, "application/json")
+            .body(params)
+            .send()
+            .await
+            .map_err(RequestError::NetworkError)?
+            .text()
+            .await
+            .map_err(RequestError::NetworkError)
+    }
+
+    async fn send_multipart(
+        &self,
+        token: &str,
+        method_name: &str,
+        params: Form,
+    ) -> Result<String, RequestError> {
+        self.client
+            .post(&super::method_url(token, method_name))
+            .multipart(params)
+            .send()
+            .await
+            .map_err(RequestError::NetworkError)?
+            .text()
+            .await
+            .map_err(RequestError::NetworkError)
+    }
+
+    async fn get_stream(
+        &self,
+        token: &str,
+        path: &str,
+    ) -> Result<FileStream, DownloadError> {
+        let response = self
+            .client
+            .get(&super::file_url(token, path))
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(Box::pin(response.bytes_stream().map_err(DownloadError::from)))
+    }
+}
@@ -1,5 +1,4 @@
 use crate::{
-    net,
     requests::form_builder::FormBuilder,
     types::{InputFile, MaskPosition, True},
     Bot,
@@ -26,21 +25,19 @@ impl Request for AddStickerToSet {
     type Output = True;
 
     async fn send(&self) -> ResponseResult<True> {
-        net::request_multipart(
-            self.bot.client(),
-            self.bot.token(),
+        self.bot.execute_multipart(
             "addStickerToSet",
             FormBuilder::new()
                 .add("user_id", &self.user_id)
-                .await
+                .await?
                 .add("name", &self.name)
-                .await
+                .await?
                 .add("png_sticker", &self.png_sticker)
-                .await
+                .await?
                 .add("emojis", &self.emojis)
-                .await
+                .await?
                 .add("mask_position", &self.mask_position)
-                .await
+                .await?
                 .build(),
         )
         .await
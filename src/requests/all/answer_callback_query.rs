@@ -1,7 +1,6 @@
 use serde::Serialize;
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
     types::True,
     Bot,
@@ -34,13 +33,12 @@ impl Request for AnswerCallbackQuery {
     type Output = True;
 
     async fn send(&self) -> ResponseResult<True> {
-        net::request_json(
-            self.bot.client(),
-            self.bot.token(),
-            "answerCallbackQuery",
-            &self,
-        )
-        .await
+        let answered =
+            self.bot.execute_json("answerCallbackQuery", &self).await?;
+        // Unless the answer is sent, the query is still answered
+        // automatically.
+        self.bot.untrack_callback_query(&self.callback_query_id);
+        Ok(answered)
     }
 }
 
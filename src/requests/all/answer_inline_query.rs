@@ -1,7 +1,6 @@
 use serde::{Serialize, Deserialize};
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
     types::{InlineQueryResult, True},
     Bot,
@@ -32,9 +31,7 @@ impl Request for AnswerInlineQuery {
     type Output = True;
 
     async fn send(&self) -> ResponseResult<True> {
-        net::request_json(
-            self.bot.client(),
-            self.bot.token(),
+        self.bot.execute_json(
             "answerInlineQuery",
             &self,
         )
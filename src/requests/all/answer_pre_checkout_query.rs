@@ -1,7 +1,6 @@
 use serde::Serialize;
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
     types::True,
     Bot,
@@ -32,9 +31,7 @@ impl Request for AnswerPreCheckoutQuery {
     type Output = True;
 
     async fn send(&self) -> ResponseResult<True> {
-        net::request_json(
-            self.bot.client(),
-            self.bot.token(),
+        self.bot.execute_json(
             "answerPreCheckoutQuery",
             &self,
         )
@@ -1,5 +1,4 @@
 use crate::{
-    net,
     requests::{form_builder::FormBuilder, Request, ResponseResult},
     types::{InputFile, MaskPosition, True},
     Bot,
@@ -27,25 +26,23 @@ impl Request for CreateNewStickerSet {
     type Output = True;
 
     async fn send(&self) -> ResponseResult<True> {
-        net::request_multipart(
-            self.bot.client(),
-            self.bot.token(),
+        self.bot.execute_multipart(
             "createNewStickerSet",
             FormBuilder::new()
                 .add("user_id", &self.user_id)
-                .await
+                .await?
                 .add("name", &self.name)
-                .await
+                .await?
                 .add("title", &self.title)
-                .await
+                .await?
                 .add("png_sticker", &self.png_sticker)
-                .await
+                .await?
                 .add("emojis", &self.emojis)
-                .await
+                .await?
                 .add("contains_masks", &self.contains_masks)
-                .await
+                .await?
                 .add("mask_position", &self.mask_position)
-                .await
+                .await?
                 .build(),
         )
         .await
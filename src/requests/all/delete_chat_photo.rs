@@ -1,7 +1,6 @@
 use serde::Serialize;
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
     types::{ChatId, True},
     Bot,
@@ -26,9 +25,7 @@ impl Request for DeleteChatPhoto {
     type Output = True;
 
     async fn send(&self) -> ResponseResult<True> {
-        net::request_json(
-            self.bot.client(),
-            self.bot.token(),
+        self.bot.execute_json(
             "deleteChatPhoto",
             &self,
         )
@@ -1,7 +1,6 @@
 use serde::Serialize;
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
     types::{ChatId, True},
     Bot,
@@ -31,9 +30,7 @@ impl Request for DeleteChatStickerSet {
     type Output = True;
 
     async fn send(&self) -> ResponseResult<True> {
-        net::request_json(
-            self.bot.client(),
-            self.bot.token(),
+        self.bot.execute_json(
             "deleteChatStickerSet",
             &self,
         )
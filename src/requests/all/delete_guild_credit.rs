@@ -1,7 +1,6 @@
 use serde::Serialize;
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
     types::{ChatId, True, GuildCredit},
     Bot,
@@ -29,9 +28,7 @@ impl Request for DeleteGuildCredit {
     type Output = True;
 
     async fn send(&self) -> ResponseResult<True> {
-        net::request_json(
-            self.bot.client(),
-            self.bot.token(),
+        self.bot.execute_json(
             "deleteGuildCredit",
             &self,
         ).await
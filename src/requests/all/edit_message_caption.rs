@@ -1,9 +1,12 @@
 use serde::Serialize;
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
-    types::{ChatOrInlineMessage, InlineKeyboardMarkup, Message, ParseMode},
+    types::{
+        ChatOrInlineMessage, InlineKeyboardMarkup, Message, MessageEntity,
+        ParseMode,
+    },
+    utils::formatted::FormattedText,
     Bot,
 };
 use std::sync::Arc;
@@ -26,6 +29,7 @@ pub struct EditMessageCaption {
     chat_or_inline_message: ChatOrInlineMessage,
     caption: Option<String>,
     parse_mode: Option<ParseMode>,
+    caption_entities: Option<Vec<MessageEntity>>,
     reply_markup: Option<InlineKeyboardMarkup>,
 }
 
@@ -34,9 +38,7 @@ impl Request for EditMessageCaption {
     type Output = Message;
 
     async fn send(&self) -> ResponseResult<Message> {
-        net::request_json(
-            self.bot.client(),
-            self.bot.token(),
+        self.bot.execute_json(
             "editMessageCaption",
             &self,
         )
@@ -54,6 +56,7 @@ impl EditMessageCaption {
             chat_or_inline_message,
             caption: None,
             parse_mode: None,
+            caption_entities: None,
             reply_markup: None,
         }
     }
@@ -84,6 +87,30 @@ impl EditMessageCaption {
         self
     }
 
+    /// A list of special entities that appear in the caption, which can be
+    /// specified instead of [`parse_mode`], see [`utils::text_builder`].
+    ///
+    /// [`parse_mode`]: crate::requests::EditMessageCaption::parse_mode
+    /// [`utils::text_builder`]: crate::utils::text_builder
+    pub fn caption_entities<T>(mut self, val: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.caption_entities = Some(val.into());
+        self
+    }
+
+    /// Sets the caption together with its parse mode, see [`FormattedText`].
+    ///
+    /// [`FormattedText`]: crate::utils::formatted::FormattedText
+    pub fn caption_formatted(mut self, val: FormattedText) -> Self {
+        let (caption, parse_mode) = val.into_parts();
+        self.caption = Some(caption);
+        self.parse_mode = Some(parse_mode);
+        self.caption_entities = None;
+        self
+    }
+
     /// A JSON-serialized object for an [inline keyboard].
     ///
     /// [inline keyboard]: https://core.telegram.org/bots#inline-keyboards-and-on-the-fly-updating
@@ -1,5 +1,4 @@
 use crate::{
-    net,
     requests::{form_builder::FormBuilder, Request, ResponseResult},
     types::{ChatOrInlineMessage, InlineKeyboardMarkup, InputMedia, Message},
     Bot,
@@ -39,25 +38,23 @@ impl Request for EditMessageMedia {
             ChatOrInlineMessage::Chat { chat_id, message_id } => {
                 params = params
                     .add("chat_id", chat_id)
-                    .await
+                    .await?
                     .add("message_id", message_id)
-                    .await;
+                    .await?;
             }
             ChatOrInlineMessage::Inline { inline_message_id } => {
                 params =
-                    params.add("inline_message_id", inline_message_id).await;
+                    params.add("inline_message_id", inline_message_id).await?;
             }
         }
 
-        net::request_multipart(
-            self.bot.client(),
-            self.bot.token(),
+        self.bot.execute_multipart(
             "editMessageMedia",
             params
                 .add("media", &self.media)
-                .await
+                .await?
                 .add("reply_markup", &self.reply_markup)
-                .await
+                .await?
                 .build(),
         )
         .await
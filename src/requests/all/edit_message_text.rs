@@ -1,9 +1,12 @@
 use serde::Serialize;
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
-    types::{ChatOrInlineMessage, InlineKeyboardMarkup, Message, ParseMode},
+    types::{
+        ChatOrInlineMessage, InlineKeyboardMarkup, Message, MessageEntity,
+        ParseMode,
+    },
+    utils::formatted::FormattedText,
     Bot,
 };
 use std::sync::Arc;
@@ -26,6 +29,7 @@ pub struct EditMessageText {
     chat_or_inline_message: ChatOrInlineMessage,
     text: String,
     parse_mode: Option<ParseMode>,
+    entities: Option<Vec<MessageEntity>>,
     disable_web_page_preview: Option<bool>,
     reply_markup: Option<InlineKeyboardMarkup>,
 }
@@ -35,9 +39,7 @@ impl Request for EditMessageText {
     type Output = Message;
 
     async fn send(&self) -> ResponseResult<Message> {
-        net::request_json(
-            self.bot.client(),
-            self.bot.token(),
+        self.bot.execute_json(
             "editMessageText",
             &self,
         )
@@ -59,6 +61,7 @@ impl EditMessageText {
             chat_or_inline_message,
             text: text.into(),
             parse_mode: None,
+            entities: None,
             disable_web_page_preview: None,
             reply_markup: None,
         }
@@ -89,6 +92,30 @@ impl EditMessageText {
         self
     }
 
+    /// A list of special entities that appear in the message text, which can
+    /// be specified instead of [`parse_mode`], see [`utils::text_builder`].
+    ///
+    /// [`parse_mode`]: crate::requests::EditMessageText::parse_mode
+    /// [`utils::text_builder`]: crate::utils::text_builder
+    pub fn entities<T>(mut self, val: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.entities = Some(val.into());
+        self
+    }
+
+    /// Sets the text together with its parse mode, see [`FormattedText`].
+    ///
+    /// [`FormattedText`]: crate::utils::formatted::FormattedText
+    pub fn formatted(mut self, val: FormattedText) -> Self {
+        let (text, parse_mode) = val.into_parts();
+        self.text = text;
+        self.parse_mode = Some(parse_mode);
+        self.entities = None;
+        self
+    }
+
     /// Disables link previews for links in this message.
     pub fn disable_web_page_preview(mut self, val: bool) -> Self {
         self.disable_web_page_preview = Some(val);
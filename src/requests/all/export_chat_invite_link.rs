@@ -1,7 +1,6 @@
 use serde::Serialize;
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
     types::ChatId,
     Bot,
@@ -41,9 +40,7 @@ impl Request for ExportChatInviteLink {
 
     /// Returns the new invite link as `String` on success.
     async fn send(&self) -> ResponseResult<String> {
-        net::request_json(
-            self.bot.client(),
-            self.bot.token(),
+        self.bot.execute_json(
             "exportChatInviteLink",
             &self,
         )
@@ -1,7 +1,6 @@
 use serde::Serialize;
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
     types::{ChatId, ChatMember},
     Bot,
@@ -29,9 +28,7 @@ impl Request for GetChatAdministrators {
     /// On success, returns an array that contains information about all chat
     /// administrators except other bots.
     async fn send(&self) -> ResponseResult<Vec<ChatMember>> {
-        net::request_json(
-            self.bot.client(),
-            self.bot.token(),
+        self.bot.execute_json(
             "getChatAdministrators",
             &self,
         )
@@ -1,7 +1,6 @@
 use serde::Serialize;
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
     types::File,
     Bot,
@@ -40,7 +39,7 @@ impl Request for GetFile {
     type Output = File;
 
     async fn send(&self) -> ResponseResult<File> {
-        net::request_json(self.bot.client(), self.bot.token(), "getFile", &self)
+        self.bot.execute_json("getFile", &self)
             .await
     }
 }
@@ -0,0 +1,33 @@
+use serde::Serialize;
+
+use crate::{
+    requests::{Request, ResponseResult},
+    types::Guild,
+    Bot,
+};
+use std::sync::Arc;
+
+/// Use this method to get up to date information about a guild.
+#[serde_with_macros::skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetGuild {
+    #[serde(skip)]
+    bot: Arc<Bot>,
+    pub guild_id: i64,
+}
+
+#[async_trait::async_trait]
+impl Request for GetGuild {
+    type Output = Guild;
+
+    async fn send(&self) -> ResponseResult<Guild> {
+        self.bot.execute_json("getGuild", &self)
+            .await
+    }
+}
+
+impl GetGuild {
+    pub(crate) fn new(bot: Arc<Bot>, guild_id: i64) -> Self {
+        Self { bot, guild_id }
+    }
+}
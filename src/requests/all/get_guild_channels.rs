@@ -0,0 +1,41 @@
+use serde::Serialize;
+
+use crate::{
+    requests::{Request, ResponseResult},
+    types::Chat,
+    Bot,
+};
+use std::sync::Arc;
+
+/// Use this method to get all channels (text channels, circles and voice
+/// channels) of a guild, visible to the bot.
+///
+/// See [`Chat::channel_kind`] to distinguish the kinds of channels.
+///
+/// [`Chat::channel_kind`]: crate::types::Chat::channel_kind
+#[serde_with_macros::skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetGuildChannels {
+    #[serde(skip)]
+    bot: Arc<Bot>,
+    pub guild_id: i64,
+}
+
+#[async_trait::async_trait]
+impl Request for GetGuildChannels {
+    type Output = Vec<Chat>;
+
+    async fn send(&self) -> ResponseResult<Vec<Chat>> {
+        self.bot.execute_json(
+            "getGuildChannels",
+            &self,
+        )
+        .await
+    }
+}
+
+impl GetGuildChannels {
+    pub(crate) fn new(bot: Arc<Bot>, guild_id: i64) -> Self {
+        Self { bot, guild_id }
+    }
+}
@@ -0,0 +1,52 @@
+use serde::Serialize;
+
+use crate::{
+    requests::{Request, ResponseResult},
+    types::{ChatId, GuildCredit},
+    Bot,
+};
+use std::sync::Arc;
+
+/// Use this method to get a guild credit of a user, previously set by
+/// [`Bot::set_guild_credit`].
+///
+/// Returns `None` if the user has no guild credit.
+///
+/// [`Bot::set_guild_credit`]: crate::Bot::set_guild_credit
+#[serde_with_macros::skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetGuildCredit {
+    #[serde(skip)]
+    bot: Arc<Bot>,
+    pub chat_id: Option<ChatId>,
+    pub guild_id: Option<i64>,
+    pub user_id: i64,
+}
+
+#[async_trait::async_trait]
+impl Request for GetGuildCredit {
+    type Output = Option<GuildCredit>;
+
+    async fn send(&self) -> ResponseResult<Option<GuildCredit>> {
+        self.bot.execute_json(
+            "getGuildCredit",
+            &self,
+        )
+        .await
+    }
+}
+
+impl GetGuildCredit {
+    pub(crate) fn new<C>(
+        bot: Arc<Bot>,
+        chat_id: Option<C>,
+        guild_id: Option<i64>,
+        user_id: i64,
+    ) -> Self
+    where
+        C: Into<ChatId>,
+    {
+        let chat_id = chat_id.map(Into::into);
+        Self { bot, chat_id, guild_id, user_id }
+    }
+}
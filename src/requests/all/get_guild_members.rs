@@ -1,7 +1,6 @@
 use serde::Serialize;
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
     types::{Chat, ChatId, ChatMember},
     Bot,
@@ -28,7 +27,7 @@ impl Request for GetGuildMembers {
     type Output = Vec<ChatMember>;
 
     async fn send(&self) -> ResponseResult<Vec<ChatMember>> {
-        net::request_json(self.bot.client(), self.bot.token(), "getGuildMembers", &self)
+        self.bot.execute_json("getGuildMembers", &self)
             .await
     }
 }
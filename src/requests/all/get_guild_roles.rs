@@ -1,7 +1,6 @@
 use serde::Serialize;
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
     types::{GuildRole, ChatId},
     Bot,
@@ -26,7 +25,7 @@ impl Request for GetGuildRoles {
     type Output = Vec<GuildRole>;
 
     async fn send(&self) -> ResponseResult<Vec<GuildRole>> {
-        net::request_json(self.bot.client(), self.bot.token(), "getGuildRoles", &self)
+        self.bot.execute_json("getGuildRoles", &self)
             .await
     }
 }
@@ -1,5 +1,4 @@
 use crate::{
-    net,
     requests::{Request, ResponseResult},
     types::Me,
     Bot,
@@ -23,7 +22,7 @@ impl Request for GetMe {
     /// Returns basic information about the bot.
     #[allow(clippy::trivially_copy_pass_by_ref)]
     async fn send(&self) -> ResponseResult<Me> {
-        net::request_json(self.bot.client(), self.bot.token(), "getMe", &self)
+        self.bot.execute_json("getMe", &self)
             .await
     }
 }
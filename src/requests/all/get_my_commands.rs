@@ -0,0 +1,34 @@
+use serde::Serialize;
+
+use crate::{
+    requests::{Request, ResponseResult},
+    types::BotCommand,
+    Bot,
+};
+use std::sync::Arc;
+
+/// Use this method to get the current list of the bot's commands.
+///
+/// [The official docs](https://core.telegram.org/bots/api#getmycommands).
+#[derive(Debug, Clone, Serialize)]
+pub struct GetMyCommands {
+    #[serde(skip_serializing)]
+    bot: Arc<Bot>,
+}
+
+#[async_trait::async_trait]
+impl Request for GetMyCommands {
+    type Output = Vec<BotCommand>;
+
+    /// Returns the list of the bot's commands, which is empty if the commands
+    /// aren't set.
+    async fn send(&self) -> ResponseResult<Vec<BotCommand>> {
+        self.bot.execute_json("getMyCommands", &self).await
+    }
+}
+
+impl GetMyCommands {
+    pub(crate) fn new(bot: Arc<Bot>) -> Self {
+        Self { bot }
+    }
+}
@@ -1,7 +1,6 @@
 use serde::Serialize;
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
     types::{Chat, ChatId},
     Bot,
@@ -26,7 +25,7 @@ impl Request for GetPrivateChat {
     type Output = Chat;
 
     async fn send(&self) -> ResponseResult<Chat> {
-        net::request_json(self.bot.client(), self.bot.token(), "getPrivateChat", &self)
+        self.bot.execute_json("getPrivateChat", &self)
             .await
     }
 }
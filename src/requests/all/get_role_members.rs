@@ -1,7 +1,6 @@
 use serde::Serialize;
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
     types::{GuildRole, ChatId, ChatMember},
     Bot,
@@ -27,7 +26,7 @@ impl Request for GetRoleMembers {
     type Output = Vec<ChatMember>;
 
     async fn send(&self) -> ResponseResult<Vec<ChatMember>> {
-        net::request_json(self.bot.client(), self.bot.token(), "getRoleMembers", &self)
+        self.bot.execute_json("getRoleMembers", &self)
             .await
     }
 }
@@ -1,7 +1,6 @@
 use serde::Serialize;
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
     types::{AllowedUpdate, Update},
     Bot, RequestError,
@@ -40,9 +39,7 @@ impl Request for GetUpdates {
     async fn send(
         &self,
     ) -> ResponseResult<Vec<Result<Update, (Value, serde_json::Error)>>> {
-        let value: Value = net::request_json(
-            self.bot.client(),
-            self.bot.token(),
+        let value: Value = self.bot.execute_json(
             "getUpdates",
             &self,
         )
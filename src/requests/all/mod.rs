@@ -40,6 +40,7 @@ mod send_document;
 mod send_game;
 mod send_invoice;
 mod send_location;
+mod send_long_message;
 mod send_media_group;
 mod send_message;
 mod send_photo;
@@ -65,13 +66,18 @@ mod unpin_chat_message;
 mod upload_sticker_file;
 mod set_guild_credit;
 mod delete_guild_credit;
+mod get_guild_credit;
 mod get_private_chat;
 mod get_guild_members;
+mod get_guild;
+mod get_guild_channels;
 mod get_guild_roles;
 mod search_guild_member;
 mod set_member_role;
 mod get_role_members;
 mod search_guild_member_by_username;
+mod set_my_commands;
+mod get_my_commands;
 
 pub use add_sticker_to_set::*;
 pub use answer_callback_query::*;
@@ -115,6 +121,7 @@ pub use send_document::*;
 pub use send_game::*;
 pub use send_invoice::*;
 pub use send_location::*;
+pub use send_long_message::*;
 pub use send_media_group::*;
 pub use send_message::*;
 pub use send_photo::*;
@@ -141,10 +148,15 @@ pub use unpin_chat_message::*;
 pub use upload_sticker_file::*;
 pub use set_guild_credit::*;
 pub use delete_guild_credit::*;
+pub use get_guild_credit::*;
 pub use get_private_chat::*;
 pub use get_guild_members::*;
+pub use get_guild::*;
+pub use get_guild_channels::*;
 pub use get_guild_roles::*;
 pub use search_guild_member::*;
 pub use set_member_role::*;
 pub use get_role_members::*;
 pub use search_guild_member_by_username::*;
+pub use set_my_commands::*;
+pub use get_my_commands::*;
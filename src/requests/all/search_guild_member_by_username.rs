@@ -1,7 +1,6 @@
 use serde::Serialize;
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
     types::{Chat, ChatId, User, ChatMember},
     Bot,
@@ -27,7 +26,7 @@ impl Request for SearchGuildMemberByUsername {
     type Output = Vec<ChatMember>;
 
     async fn send(&self) -> ResponseResult<Vec<ChatMember>> {
-        net::request_json(self.bot.client(), self.bot.token(), "searchGuildMemberByUsername", &self)
+        self.bot.execute_json("searchGuildMemberByUsername", &self)
             .await
     }
 }
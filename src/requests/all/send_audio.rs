@@ -1,7 +1,10 @@
 use crate::{
-    net,
-    requests::{form_builder::FormBuilder, Request, ResponseResult},
-    types::{ChatId, InputFile, Message, ParseMode, ReplyMarkup},
+    requests::{
+        form_builder::FormBuilder, upload::UploadRequest, Request,
+        ResponseResult,
+    },
+    types::{ChatId, InputFile, Message, MessageEntity, ParseMode, ReplyMarkup},
+    utils::{file_id_cache::FileKind, formatted::FormattedText},
     Bot,
 };
 use std::sync::Arc;
@@ -24,6 +27,7 @@ pub struct SendAudio {
     audio: InputFile,
     caption: Option<String>,
     parse_mode: Option<ParseMode>,
+    caption_entities: Option<Vec<MessageEntity>>,
     duration: Option<i32>,
     performer: Option<String>,
     title: Option<String>,
@@ -38,33 +42,50 @@ impl Request for SendAudio {
     type Output = Message;
 
     async fn send(&self) -> ResponseResult<Message> {
-        net::request_multipart(
-            self.bot.client(),
-            self.bot.token(),
+        self.send_cached().await
+    }
+}
+
+#[async_trait::async_trait]
+impl UploadRequest for SendAudio {
+    const KIND: FileKind = FileKind::Audio;
+
+    fn bot(&self) -> &Bot {
+        &self.bot
+    }
+
+    fn file(&self) -> &InputFile {
+        &self.audio
+    }
+
+    async fn send_file(&self, audio: &InputFile) -> ResponseResult<Message> {
+        self.bot.execute_multipart(
             "sendAudio",
             FormBuilder::new()
                 .add("chat_id", &self.chat_id)
-                .await
-                .add("audio", &self.audio)
-                .await
+                .await?
+                .add("audio", audio)
+                .await?
                 .add("caption", &self.caption)
-                .await
+                .await?
                 .add("parse_mode", &self.parse_mode)
-                .await
+                .await?
+                .add("caption_entities", &self.caption_entities)
+                .await?
                 .add("duration", &self.duration)
-                .await
+                .await?
                 .add("performer", &self.performer)
-                .await
+                .await?
                 .add("title", &self.title)
-                .await
+                .await?
                 .add("thumb", &self.thumb)
-                .await
+                .await?
                 .add("disable_notification", &self.disable_notification)
-                .await
+                .await?
                 .add("reply_to_message_id", &self.reply_to_message_id)
-                .await
+                .await?
                 .add("reply_markup", &self.reply_markup)
-                .await
+                .await?
                 .build(),
         )
         .await
@@ -82,6 +103,7 @@ impl SendAudio {
             audio,
             caption: None,
             parse_mode: None,
+            caption_entities: None,
             duration: None,
             performer: None,
             title: None,
@@ -140,6 +162,30 @@ impl SendAudio {
         self
     }
 
+    /// A list of special entities that appear in the caption, which can be
+    /// specified instead of [`parse_mode`], see [`utils::text_builder`].
+    ///
+    /// [`parse_mode`]: crate::requests::SendAudio::parse_mode
+    /// [`utils::text_builder`]: crate::utils::text_builder
+    pub fn caption_entities<T>(mut self, val: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.caption_entities = Some(val.into());
+        self
+    }
+
+    /// Sets the caption together with its parse mode, see [`FormattedText`].
+    ///
+    /// [`FormattedText`]: crate::utils::formatted::FormattedText
+    pub fn caption_formatted(mut self, val: FormattedText) -> Self {
+        let (caption, parse_mode) = val.into_parts();
+        self.caption = Some(caption);
+        self.parse_mode = Some(parse_mode);
+        self.caption_entities = None;
+        self
+    }
+
     /// Duration of the audio in seconds.
     pub fn duration(mut self, val: i32) -> Self {
         self.duration = Some(val);
@@ -0,0 +1,247 @@
+use crate::{
+    requests::{Request, ResponseResult, SendMessage},
+    types::{ChatId, Message, MessageEntity, ParseMode, ReplyMarkup},
+    utils::{
+        entities::ParseError,
+        formatted::FormattedText,
+        split::{self, MAX_MESSAGE_LEN},
+    },
+    Bot, RequestError,
+};
+use std::sync::Arc;
+
+/// Sends a text of any length, splitting it into several messages if it's
+/// too long for one.
+///
+/// The text is split at paragraph, sentence or word boundaries (see
+/// [`utils::split`]) and the parts are sent one by one, in order. Markup and
+/// entities crossing a split are closed and continued in the next part.
+///
+/// A text with [`ParseMode::Markdown`] or [`ParseMode::Fanbook`] cannot be
+/// split, so it's sent only if it fits into one message.
+///
+/// If sending a part fails, the error is returned and the rest of the parts
+/// are not sent.
+///
+/// [`utils::split`]: crate::utils::split
+/// [`ParseMode::Markdown`]: crate::types::ParseMode::Markdown
+/// [`ParseMode::Fanbook`]: crate::types::ParseMode::Fanbook
+#[derive(Debug, Clone)]
+pub struct SendLongMessage {
+    bot: Arc<Bot>,
+    pub chat_id: ChatId,
+    pub text: String,
+    pub parse_mode: Option<ParseMode>,
+    pub entities: Option<Vec<MessageEntity>>,
+    pub disable_web_page_preview: Option<bool>,
+    pub disable_notification: Option<bool>,
+    pub reply_to_message_id: Option<i64>,
+    pub reply_markup: Option<ReplyMarkup>,
+    pub max_len: usize,
+}
+
+#[async_trait::async_trait]
+impl Request for SendLongMessage {
+    type Output = Vec<Message>;
+
+    async fn send(&self) -> ResponseResult<Vec<Message>> {
+        let parts = self.parts()?;
+        let last = parts.len() - 1;
+
+        let mut messages = Vec::with_capacity(parts.len());
+        for (i, (text, entities)) in parts.into_iter().enumerate() {
+            let mut request = SendMessage::new(
+                Arc::clone(&self.bot),
+                self.chat_id.clone(),
+                text,
+            );
+            request.parse_mode = self.parse_mode;
+            request.entities = entities;
+            request.disable_web_page_preview = self.disable_web_page_preview;
+            request.disable_notification = self.disable_notification;
+            if i == 0 {
+                request.reply_to_message_id = self.reply_to_message_id;
+            }
+            if i == last {
+                request.reply_markup = self.reply_markup.clone();
+            }
+
+            messages.push(request.send().await?);
+        }
+
+        Ok(messages)
+    }
+}
+
+impl SendLongMessage {
+    pub(crate) fn new<C, T>(bot: Arc<Bot>, chat_id: C, text: T) -> Self
+    where
+        C: Into<ChatId>,
+        T: Into<String>,
+    {
+        Self {
+            bot,
+            chat_id: chat_id.into(),
+            text: text.into(),
+            parse_mode: None,
+            entities: None,
+            disable_web_page_preview: None,
+            disable_notification: None,
+            reply_to_message_id: None,
+            reply_markup: None,
+            max_len: MAX_MESSAGE_LEN,
+        }
+    }
+
+    /// Texts and entities of the messages to be sent.
+    fn parts(
+        &self,
+    ) -> ResponseResult<Vec<(String, Option<Vec<MessageEntity>>)>> {
+        if self.max_len == 0 {
+            return Err(RequestError::InvalidInput(
+                "`max_len` must be positive".to_owned(),
+            ));
+        }
+        if self.text.encode_utf16().count() <= self.max_len {
+            return Ok(vec![(self.text.clone(), self.entities.clone())]);
+        }
+
+        let invalid_markup = |err: ParseError| {
+            RequestError::InvalidInput(format!(
+                "the text cannot be split: {}",
+                err
+            ))
+        };
+        let parts = match self.parse_mode {
+            None => {
+                let entities = self.entities.as_deref().unwrap_or(&[]);
+                split::split_entities(&self.text, entities, self.max_len)
+                    .into_iter()
+                    .map(|(text, entities)| {
+                        let entities = self.entities.as_ref().map(|_| entities);
+                        (text, entities)
+                    })
+                    .collect()
+            }
+            Some(ParseMode::HTML) => {
+                split::split_html(&self.text, self.max_len)
+                    .map_err(invalid_markup)?
+                    .into_iter()
+                    .map(|text| (text, None))
+                    .collect()
+            }
+            Some(ParseMode::MarkdownV2) => {
+                split::split_markdown(&self.text, self.max_len)
+                    .map_err(invalid_markup)?
+                    .into_iter()
+                    .map(|text| (text, None))
+                    .collect()
+            }
+            Some(parse_mode) => {
+                return Err(RequestError::InvalidInput(format!(
+                    "the text is too long and {:?} cannot be split",
+                    parse_mode
+                )))
+            }
+        };
+
+        Ok(parts)
+    }
+
+    /// Unique identifier for the target chat or username of the target channel
+    /// (in the format `@channelusername`).
+    pub fn chat_id<T>(mut self, val: T) -> Self
+    where
+        T: Into<ChatId>,
+    {
+        self.chat_id = val.into();
+        self
+    }
+
+    /// Text to be sent, of any length.
+    pub fn text<T>(mut self, val: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.text = val.into();
+        self
+    }
+
+    /// Send [Markdown V2] or [HTML], if you want Telegram apps to show
+    /// [bold, italic, fixed-width text or inline URLs] in the message.
+    ///
+    /// [Markdown V2]: crate::types::ParseMode::MarkdownV2
+    /// [HTML]: crate::types::ParseMode::HTML
+    /// [bold, italic, fixed-width text or inline URLs]:
+    /// crate::types::ParseMode
+    pub fn parse_mode(mut self, val: ParseMode) -> Self {
+        self.parse_mode = Some(val);
+        self
+    }
+
+    /// A list of special entities that appear in the text, which can be
+    /// specified instead of [`parse_mode`].
+    ///
+    /// [`parse_mode`]: crate::requests::SendLongMessage::parse_mode
+    pub fn entities<T>(mut self, val: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.entities = Some(val.into());
+        self
+    }
+
+    /// Sets the text together with its parse mode, see [`FormattedText`].
+    ///
+    /// [`FormattedText`]: crate::utils::formatted::FormattedText
+    pub fn formatted(mut self, val: FormattedText) -> Self {
+        let (text, parse_mode) = val.into_parts();
+        self.text = text;
+        self.parse_mode = Some(parse_mode);
+        self.entities = None;
+        self
+    }
+
+    /// Disables link previews for links in the messages.
+    pub fn disable_web_page_preview(mut self, val: bool) -> Self {
+        self.disable_web_page_preview = Some(val);
+        self
+    }
+
+    /// Sends the messages [silently]. Users will receive notifications with no
+    /// sound.
+    ///
+    /// [silently]: https://telegram.org/blog/channels-2-0#silent-messages
+    pub fn disable_notification(mut self, val: bool) -> Self {
+        self.disable_notification = Some(val);
+        self
+    }
+
+    /// ID of a message the first part replies to.
+    pub fn reply_to_message_id(mut self, val: i64) -> Self {
+        self.reply_to_message_id = Some(val);
+        self
+    }
+
+    /// Additional interface options attached to the last part.
+    pub fn reply_markup<T>(mut self, val: T) -> Self
+    where
+        T: Into<ReplyMarkup>,
+    {
+        self.reply_markup = Some(val.into());
+        self
+    }
+
+    /// The maximum length of a part in UTF-16 code units, [`MAX_MESSAGE_LEN`]
+    /// by default.
+    ///
+    /// [`send`] fails with [`RequestError::InvalidInput`] if it's zero.
+    ///
+    /// [`MAX_MESSAGE_LEN`]: crate::utils::split::MAX_MESSAGE_LEN
+    /// [`send`]: crate::requests::Request::send
+    /// [`RequestError::InvalidInput`]: crate::RequestError::InvalidInput
+    pub fn max_len(mut self, val: usize) -> Self {
+        self.max_len = val;
+        self
+    }
+}
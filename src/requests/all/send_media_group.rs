@@ -1,5 +1,4 @@
 use crate::{
-    net,
     requests::{form_builder::FormBuilder, Request, ResponseResult},
     types::{ChatId, InputMedia, Message},
     Bot,
@@ -23,19 +22,17 @@ impl Request for SendMediaGroup {
     type Output = Vec<Message>;
 
     async fn send(&self) -> ResponseResult<Vec<Message>> {
-        net::request_multipart(
-            self.bot.client(),
-            self.bot.token(),
+        self.bot.execute_multipart(
             "sendMediaGroup",
             FormBuilder::new()
                 .add("chat_id", &self.chat_id)
-                .await
+                .await?
                 .add("media", &self.media)
-                .await
+                .await?
                 .add("disable_notification", &self.disable_notification)
-                .await
+                .await?
                 .add("reply_to_message_id", &self.reply_to_message_id)
-                .await
+                .await?
                 .build(),
         )
         .await
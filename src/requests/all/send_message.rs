@@ -1,9 +1,9 @@
 use serde::{Serialize, Deserialize};
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
-    types::{ChatId, Message, ParseMode, ReplyMarkup},
+    types::{ChatId, Message, MessageEntity, ParseMode, ReplyMarkup},
+    utils::formatted::FormattedText,
     Bot,
 };
 use std::sync::Arc;
@@ -19,6 +19,7 @@ pub struct SendMessage {
     pub chat_id: ChatId,
     pub text: String,
     pub parse_mode: Option<ParseMode>,
+    pub entities: Option<Vec<MessageEntity>>,
     pub selective: Option<bool>,
     pub disable_web_page_preview: Option<bool>,
     pub disable_notification: Option<bool>,
@@ -31,9 +32,7 @@ impl Request for SendMessage {
     type Output = Message;
 
     async fn send(&self) -> ResponseResult<Message> {
-        net::request_json(
-            self.bot.client(),
-            self.bot.token(),
+        self.bot.execute_json(
             "sendMessage",
             &self,
         )
@@ -52,6 +51,7 @@ impl SendMessage {
             chat_id: chat_id.into(),
             text: text.into(),
             parse_mode: None,
+            entities: None,
             selective: None,
             disable_web_page_preview: None,
             disable_notification: None,
@@ -91,6 +91,30 @@ impl SendMessage {
         self
     }
 
+    /// A list of special entities that appear in the message text, which can
+    /// be specified instead of [`parse_mode`], see [`utils::text_builder`].
+    ///
+    /// [`parse_mode`]: crate::requests::SendMessage::parse_mode
+    /// [`utils::text_builder`]: crate::utils::text_builder
+    pub fn entities<T>(mut self, val: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.entities = Some(val.into());
+        self
+    }
+
+    /// Sets the text together with its parse mode, see [`FormattedText`].
+    ///
+    /// [`FormattedText`]: crate::utils::formatted::FormattedText
+    pub fn formatted(mut self, val: FormattedText) -> Self {
+        let (text, parse_mode) = val.into_parts();
+        self.text = text;
+        self.parse_mode = Some(parse_mode);
+        self.entities = None;
+        self
+    }
+
     /// Disables link previews for links in this message.
     pub fn disable_web_page_preview(mut self, value: bool) -> Self {
         self.disable_web_page_preview = Some(value);
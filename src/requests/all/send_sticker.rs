@@ -1,7 +1,10 @@
 use crate::{
-    net,
-    requests::{form_builder::FormBuilder, Request, ResponseResult},
+    requests::{
+        form_builder::FormBuilder, upload::UploadRequest, Request,
+        ResponseResult,
+    },
     types::{ChatId, InputFile, Message, ReplyMarkup},
+    utils::file_id_cache::FileKind,
     Bot,
 };
 use std::sync::Arc;
@@ -26,21 +29,36 @@ impl Request for SendSticker {
     type Output = Message;
 
     async fn send(&self) -> ResponseResult<Message> {
-        net::request_multipart(
-            self.bot.client(),
-            self.bot.token(),
+        self.send_cached().await
+    }
+}
+
+#[async_trait::async_trait]
+impl UploadRequest for SendSticker {
+    const KIND: FileKind = FileKind::Sticker;
+
+    fn bot(&self) -> &Bot {
+        &self.bot
+    }
+
+    fn file(&self) -> &InputFile {
+        &self.sticker
+    }
+
+    async fn send_file(&self, sticker: &InputFile) -> ResponseResult<Message> {
+        self.bot.execute_multipart(
             "sendSticker",
             FormBuilder::new()
                 .add("chat_id", &self.chat_id)
-                .await
-                .add("sticker", &self.sticker)
-                .await
+                .await?
+                .add("sticker", sticker)
+                .await?
                 .add("disable_notification", &self.disable_notification)
-                .await
+                .await?
                 .add("reply_to_message_id", &self.reply_to_message_id)
-                .await
+                .await?
                 .add("reply_markup", &self.reply_markup)
-                .await
+                .await?
                 .build(),
         )
         .await
@@ -1,7 +1,10 @@
 use crate::{
-    net,
-    requests::{form_builder::FormBuilder, Request, ResponseResult},
-    types::{ChatId, InputFile, Message, ParseMode, ReplyMarkup},
+    requests::{
+        form_builder::FormBuilder, upload::UploadRequest, Request,
+        ResponseResult,
+    },
+    types::{ChatId, InputFile, Message, MessageEntity, ParseMode, ReplyMarkup},
+    utils::{file_id_cache::FileKind, formatted::FormattedText},
     Bot,
 };
 use std::sync::Arc;
@@ -26,6 +29,7 @@ pub struct SendVideo {
     pub thumb: Option<InputFile>,
     pub caption: Option<String>,
     pub parse_mode: Option<ParseMode>,
+    pub caption_entities: Option<Vec<MessageEntity>>,
     pub supports_streaming: Option<bool>,
     pub disable_notification: Option<bool>,
     pub reply_to_message_id: Option<i64>,
@@ -37,35 +41,52 @@ impl Request for SendVideo {
     type Output = Message;
 
     async fn send(&self) -> ResponseResult<Message> {
-        net::request_multipart(
-            self.bot.client(),
-            self.bot.token(),
+        self.send_cached().await
+    }
+}
+
+#[async_trait::async_trait]
+impl UploadRequest for SendVideo {
+    const KIND: FileKind = FileKind::Video;
+
+    fn bot(&self) -> &Bot {
+        &self.bot
+    }
+
+    fn file(&self) -> &InputFile {
+        &self.video
+    }
+
+    async fn send_file(&self, video: &InputFile) -> ResponseResult<Message> {
+        self.bot.execute_multipart(
             "sendVideo",
             FormBuilder::new()
                 .add("chat_id", &self.chat_id)
-                .await
-                .add("video", &self.video)
-                .await
+                .await?
+                .add("video", video)
+                .await?
                 .add("duration", &self.duration)
-                .await
+                .await?
                 .add("width", &self.width)
-                .await
+                .await?
                 .add("height", &self.height)
-                .await
+                .await?
                 .add("thumb", &self.thumb)
-                .await
+                .await?
                 .add("caption", &self.caption)
-                .await
+                .await?
                 .add("parse_mode", &self.parse_mode)
-                .await
+                .await?
+                .add("caption_entities", &self.caption_entities)
+                .await?
                 .add("supports_streaming", &self.supports_streaming)
-                .await
+                .await?
                 .add("disable_notification", &self.disable_notification)
-                .await
+                .await?
                 .add("reply_to_message_id", &self.reply_to_message_id)
-                .await
+                .await?
                 .add("reply_markup", &self.reply_markup)
-                .await
+                .await?
                 .build(),
         )
         .await
@@ -87,6 +108,7 @@ impl SendVideo {
             thumb: None,
             caption: None,
             parse_mode: None,
+            caption_entities: None,
             supports_streaming: None,
             disable_notification: None,
             reply_to_message_id: None,
@@ -176,6 +198,30 @@ impl SendVideo {
         self
     }
 
+    /// A list of special entities that appear in the caption, which can be
+    /// specified instead of [`parse_mode`], see [`utils::text_builder`].
+    ///
+    /// [`parse_mode`]: crate::requests::SendVideo::parse_mode
+    /// [`utils::text_builder`]: crate::utils::text_builder
+    pub fn caption_entities<T>(mut self, val: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.caption_entities = Some(val.into());
+        self
+    }
+
+    /// Sets the caption together with its parse mode, see [`FormattedText`].
+    ///
+    /// [`FormattedText`]: crate::utils::formatted::FormattedText
+    pub fn caption_formatted(mut self, val: FormattedText) -> Self {
+        let (caption, parse_mode) = val.into_parts();
+        self.caption = Some(caption);
+        self.parse_mode = Some(parse_mode);
+        self.caption_entities = None;
+        self
+    }
+
     /// Pass `true`, if the uploaded video is suitable for streaming.
     pub fn supports_streaming(mut self, val: bool) -> Self {
         self.supports_streaming = Some(val);
@@ -1,7 +1,10 @@
 use crate::{
-    net,
-    requests::{form_builder::FormBuilder, Request, ResponseResult},
+    requests::{
+        form_builder::FormBuilder, upload::UploadRequest, Request,
+        ResponseResult,
+    },
     types::{ChatId, InputFile, Message, ReplyMarkup},
+    utils::file_id_cache::FileKind,
     Bot,
 };
 use std::sync::Arc;
@@ -30,27 +33,45 @@ impl Request for SendVideoNote {
     type Output = Message;
 
     async fn send(&self) -> ResponseResult<Message> {
-        net::request_multipart(
-            self.bot.client(),
-            self.bot.token(),
+        self.send_cached().await
+    }
+}
+
+#[async_trait::async_trait]
+impl UploadRequest for SendVideoNote {
+    const KIND: FileKind = FileKind::VideoNote;
+
+    fn bot(&self) -> &Bot {
+        &self.bot
+    }
+
+    fn file(&self) -> &InputFile {
+        &self.video_note
+    }
+
+    async fn send_file(
+        &self,
+        video_note: &InputFile,
+    ) -> ResponseResult<Message> {
+        self.bot.execute_multipart(
             "sendVideoNote",
             FormBuilder::new()
                 .add("chat_id", &self.chat_id)
-                .await
-                .add("video_note", &self.video_note)
-                .await
+                .await?
+                .add("video_note", video_note)
+                .await?
                 .add("duration", &self.duration)
-                .await
+                .await?
                 .add("length", &self.length)
-                .await
+                .await?
                 .add("thumb", &self.thumb)
-                .await
+                .await?
                 .add("disable_notification", &self.disable_notification)
-                .await
+                .await?
                 .add("reply_to_message_id", &self.reply_to_message_id)
-                .await
+                .await?
                 .add("reply_markup", &self.reply_markup)
-                .await
+                .await?
                 .build(),
         )
         .await
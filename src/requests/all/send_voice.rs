@@ -1,7 +1,10 @@
 use crate::{
-    net,
-    requests::{form_builder::FormBuilder, Request, ResponseResult},
-    types::{ChatId, InputFile, Message, ParseMode, ReplyMarkup},
+    requests::{
+        form_builder::FormBuilder, upload::UploadRequest, Request,
+        ResponseResult,
+    },
+    types::{ChatId, InputFile, Message, MessageEntity, ParseMode, ReplyMarkup},
+    utils::{file_id_cache::FileKind, formatted::FormattedText},
     Bot,
 };
 use std::sync::Arc;
@@ -27,6 +30,7 @@ pub struct SendVoice {
     pub voice: InputFile,
     pub caption: Option<String>,
     pub parse_mode: Option<ParseMode>,
+    pub caption_entities: Option<Vec<MessageEntity>>,
     pub duration: Option<i32>,
     pub disable_notification: Option<bool>,
     pub reply_to_message_id: Option<i64>,
@@ -38,27 +42,44 @@ impl Request for SendVoice {
     type Output = Message;
 
     async fn send(&self) -> ResponseResult<Message> {
-        net::request_multipart(
-            self.bot.client(),
-            self.bot.token(),
+        self.send_cached().await
+    }
+}
+
+#[async_trait::async_trait]
+impl UploadRequest for SendVoice {
+    const KIND: FileKind = FileKind::Voice;
+
+    fn bot(&self) -> &Bot {
+        &self.bot
+    }
+
+    fn file(&self) -> &InputFile {
+        &self.voice
+    }
+
+    async fn send_file(&self, voice: &InputFile) -> ResponseResult<Message> {
+        self.bot.execute_multipart(
             "sendVoice",
             FormBuilder::new()
                 .add("chat_id", &self.chat_id)
-                .await
-                .add("voice", &self.voice)
-                .await
+                .await?
+                .add("voice", voice)
+                .await?
                 .add("caption", &self.caption)
-                .await
+                .await?
                 .add("parse_mode", &self.parse_mode)
-                .await
+                .await?
+                .add("caption_entities", &self.caption_entities)
+                .await?
                 .add("duration", &self.duration)
-                .await
+                .await?
                 .add("disable_notification", &self.disable_notification)
-                .await
+                .await?
                 .add("reply_to_message_id", &self.reply_to_message_id)
-                .await
+                .await?
                 .add("reply_markup", &self.reply_markup)
-                .await
+                .await?
                 .build(),
         )
         .await
@@ -76,6 +97,7 @@ impl SendVoice {
             voice,
             caption: None,
             parse_mode: None,
+            caption_entities: None,
             duration: None,
             disable_notification: None,
             reply_to_message_id: None,
@@ -130,6 +152,30 @@ impl SendVoice {
         self
     }
 
+    /// A list of special entities that appear in the caption, which can be
+    /// specified instead of [`parse_mode`], see [`utils::text_builder`].
+    ///
+    /// [`parse_mode`]: crate::requests::SendVoice::parse_mode
+    /// [`utils::text_builder`]: crate::utils::text_builder
+    pub fn caption_entities<T>(mut self, val: T) -> Self
+    where
+        T: Into<Vec<MessageEntity>>,
+    {
+        self.caption_entities = Some(val.into());
+        self
+    }
+
+    /// Sets the caption together with its parse mode, see [`FormattedText`].
+    ///
+    /// [`FormattedText`]: crate::utils::formatted::FormattedText
+    pub fn caption_formatted(mut self, val: FormattedText) -> Self {
+        let (caption, parse_mode) = val.into_parts();
+        self.caption = Some(caption);
+        self.parse_mode = Some(parse_mode);
+        self.caption_entities = None;
+        self
+    }
+
     /// Duration of the voice message in seconds.
     pub fn duration(mut self, val: i32) -> Self {
         self.duration = Some(val);
@@ -1,19 +1,18 @@
 use serde::Serialize;
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
     types::{ChatId, True, GuildCredit},
     Bot,
 };
 use std::sync::Arc;
 
-/// Use this method to change the title of a chat.
+/// Use this method to set a guild credit of a user.
 ///
-/// Titles can't be changed for private chats. The bot must be an administrator
-/// in the chat for this to work and must have the appropriate admin rights.
+/// Use [`GuildCreditBuilder`] to check the credit against the platform limits
+/// before sending it.
 ///
-/// [The official docs](https://core.telegram.org/bots/api#setchattitle).
+/// [`GuildCreditBuilder`]: crate::types::GuildCreditBuilder
 #[serde_with_macros::skip_serializing_none]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SetGuildCredit {
@@ -30,9 +29,7 @@ impl Request for SetGuildCredit {
     type Output = True;
 
     async fn send(&self) -> ResponseResult<True> {
-        net::request_json(
-            self.bot.client(),
-            self.bot.token(),
+        self.bot.execute_json(
             "setGuildCredit",
             &self,
         )
@@ -54,8 +51,8 @@ impl SetGuildCredit {
         Self { bot, chat_id, guild_id, user_id, guild_credit }
     }
 
-    /// New chat title, 1-255 characters.
-    pub fn guild_credit<T>(mut self, guild_credit: GuildCredit) -> Self {
+    /// A new guild credit of the user.
+    pub fn guild_credit(mut self, guild_credit: GuildCredit) -> Self {
         self.guild_credit = guild_credit;
         self
     }
@@ -1,7 +1,6 @@
 use serde::Serialize;
 
 use crate::{
-    net,
     requests::{Request, ResponseResult},
     types::{GuildRole, ChatId, True},
     Bot,
@@ -28,7 +27,7 @@ impl Request for SetMemberRoles {
     type Output = True;
 
     async fn send(&self) -> ResponseResult<True> {
-        net::request_json(self.bot.client(), self.bot.token(), "setMemberRoles", &self)
+        self.bot.execute_json("setMemberRoles", &self)
             .await
     }
 }
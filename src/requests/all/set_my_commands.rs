@@ -0,0 +1,49 @@
+use serde::Serialize;
+
+use crate::{
+    requests::{Request, ResponseResult},
+    types::{BotCommand, True},
+    Bot,
+};
+use std::sync::Arc;
+
+/// Use this method to change the list of the bot's commands.
+///
+/// The list is shown to users in the command menu of the client.
+///
+/// [The official docs](https://core.telegram.org/bots/api#setmycommands).
+#[derive(Debug, Clone, Serialize)]
+pub struct SetMyCommands {
+    #[serde(skip_serializing)]
+    bot: Arc<Bot>,
+    commands: Vec<BotCommand>,
+}
+
+#[async_trait::async_trait]
+impl Request for SetMyCommands {
+    type Output = True;
+
+    async fn send(&self) -> ResponseResult<True> {
+        self.bot.execute_json("setMyCommands", &self).await
+    }
+}
+
+impl SetMyCommands {
+    pub(crate) fn new<C>(bot: Arc<Bot>, commands: C) -> Self
+    where
+        C: Into<Vec<BotCommand>>,
+    {
+        let commands = commands.into();
+        Self { bot, commands }
+    }
+
+    /// A list of bot commands to be set as the list of the bot's commands. At
+    /// most 100 commands can be specified.
+    pub fn commands<C>(mut self, val: C) -> Self
+    where
+        C: Into<Vec<BotCommand>>,
+    {
+        self.commands = val.into();
+        self
+    }
+}
@@ -1,8 +1,7 @@
 use serde::Serialize;
 
 use crate::{
-    net,
-    requests::{Request, ResponseResult},
+    requests::{form_builder::FormBuilder, Request, ResponseResult},
     types::{File, InputFile},
     Bot,
 };
@@ -29,11 +28,14 @@ impl Request for UploadStickerFile {
     type Output = File;
 
     async fn send(&self) -> ResponseResult<File> {
-        net::request_json(
-            self.bot.client(),
-            self.bot.token(),
+        self.bot.execute_multipart(
             "uploadStickerFile",
-            &self,
+            FormBuilder::new()
+                .add("user_id", &self.user_id)
+                .await?
+                .add("png_sticker", &self.png_sticker)
+                .await?
+                .build(),
         )
         .await
     }
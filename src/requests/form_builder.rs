@@ -1,13 +1,19 @@
 use std::{borrow::Cow, path::PathBuf};
 
-use reqwest::multipart::Form;
+use bytes::Bytes;
+use reqwest::multipart::{Form, Part};
+use serde_json::Value;
 
 use crate::{
-    requests::utils::{file_from_memory_to_part, file_to_part},
+    requests::utils::{
+        bytes_to_part, file_from_memory_to_part, file_to_part, max_upload_size,
+        stream_to_part, MAX_PHOTO_SIZE, MAX_UPLOAD_SIZE,
+    },
     types::{
-        ChatId, InlineKeyboardMarkup, InputFile, InputMedia, MaskPosition,
-        ParseMode, ReplyMarkup,
+        ChatId, InlineKeyboardMarkup, InputFile, InputMedia, InputStream,
+        MaskPosition, MessageEntity, ParseMode, ReplyMarkup,
     },
+    RequestError,
 };
 
 /// This is a convenient struct that builds `reqwest::multipart::Form`
@@ -22,52 +28,64 @@ impl FormBuilder {
     }
 
     /// Add the supplied key-value pair to this `FormBuilder`.
-    pub async fn add<'a, T, N>(self, name: N, value: &T) -> Self
+    ///
+    /// Fails if a file to be uploaded cannot be read, is empty or is too
+    /// large.
+    pub async fn add<'a, T, N>(
+        self,
+        name: N,
+        value: &T,
+    ) -> Result<Self, RequestError>
     where
         N: Into<Cow<'a, str>>,
         T: IntoFormValue,
     {
         let name = name.into().into_owned();
         match value.into_form_value() {
-            Some(FormValue::Str(string)) => {
-                Self { form: self.form.text(name, string) }
+            Some(FormValue::Attached { json, files }) => {
+                let mut this = Self { form: self.form.text(name, json) };
+                for Attachment { name, value, max_size } in files {
+                    this = this.add_value(name, value, max_size).await?;
+                }
+                Ok(this)
             }
-            Some(FormValue::File(path)) => self.add_file(name, path).await,
-            Some(FormValue::Memory { file_name, data }) => {
-                self.add_file_from_memory(name, file_name, data)
+            Some(value) => {
+                let max_size = max_upload_size(&name);
+                self.add_value(name, value, max_size).await
             }
-            None => self,
+            None => Ok(self),
         }
     }
 
-    // used in SendMediaGroup
-    pub async fn add_file<'a, N>(self, name: N, path_to_file: PathBuf) -> Self
-    where
-        N: Into<Cow<'a, str>>,
-    {
-        Self {
-            form: self.form.part(
-                name.into().into_owned(),
-                file_to_part(path_to_file).await,
-            ),
-        }
+    async fn add_value(
+        self,
+        name: String,
+        value: FormValue,
+        max_size: u64,
+    ) -> Result<Self, RequestError> {
+        let part = match value {
+            FormValue::Str(string) => {
+                return Ok(Self { form: self.form.text(name, string) })
+            }
+            FormValue::File(path) => file_to_part(path, max_size).await?,
+            FormValue::Memory { file_name, data } => {
+                file_from_memory_to_part(data, file_name, max_size)?
+            }
+            FormValue::Bytes { file_name, data } => {
+                bytes_to_part(data, file_name, max_size)?
+            }
+            FormValue::Stream { file_name, stream } => {
+                stream_to_part(stream, file_name, max_size).await?
+            }
+            FormValue::Attached { .. } => {
+                unreachable!("attachments are never nested")
+            }
+        };
+        Ok(self.add_part(name, part))
     }
 
-    fn add_file_from_memory<'a, N>(
-        self,
-        name: N,
-        file_name: String,
-        data: Cow<'static, [u8]>,
-    ) -> Self
-    where
-        N: Into<Cow<'a, str>>,
-    {
-        Self {
-            form: self.form.part(
-                name.into().into_owned(),
-                file_from_memory_to_part(data, file_name),
-            ),
-        }
+    fn add_part(self, name: String, part: Part) -> Self {
+        Self { form: self.form.part(name, part) }
     }
 
     pub fn build(self) -> Form {
@@ -77,8 +95,33 @@ impl FormBuilder {
 
 pub(crate) enum FormValue {
     File(PathBuf),
-    Memory { file_name: String, data: Cow<'static, [u8]> },
+    Memory {
+        file_name: String,
+        data: Cow<'static, [u8]>,
+    },
+    Bytes {
+        file_name: String,
+        data: Bytes,
+    },
+    Stream {
+        file_name: String,
+        stream: InputStream,
+    },
     Str(String),
+
+    /// JSON referring to `files` with `attach://<name>`.
+    Attached {
+        json: String,
+        files: Vec<Attachment>,
+    },
+}
+
+/// A file sent as a separate part named `name`, which is referred to from
+/// another field.
+pub(crate) struct Attachment {
+    name: String,
+    value: FormValue,
+    max_size: u64,
 }
 
 pub(crate) trait IntoFormValue {
@@ -106,7 +149,8 @@ impl_for_struct!(
     u32,
     ReplyMarkup,
     InlineKeyboardMarkup,
-    MaskPosition
+    MaskPosition,
+    Vec<MessageEntity>
 );
 
 impl<T> IntoFormValue for Option<T>
@@ -118,21 +162,61 @@ where
     }
 }
 
-// TODO: fix InputMedia implementation of IntoFormValue (for now it doesn't
-// encode files :|)
 impl IntoFormValue for Vec<InputMedia> {
     fn into_form_value(&self) -> Option<FormValue> {
-        let json =
-            serde_json::to_string(self).expect("serde_json::to_string failed");
-        Some(FormValue::Str(json))
+        let mut json =
+            serde_json::to_value(self).expect("serde_json::to_value failed");
+        let mut files = Vec::new();
+        if let Value::Array(items) = &mut json {
+            for (media, item) in self.iter().zip(items) {
+                attach_media(media, item, &mut files);
+            }
+        }
+        Some(FormValue::Attached { json: json.to_string(), files })
     }
 }
 
 impl IntoFormValue for InputMedia {
     fn into_form_value(&self) -> Option<FormValue> {
-        let json =
-            serde_json::to_string(self).expect("serde_json::to_string failed");
-        Some(FormValue::Str(json))
+        let mut json =
+            serde_json::to_value(self).expect("serde_json::to_value failed");
+        let mut files = Vec::new();
+        attach_media(self, &mut json, &mut files);
+        Some(FormValue::Attached { json: json.to_string(), files })
+    }
+}
+
+/// Replaces files of `media` to be uploaded in its serialized form `json`
+/// with references to `files`, named `file0`, `file1`, etc.
+fn attach_media(
+    media: &InputMedia,
+    json: &mut Value,
+    files: &mut Vec<Attachment>,
+) {
+    let (thumb, max_size) = match media {
+        InputMedia::Photo(_) => (None, MAX_PHOTO_SIZE),
+        InputMedia::Video(video) => (video.thumb.as_ref(), MAX_UPLOAD_SIZE),
+        InputMedia::Animation(animation) => {
+            (animation.thumb.as_ref(), MAX_UPLOAD_SIZE)
+        }
+        InputMedia::Audio(audio) => (audio.thumb.as_ref(), MAX_UPLOAD_SIZE),
+        InputMedia::Document(document) => {
+            (document.thumb.as_ref(), MAX_UPLOAD_SIZE)
+        }
+    };
+
+    for (field, file) in Some(("media", media.media()))
+        .into_iter()
+        .chain(thumb.map(|t| ("thumb", t)))
+    {
+        match file.into_form_value() {
+            Some(FormValue::Str(_)) | None => {}
+            Some(value) => {
+                let name = format!("file{}", files.len());
+                json[field] = Value::String(format!("attach://{}", name));
+                files.push(Attachment { name, value, max_size });
+            }
+        }
     }
 }
 
@@ -179,8 +263,85 @@ impl IntoFormValue for InputFile {
                 file_name: file_name.clone(),
                 data: data.clone(),
             }),
+            InputFile::Bytes { file_name, data } => Some(FormValue::Bytes {
+                file_name: file_name.clone(),
+                data: data.clone(),
+            }),
+            InputFile::Stream { file_name, stream } => {
+                Some(FormValue::Stream {
+                    file_name: file_name.clone(),
+                    stream: stream.clone(),
+                })
+            }
             InputFile::Url(url) => Some(FormValue::Str(url.clone())),
             InputFile::FileId(file_id) => Some(FormValue::Str(file_id.clone())),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{InputMediaDocument, InputMediaPhoto};
+
+    fn photo(media: InputFile) -> InputMedia {
+        InputMedia::Photo(InputMediaPhoto {
+            media,
+            caption: None,
+            parse_mode: None,
+        })
+    }
+
+    #[test]
+    fn attached_media() {
+        let media = vec![
+            photo(InputFile::memory("a.png", &b"png"[..])),
+            photo(InputFile::url("https://example.com/b.png")),
+            InputMedia::Document(InputMediaDocument {
+                media: InputFile::file("c.txt"),
+                thumb: Some(InputFile::bytes("c.jpg", &b"jpg"[..])),
+                caption: None,
+                parse_mode: None,
+            }),
+        ];
+
+        let (json, files) = match media.into_form_value() {
+            Some(FormValue::Attached { json, files }) => (json, files),
+            _ => panic!("media must be attached"),
+        };
+        let json: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json[0]["media"], "attach://file0");
+        assert_eq!(json[1]["media"], "https://example.com/b.png");
+        assert_eq!(json[2]["media"], "attach://file1");
+        assert_eq!(json[2]["thumb"], "attach://file2");
+
+        let files: Vec<_> = files
+            .iter()
+            .map(|file| (file.name.as_str(), file.max_size))
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                ("file0", MAX_PHOTO_SIZE),
+                ("file1", MAX_UPLOAD_SIZE),
+                ("file2", MAX_UPLOAD_SIZE)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_media() {
+        let add = |media: InputMedia| async move {
+            FormBuilder::new().add("media", &vec![media]).await.map(|_| ())
+        };
+
+        let missing = photo(InputFile::file("/nonexistent/photo.png"));
+        assert!(matches!(add(missing).await, Err(RequestError::Io(_))));
+
+        let large = photo(InputFile::memory(
+            "large.png",
+            vec![0; MAX_PHOTO_SIZE as usize + 1],
+        ));
+        assert!(matches!(add(large).await, Err(RequestError::InvalidInput(_))));
+    }
+}
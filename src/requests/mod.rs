@@ -2,6 +2,7 @@
 
 mod all;
 mod form_builder;
+mod upload;
 mod utils;
 
 pub use all::*;
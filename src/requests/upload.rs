@@ -0,0 +1,55 @@
+use crate::{
+    requests::ResponseResult,
+    types::{InputFile, Message},
+    utils::file_id_cache::FileKind,
+    ApiErrorKind, Bot, RequestError,
+};
+
+/// A request uploading a file, which can be replaced with an ID of the file
+/// uploaded earlier (see [`FileIdCache`]).
+///
+/// [`FileIdCache`]: crate::utils::file_id_cache::FileIdCache
+#[async_trait::async_trait]
+pub(crate) trait UploadRequest: Sync {
+    const KIND: FileKind;
+
+    fn bot(&self) -> &Bot;
+
+    fn file(&self) -> &InputFile;
+
+    /// Sends the request with `file` instead of [`UploadRequest::file`].
+    async fn send_file(&self, file: &InputFile) -> ResponseResult<Message>;
+
+    /// Sends the request, using [`Bot::file_id_cache`] if it is set.
+    ///
+    /// If a cached ID is rejected, the file is uploaded again.
+    async fn send_cached(&self) -> ResponseResult<Message> {
+        let file = self.file();
+        let cache = match self.bot().file_id_cache() {
+            Some(cache) => cache,
+            None => return self.send_file(file).await,
+        };
+        let (key, file_id) = match cache.lookup(Self::KIND, file).await {
+            Some(found) => found,
+            None => return self.send_file(file).await,
+        };
+
+        if let Some(file_id) = file_id {
+            match self.send_file(&InputFile::FileId(file_id)).await {
+                Err(RequestError::ApiError {
+                    kind: ApiErrorKind::WrongFileID,
+                    ..
+                })
+                | Err(RequestError::ApiError {
+                    kind: ApiErrorKind::FileIdInvalid,
+                    ..
+                }) => cache.forget(&key),
+                result => return result,
+            }
+        }
+
+        let message = self.send_file(file).await?;
+        cache.remember(Self::KIND, key, &message);
+        Ok(message)
+    }
+}
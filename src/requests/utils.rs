@@ -1,9 +1,29 @@
-use std::{borrow::Cow, path::PathBuf};
+use std::{
+    borrow::Cow,
+    io,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll},
+};
 
 use bytes::{Bytes, BytesMut};
+use futures::{future, stream, Stream, StreamExt, TryStreamExt};
 use reqwest::{multipart::Part, Body};
+use tokio::io::{AsyncRead, AsyncReadExt};
 use tokio_util::codec::{Decoder, FramedRead};
 
+use crate::{types::InputStream, RequestError};
+
+/// The maximum size of an uploaded file.
+pub(crate) const MAX_UPLOAD_SIZE: u64 = 50 * 1024 * 1024;
+
+/// The maximum size of an uploaded photo.
+pub(crate) const MAX_PHOTO_SIZE: u64 = 10 * 1024 * 1024;
+
+/// How many first bytes of a file are used to detect its MIME type.
+const SNIFF_LEN: usize = 16;
+
 struct FileDecoder;
 
 impl Decoder for FileDecoder {
@@ -21,23 +41,304 @@ impl Decoder for FileDecoder {
     }
 }
 
-pub async fn file_to_part(path_to_file: PathBuf) -> Part {
-    let file_name =
-        path_to_file.file_name().unwrap().to_string_lossy().into_owned();
+/// The maximum size of a file uploaded as a field `name`.
+pub(crate) fn max_upload_size(name: &str) -> u64 {
+    match name {
+        "photo" => MAX_PHOTO_SIZE,
+        _ => MAX_UPLOAD_SIZE,
+    }
+}
+
+pub async fn file_to_part(
+    path_to_file: PathBuf,
+    max_size: u64,
+) -> Result<Part, RequestError> {
+    let file_name = match path_to_file.file_name() {
+        Some(name) => name.to_string_lossy().into_owned(),
+        None => {
+            return Err(RequestError::InvalidInput(format!(
+                "{} is not a file",
+                path_to_file.display()
+            )))
+        }
+    };
 
-    let file = FramedRead::new(
-        tokio::fs::File::open(path_to_file).await.unwrap(), /* TODO: this
-                                                             * can
-                                                             * cause panics */
-        FileDecoder,
-    );
+    let file =
+        tokio::fs::File::open(&path_to_file).await.map_err(RequestError::Io)?;
+    let metadata = file.metadata().await.map_err(RequestError::Io)?;
+    if !metadata.is_file() {
+        return Err(RequestError::InvalidInput(format!(
+            "{} is not a file",
+            path_to_file.display()
+        )));
+    }
 
-    Part::stream(Body::wrap_stream(file)).file_name(file_name)
+    reader_to_part(file_name, Box::pin(file), Some(metadata.len()), max_size)
+        .await
 }
 
 pub fn file_from_memory_to_part(
     data: Cow<'static, [u8]>,
     name: String,
-) -> Part {
-    Part::bytes(data).file_name(name)
+    max_size: u64,
+) -> Result<Part, RequestError> {
+    check_size(&name, data.len() as u64, max_size)?;
+
+    let mime = mime_type(&name, &data);
+    with_mime(Part::bytes(data).file_name(name), mime)
+}
+
+pub fn bytes_to_part(
+    data: Bytes,
+    name: String,
+    max_size: u64,
+) -> Result<Part, RequestError> {
+    check_size(&name, data.len() as u64, max_size)?;
+
+    let mime = mime_type(&name, &data);
+    with_mime(Part::stream(Body::from(data)).file_name(name), mime)
+}
+
+/// Fails if the stream is already uploaded.
+pub async fn stream_to_part(
+    stream: InputStream,
+    name: String,
+    max_size: u64,
+) -> Result<Part, RequestError> {
+    match stream.take() {
+        Some(reader) => {
+            reader_to_part(name, reader, stream.len, max_size).await
+        }
+        None => Err(RequestError::InvalidInput(format!(
+            "{} is already uploaded",
+            name
+        ))),
+    }
+}
+
+/// Makes a part streaming `reader`.
+///
+/// If `len` is unknown, the part is sent chunked and fails when more than
+/// `max_size` bytes are read.
+async fn reader_to_part(
+    file_name: String,
+    mut reader: Pin<Box<dyn AsyncRead + Send>>,
+    len: Option<u64>,
+    max_size: u64,
+) -> Result<Part, RequestError> {
+    if let Some(len) = len {
+        check_size(&file_name, len, max_size)?;
+    }
+
+    let head = read_head(&mut reader).await.map_err(RequestError::Io)?;
+    if head.is_empty() {
+        check_size(&file_name, 0, max_size)?;
+    }
+    let mime = mime_type(&file_name, &head);
+
+    // The first bytes are already read, so they are sent before the rest of
+    // the file.
+    let mut read = 0;
+    let body = stream::once(future::ready(Ok(Bytes::from(head))))
+        .chain(FramedRead::new(reader, FileDecoder))
+        .and_then(move |chunk| {
+            read += chunk.len() as u64;
+            future::ready(if read > max_size {
+                Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("the file is larger than {} bytes", max_size),
+                ))
+            } else {
+                Ok(chunk)
+            })
+        });
+    let body: ByteStream = Box::pin(body);
+    let body = Body::wrap_stream(SyncStream(Mutex::new(body)));
+
+    let part = match len {
+        Some(len) => Part::stream_with_length(body, len),
+        None => Part::stream(body),
+    };
+    with_mime(part.file_name(file_name), mime)
+}
+
+type ByteStream = Pin<Box<dyn Stream<Item = io::Result<Bytes>> + Send>>;
+
+/// A stream which is `Sync`, as required by [`Body::wrap_stream`], even if a
+/// reader of a file is not.
+///
+/// The stream is polled only through `&mut`, so the mutex is never locked.
+struct SyncStream(Mutex<ByteStream>);
+
+impl Stream for SyncStream {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        match self.get_mut().0.get_mut() {
+            Ok(stream) => stream.as_mut().poll_next(cx),
+            Err(poisoned) => poisoned.into_inner().as_mut().poll_next(cx),
+        }
+    }
+}
+
+fn check_size(
+    file_name: &str,
+    size: u64,
+    max_size: u64,
+) -> Result<(), RequestError> {
+    if size == 0 {
+        return Err(RequestError::InvalidInput(format!(
+            "{} is empty",
+            file_name
+        )));
+    }
+    if size > max_size {
+        return Err(RequestError::InvalidInput(format!(
+            "{} is larger than {} bytes",
+            file_name, max_size
+        )));
+    }
+
+    Ok(())
+}
+
+fn with_mime(part: Part, mime: Option<&str>) -> Result<Part, RequestError> {
+    match mime {
+        Some(mime) => part.mime_str(mime).map_err(RequestError::NetworkError),
+        None => Ok(part),
+    }
+}
+
+/// Reads up to [`SNIFF_LEN`] first bytes of `reader`.
+async fn read_head<R>(reader: &mut R) -> std::io::Result<Vec<u8>>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut head = vec![0; SNIFF_LEN];
+    let mut len = 0;
+    while len < head.len() {
+        match reader.read(&mut head[len..]).await? {
+            0 => break,
+            read => len += read,
+        }
+    }
+
+    head.truncate(len);
+    Ok(head)
+}
+
+/// Detects a MIME type of a file by its first bytes, falling back to its
+/// extension.
+pub(crate) fn mime_type(file_name: &str, head: &[u8]) -> Option<&'static str> {
+    mime_type_by_magic(head).or_else(|| mime_type_by_extension(file_name))
+}
+
+fn mime_type_by_magic(head: &[u8]) -> Option<&'static str> {
+    let mime = match head {
+        [0xFF, 0xD8, 0xFF, ..] => "image/jpeg",
+        [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, ..] => "image/png",
+        [b'G', b'I', b'F', b'8', b'7', b'a', ..]
+        | [b'G', b'I', b'F', b'8', b'9', b'a', ..] => "image/gif",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => {
+            "image/webp"
+        }
+        [b'%', b'P', b'D', b'F', b'-', ..] => "application/pdf",
+        [b'O', b'g', b'g', b'S', ..] => "audio/ogg",
+        [b'I', b'D', b'3', ..] | [0xFF, 0xFB, ..] => "audio/mpeg",
+        [_, _, _, _, b'f', b't', b'y', b'p', ..] => "video/mp4",
+        [0x1A, 0x45, 0xDF, 0xA3, ..] => "video/webm",
+        [b'P', b'K', 0x03, 0x04, ..] => "application/zip",
+        _ => return None,
+    };
+
+    Some(mime)
+}
+
+fn mime_type_by_extension(file_name: &str) -> Option<&'static str> {
+    let extension = Path::new(file_name).extension()?.to_str()?;
+    let mime = match extension.to_ascii_lowercase().as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "pdf" => "application/pdf",
+        "ogg" | "oga" | "opus" => "audio/ogg",
+        "mp3" => "audio/mpeg",
+        "m4a" => "audio/mp4",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "zip" => "application/zip",
+        "tgs" => "application/x-tgsticker",
+        "txt" => "text/plain",
+        "json" => "application/json",
+        _ => return None,
+    };
+
+    Some(mime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mime_types() {
+        assert_eq!(
+            mime_type("a.bin", b"\x89PNG\r\n\x1a\nrest"),
+            Some("image/png")
+        );
+        assert_eq!(mime_type("a.png", b"\xff\xd8\xff\xe0"), Some("image/jpeg"));
+        assert_eq!(mime_type("a.WEBP", b"RIFF"), Some("image/webp"));
+        assert_eq!(mime_type("a", b"\0\0\0\x18ftypmp42"), Some("video/mp4"));
+        assert_eq!(mime_type("a.bin", b"unknown"), None);
+    }
+
+    #[test]
+    fn memory_sizes() {
+        let part = |data: &'static [u8]| {
+            file_from_memory_to_part(data.into(), "a.txt".to_owned(), 3)
+        };
+
+        assert!(part(b"abc").is_ok());
+        assert!(matches!(part(b""), Err(RequestError::InvalidInput(_))));
+        assert!(matches!(part(b"abcd"), Err(RequestError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn streams() {
+        let stream = InputStream::new(&b"abc"[..], None);
+        let part = |stream| stream_to_part(stream, "a.txt".to_owned(), 3);
+
+        assert!(part(stream.clone()).await.is_ok());
+        assert!(matches!(
+            part(stream).await,
+            Err(RequestError::InvalidInput(_))
+        ));
+
+        let empty = InputStream::new(&b""[..], None);
+        assert!(matches!(
+            part(empty).await,
+            Err(RequestError::InvalidInput(_))
+        ));
+
+        let large = InputStream::new(&b"abcd"[..], Some(4));
+        assert!(matches!(
+            part(large).await,
+            Err(RequestError::InvalidInput(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn missing_file() {
+        let result =
+            file_to_part("/nonexistent/photo.png".into(), MAX_UPLOAD_SIZE)
+                .await;
+        assert!(matches!(result, Err(RequestError::Io(_))));
+
+        let result = file_to_part("/".into(), MAX_UPLOAD_SIZE).await;
+        assert!(matches!(result, Err(RequestError::InvalidInput(_))));
+    }
 }
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// This object represents a bot command.
+///
+/// [The official docs](https://core.telegram.org/bots/api#botcommand).
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct BotCommand {
+    /// Text of the command, 1-32 characters. Can contain only lowercase
+    /// English letters, digits and underscores.
+    pub command: String,
+
+    /// Description of the command, 3-256 characters.
+    pub description: String,
+}
+
+impl BotCommand {
+    pub fn new<C, D>(command: C, description: D) -> Self
+    where
+        C: Into<String>,
+        D: Into<String>,
+    {
+        Self { command: command.into(), description: description.into() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize() {
+        let command = BotCommand::new("start", "Start the bot");
+        let expected = r#"{"command":"start","description":"Start the bot"}"#;
+        let actual = serde_json::to_string(&command).unwrap();
+        assert_eq!(actual, expected);
+    }
+}
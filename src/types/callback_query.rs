@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::types::{Message, User};
+use crate::types::{ChatOrInlineMessage, Message, User};
 
 /// This object represents an incoming callback query from a callback button in
 /// an [inline keyboard].
@@ -49,6 +49,29 @@ pub struct CallbackQuery {
     pub game_short_name: Option<String>,
 }
 
+impl CallbackQuery {
+    /// Returns the message with the callback button that originated the
+    /// query.
+    ///
+    /// Returns `None` if there is neither [`message`] nor a valid
+    /// [`inline_message_id`].
+    ///
+    /// [`message`]: crate::types::CallbackQuery::message
+    /// [`inline_message_id`]: crate::types::CallbackQuery::inline_message_id
+    pub fn chat_or_inline_message(&self) -> Option<ChatOrInlineMessage> {
+        match (&self.message, &self.inline_message_id) {
+            (Some(message), _) => Some(ChatOrInlineMessage::Chat {
+                chat_id: message.chat.id.into(),
+                message_id: message.id,
+            }),
+            (None, Some(id)) => Some(ChatOrInlineMessage::Inline {
+                inline_message_id: id.parse().ok()?,
+            }),
+            (None, None) => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,6 +99,7 @@ mod tests {
                 last_name: None,
                 username: None,
                 language_code: None,
+                ..User::default()
             },
             chat_instance: "123456".to_string(),
             message: None,
@@ -86,4 +110,26 @@ mod tests {
         let actual = serde_json::from_str::<CallbackQuery>(json).unwrap();
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn chat_or_inline_message() {
+        let query = |inline_message_id: &str| {
+            serde_json::from_str::<CallbackQuery>(&format!(
+                r#"{{
+                    "id":"id",
+                    "from":{{"id":1,"is_bot":false,"first_name":"a"}},
+                    "inline_message_id":"{}",
+                    "chat_instance":"1"
+                }}"#,
+                inline_message_id
+            ))
+            .unwrap()
+        };
+
+        assert_eq!(
+            query("123").chat_or_inline_message(),
+            Some(ChatOrInlineMessage::Inline { inline_message_id: 123 })
+        );
+        assert_eq!(query("i_m_id").chat_or_inline_message(), None);
+    }
 }
@@ -15,8 +15,18 @@ pub struct Chat {
     /// identifier.
     pub id: i64,
 
-    #[serde(default)]
-    pub guild_id: i64,
+    /// An identifier of a guild this chat belongs to. `None` for chats outside
+    /// of guilds (e.g. direct messages).
+    ///
+    /// `0`, which is sent by the platform for such chats, is also
+    /// deserialized as `None`.
+    #[serde(default, deserialize_with = "deserialize_guild_id")]
+    pub guild_id: Option<i64>,
+
+    /// A kind of a guild channel. `None` if it isn't specified by the
+    /// platform.
+    #[serde(rename = "channel_type")]
+    pub channel_kind: Option<ChannelKind>,
 
     #[serde(flatten)]
     pub kind: ChatKind,
@@ -27,6 +37,27 @@ pub struct Chat {
     pub photo: Option<ChatPhoto>,
 }
 
+/// A kind of a guild channel.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChannelKind {
+    /// A text channel.
+    Text,
+
+    /// A circle (a feed of posts with comments).
+    Circle,
+
+    /// A voice channel.
+    Voice,
+
+    /// A direct message chat with a user.
+    Dm,
+
+    /// A channel kind, which is unknown to this version of the library.
+    #[serde(other)]
+    Unknown,
+}
+
 #[serde_with_macros::skip_serializing_none]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -179,6 +210,22 @@ fn assert_private_field<'de, D>(des: D) -> Result<(), D::Error>
     des.deserialize_str(PrivateChatKindVisitor)
 }
 
+fn deserialize_guild_id<'de, D>(des: D) -> Result<Option<i64>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+{
+    Option::<i64>::deserialize(des).map(|id| id.and_then(guild_id_from_raw))
+}
+
+/// Converts a raw guild identifier into `Option<i64>`, treating `0` as an
+/// absent guild.
+pub(crate) fn guild_id_from_raw(guild_id: i64) -> Option<i64> {
+    match guild_id {
+        0 => None,
+        id => Some(id),
+    }
+}
+
 fn ser_private_field<S>(_: &(), s: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
@@ -220,6 +267,39 @@ impl Chat {
     pub fn is_chat(&self) -> bool {
         self.is_private() || self.is_group() || self.is_supergroup()
     }
+
+    /// Returns `true` if this is a direct message chat with a user.
+    pub fn is_dm(&self) -> bool {
+        match self.channel_kind {
+            Some(ChannelKind::Dm) => true,
+            Some(_) => false,
+            None => self.is_private(),
+        }
+    }
+
+    /// Returns an identifier of a guild this chat belongs to.
+    ///
+    /// Use [`Bot::get_guild`] to get the full information about the guild.
+    ///
+    /// [`Bot::get_guild`]: crate::Bot::get_guild
+    pub fn guild(&self) -> Option<i64> {
+        self.guild_id
+    }
+
+    /// Returns `true` if this chat is a text channel of a guild.
+    pub fn is_text_channel(&self) -> bool {
+        self.channel_kind == Some(ChannelKind::Text)
+    }
+
+    /// Returns `true` if this chat is a circle of a guild.
+    pub fn is_circle(&self) -> bool {
+        self.channel_kind == Some(ChannelKind::Circle)
+    }
+
+    /// Returns `true` if this chat is a voice channel of a guild.
+    pub fn is_voice(&self) -> bool {
+        self.channel_kind == Some(ChannelKind::Voice)
+    }
 }
 
 #[cfg(test)]
@@ -232,7 +312,8 @@ mod tests {
     fn channel_de() {
         let expected = Chat {
             id: -1,
-            guild_id: 0,
+            guild_id: None,
+            channel_kind: None,
             kind: ChatKind::Public(ChatPublic {
                 title: None,
                 kind: PublicChatKind::Channel(PublicChatChannel {
@@ -255,7 +336,8 @@ mod tests {
         assert_eq!(
             Chat {
                 id: 0,
-                guild_id: 0,
+                guild_id: None,
+                channel_kind: None,
                 kind: ChatKind::Private(ChatPrivate {
                     type_: (),
                     username: Some("username".into()),
@@ -269,6 +351,40 @@ mod tests {
             ).unwrap());
     }
 
+    #[test]
+    fn guild_channel_de() {
+        let chat: Chat = from_str(
+            r#"{"id":-1,"guild_id":7,"channel_type":"circle","type":"channel"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(chat.guild(), Some(7));
+        assert_eq!(chat.channel_kind, Some(ChannelKind::Circle));
+        assert!(chat.is_circle());
+        assert!(!chat.is_dm());
+    }
+
+    #[test]
+    fn zero_guild_id_de() {
+        let chat: Chat = from_str(
+            r#"{"id":0,"guild_id":0,"channel_type":"dm","type":"private"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(chat.guild(), None);
+        assert!(chat.is_dm());
+    }
+
+    #[test]
+    fn unknown_channel_kind_de() {
+        let chat: Chat = from_str(
+            r#"{"id":-1,"channel_type":"stage","type":"channel"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(chat.channel_kind, Some(ChannelKind::Unknown));
+    }
+
     #[test]
     fn private_chat_de_wrong_type_field() {
         assert!(from_str::<Chat>(r#"{"id":0,"type":"WRONG"}"#).is_err());
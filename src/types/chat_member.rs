@@ -200,6 +200,6 @@ mod tests {
             can_manage_emojis: None
         };
         let actual = serde_json::from_str::<ChatMember>(&json).unwrap();
-        assert_eq!(actual, expected)
+        assert_eq!(format!("{:?}", actual), format!("{:?}", expected))
     }
 }
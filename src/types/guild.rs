@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+/// This object represents a guild: a community, which owns text channels,
+/// circles and voice channels.
+#[serde_with_macros::skip_serializing_none]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct Guild {
+    /// A unique identifier for this guild.
+    pub id: i64,
+
+    /// A name of the guild.
+    pub name: String,
+
+    /// A URL of the guild's icon.
+    pub icon: Option<String>,
+
+    /// A description of the guild.
+    pub description: Option<String>,
+
+    /// An identifier of the user who owns the guild.
+    pub owner_id: Option<i64>,
+
+    /// A number of members in the guild.
+    pub member_count: Option<i32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize() {
+        let json = r#"{"id":7,"name":"Rustaceans","owner_id":42}"#;
+        let expected = Guild {
+            id: 7,
+            name: "Rustaceans".to_string(),
+            icon: None,
+            description: None,
+            owner_id: Some(42),
+            member_count: None,
+        };
+        let actual = serde_json::from_str(json).unwrap();
+        assert_eq!(expected, actual);
+    }
+}
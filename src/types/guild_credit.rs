@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-
+use thiserror::Error;
 
 /// This object represents guild credit of user
 #[serde_with_macros::skip_serializing_none]
@@ -10,6 +10,251 @@ pub struct GuildCredit {
     pub slots: Vec<Vec<CreditSlot>>,
 }
 
+impl GuildCredit {
+    /// The maximum number of slot rows in a guild credit.
+    pub const MAX_ROWS: usize = 3;
+
+    /// The maximum number of slots in a single row.
+    pub const MAX_SLOTS_PER_ROW: usize = 4;
+
+    /// The maximum length of a [`CreditAuthority::name`], in characters.
+    ///
+    /// [`CreditAuthority::name`]: crate::types::CreditAuthority::name
+    pub const MAX_AUTHORITY_NAME_LEN: usize = 32;
+
+    /// The maximum length of a [`SlotTitle::Label`], in characters.
+    ///
+    /// [`SlotTitle::Label`]: crate::types::SlotTitle::Label
+    pub const MAX_LABEL_LEN: usize = 16;
+
+    /// The maximum length of a [`CreditSlot::value`], in characters.
+    ///
+    /// [`CreditSlot::value`]: crate::types::CreditSlot::value
+    pub const MAX_VALUE_LEN: usize = 32;
+
+    /// Creates a [`GuildCreditBuilder`] with the specified `authority`.
+    ///
+    /// [`GuildCreditBuilder`]: crate::types::GuildCreditBuilder
+    pub fn builder(authority: CreditAuthority) -> GuildCreditBuilder {
+        GuildCreditBuilder::new(authority)
+    }
+
+    /// Checks that this guild credit satisfies the platform limits.
+    ///
+    /// The same checks are performed by [`GuildCreditBuilder::build`].
+    ///
+    /// [`GuildCreditBuilder::build`]:
+    /// crate::types::GuildCreditBuilder::build
+    pub fn validate(&self) -> Result<(), GuildCreditError> {
+        check_len(
+            "authority name",
+            &self.authority.name,
+            Self::MAX_AUTHORITY_NAME_LEN,
+        )?;
+        check_img_url(&self.authority.icon)?;
+
+        if let Some(CreditTitle { img: Some(img) }) = &self.title {
+            check_img_url(img)?;
+        }
+
+        if self.slots.len() > Self::MAX_ROWS {
+            return Err(GuildCreditError::TooManyRows(self.slots.len()));
+        }
+
+        for (row, slots) in self.slots.iter().enumerate() {
+            if slots.is_empty() {
+                return Err(GuildCreditError::EmptyRow { row });
+            }
+            if slots.len() > Self::MAX_SLOTS_PER_ROW {
+                return Err(GuildCreditError::TooManySlots {
+                    row,
+                    count: slots.len(),
+                });
+            }
+
+            for slot in slots {
+                match &slot.title {
+                    SlotTitle::Label(label) => {
+                        check_len("slot label", label, Self::MAX_LABEL_LEN)?
+                    }
+                    SlotTitle::Img(img) => check_img_url(img)?,
+                }
+                check_len("slot value", &slot.value, Self::MAX_VALUE_LEN)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A builder of [`GuildCredit`], which validates the platform limits before
+/// the request is sent.
+///
+/// # Examples
+/// ```
+/// use teloxide::types::{CreditAuthority, CreditSlot, GuildCredit};
+///
+/// let credit = GuildCredit::builder(CreditAuthority::new(
+///     "Arena".to_string(),
+///     "https://example.com/arena.png".to_string(),
+/// ))
+/// .append_row(vec![
+///     CreditSlot::label("Rank".to_string(), "Diamond".to_string()),
+///     CreditSlot::label("Wins".to_string(), "42".to_string()),
+/// ])
+/// .build()
+/// .unwrap();
+///
+/// assert_eq!(credit.slots.len(), 1);
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct GuildCreditBuilder {
+    authority: CreditAuthority,
+    title: Option<CreditTitle>,
+    slots: Vec<Vec<CreditSlot>>,
+}
+
+impl GuildCreditBuilder {
+    pub fn new(authority: CreditAuthority) -> Self {
+        Self { authority, title: None, slots: Vec::new() }
+    }
+
+    pub fn title<T>(mut self, val: T) -> Self
+    where
+        T: Into<Option<CreditTitle>>,
+    {
+        self.title = val.into();
+        self
+    }
+
+    /// Sets the title image.
+    pub fn title_img<T>(self, img: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.title(CreditTitle::img(img.into()))
+    }
+
+    pub fn append_row(mut self, slots: Vec<CreditSlot>) -> Self {
+        self.slots.push(slots);
+        self
+    }
+
+    /// Appends `slot` to the row with the specified `index` or to a new row if
+    /// there is no such row.
+    pub fn append_to_row(mut self, slot: CreditSlot, index: usize) -> Self {
+        match self.slots.get_mut(index) {
+            Some(slots) => slots.push(slot),
+            None => self.slots.push(vec![slot]),
+        };
+        self
+    }
+
+    /// Appends a slot with a text label to the last row.
+    pub fn label<L, V>(self, label: L, value: V) -> Self
+    where
+        L: Into<String>,
+        V: Into<String>,
+    {
+        self.append_to_last_row(CreditSlot::label(label.into(), value.into()))
+    }
+
+    /// Appends a slot with an image title to the last row.
+    pub fn img<I, V>(self, img: I, value: V) -> Self
+    where
+        I: Into<String>,
+        V: Into<String>,
+    {
+        self.append_to_last_row(CreditSlot::img(img.into(), value.into()))
+    }
+
+    /// Starts a new (empty) row, so the next slots will be appended to it.
+    pub fn next_row(mut self) -> Self {
+        self.slots.push(Vec::new());
+        self
+    }
+
+    /// Validates the collected data and builds [`GuildCredit`].
+    ///
+    /// [`GuildCredit`]: crate::types::GuildCredit
+    pub fn build(self) -> Result<GuildCredit, GuildCreditError> {
+        let credit = GuildCredit {
+            authority: self.authority,
+            title: self.title,
+            slots: self.slots,
+        };
+        credit.validate()?;
+        Ok(credit)
+    }
+
+    fn append_to_last_row(mut self, slot: CreditSlot) -> Self {
+        match self.slots.last_mut() {
+            Some(slots) => slots.push(slot),
+            None => self.slots.push(vec![slot]),
+        };
+        self
+    }
+}
+
+/// An error occurred while validating [`GuildCredit`].
+///
+/// [`GuildCredit`]: crate::types::GuildCredit
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum GuildCreditError {
+    #[error(
+        "Too many rows: {0}, the maximum is {max}",
+        max = GuildCredit::MAX_ROWS
+    )]
+    TooManyRows(usize),
+
+    #[error("Row #{row} is empty")]
+    EmptyRow { row: usize },
+
+    #[error(
+        "Too many slots in row #{row}: {count}, the maximum is {max}",
+        max = GuildCredit::MAX_SLOTS_PER_ROW
+    )]
+    TooManySlots { row: usize, count: usize },
+
+    #[error("The {field} is empty")]
+    EmptyString { field: &'static str },
+
+    #[error(
+        "The {field} is too long: {len} characters, the maximum is {max}"
+    )]
+    StringTooLong { field: &'static str, len: usize, max: usize },
+
+    #[error("Invalid image URL {0:?}, expected an absolute http(s) URL")]
+    InvalidImgUrl(String),
+}
+
+fn check_len(
+    field: &'static str,
+    s: &str,
+    max: usize,
+) -> Result<(), GuildCreditError> {
+    let len = s.chars().count();
+    if len == 0 {
+        return Err(GuildCreditError::EmptyString { field });
+    }
+    if len > max {
+        return Err(GuildCreditError::StringTooLong { field, len, max });
+    }
+    Ok(())
+}
+
+fn check_img_url(url: &str) -> Result<(), GuildCreditError> {
+    match reqwest::Url::parse(url) {
+        Ok(parsed)
+            if (parsed.scheme() == "http" || parsed.scheme() == "https")
+                && parsed.has_host() =>
+        {
+            Ok(())
+        }
+        _ => Err(GuildCreditError::InvalidImgUrl(url.to_owned())),
+    }
+}
+
 /// This object represents profile of a guild credit authority
 #[serde_with_macros::skip_serializing_none]
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
@@ -71,6 +316,113 @@ pub enum SlotTitle {
     Img(String),
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn authority() -> CreditAuthority {
+        CreditAuthority::new(
+            "Arena".to_string(),
+            "https://example.com/icon.png".to_string(),
+        )
+    }
+
+    #[test]
+    fn build() {
+        let credit = GuildCredit::builder(authority())
+            .title_img("https://example.com/title.png")
+            .label("Rank", "Diamond")
+            .img("https://example.com/win.png", "42")
+            .next_row()
+            .label("Level", "7")
+            .build()
+            .unwrap();
+
+        let expected = GuildCredit {
+            authority: authority(),
+            title: Some(CreditTitle::img(
+                "https://example.com/title.png".to_string(),
+            )),
+            slots: vec![
+                vec![
+                    CreditSlot::label("Rank".to_string(), "Diamond".to_string()),
+                    CreditSlot::img(
+                        "https://example.com/win.png".to_string(),
+                        "42".to_string(),
+                    ),
+                ],
+                vec![CreditSlot::label("Level".to_string(), "7".to_string())],
+            ],
+        };
+
+        assert_eq!(credit, expected);
+    }
+
+    #[test]
+    fn too_many_rows() {
+        let builder = (0..=GuildCredit::MAX_ROWS).fold(
+            GuildCredit::builder(authority()),
+            |builder, _| {
+                builder.append_row(vec![CreditSlot::label(
+                    "a".to_string(),
+                    "b".to_string(),
+                )])
+            },
+        );
+
+        assert_eq!(
+            builder.build(),
+            Err(GuildCreditError::TooManyRows(GuildCredit::MAX_ROWS + 1))
+        );
+    }
+
+    #[test]
+    fn too_many_slots() {
+        let row = vec![
+            CreditSlot::label("a".to_string(), "b".to_string());
+            GuildCredit::MAX_SLOTS_PER_ROW + 1
+        ];
 
+        assert_eq!(
+            GuildCredit::builder(authority()).append_row(row).build(),
+            Err(GuildCreditError::TooManySlots {
+                row: 0,
+                count: GuildCredit::MAX_SLOTS_PER_ROW + 1
+            })
+        );
+    }
+
+    #[test]
+    fn empty_row() {
+        assert_eq!(
+            GuildCredit::builder(authority()).next_row().build(),
+            Err(GuildCreditError::EmptyRow { row: 0 })
+        );
+    }
 
+    #[test]
+    fn label_too_long() {
+        let label = "й".repeat(GuildCredit::MAX_LABEL_LEN + 1);
 
+        assert_eq!(
+            GuildCredit::builder(authority()).label(label, "value").build(),
+            Err(GuildCreditError::StringTooLong {
+                field: "slot label",
+                len: GuildCredit::MAX_LABEL_LEN + 1,
+                max: GuildCredit::MAX_LABEL_LEN,
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_img_url() {
+        assert_eq!(
+            GuildCredit::builder(authority())
+                .img("/relative/path.png", "value")
+                .build(),
+            Err(GuildCreditError::InvalidImgUrl(
+                "/relative/path.png".to_string()
+            ))
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use crate::types::{CallbackGame, LoginUrl};
+use crate::{
+    types::{CallbackGame, LoginUrl},
+    utils::callback_data::{CallbackData, CallbackDataError},
+};
 use serde::{Deserialize, Serialize};
 
 /// This object represents one button of an inline keyboard.
@@ -114,6 +117,22 @@ impl InlineKeyboardButton {
         }
     }
 
+    /// Creates a callback button carrying typed `data`.
+    ///
+    /// Returns an error if encoded `data` doesn't fit into a button. See
+    /// [`CallbackData`] for details.
+    ///
+    /// [`CallbackData`]: crate::utils::callback_data::CallbackData
+    pub fn typed_callback<D>(
+        text: String,
+        data: &D,
+    ) -> Result<InlineKeyboardButton, CallbackDataError>
+    where
+        D: CallbackData,
+    {
+        Ok(Self::callback(text, data.encode()?))
+    }
+
     pub fn switch_inline_query(
         text: String,
         switch_inline_query: String,
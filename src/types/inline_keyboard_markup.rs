@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::types::InlineKeyboardButton;
+use crate::types::{InlineKeyboardButton, KeyboardButton, ReplyKeyboardMarkup};
 
 /// This object represents an [inline keyboard] that appears right next to the
 /// message it belongs to.
@@ -49,6 +49,28 @@ impl InlineKeyboardMarkup {
         };
         self
     }
+
+    /// Creates a reply keyboard with the same layout and texts of buttons.
+    ///
+    /// See [`ReplyKeyboardMarkup::to_inline_keyboard`] for the reverse
+    /// conversion.
+    ///
+    /// [`ReplyKeyboardMarkup::to_inline_keyboard`]:
+    /// crate::types::ReplyKeyboardMarkup::to_inline_keyboard
+    pub fn to_reply_keyboard(&self) -> ReplyKeyboardMarkup {
+        ReplyKeyboardMarkup {
+            keyboard: self
+                .inline_keyboard
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|button| KeyboardButton::new(button.text.clone()))
+                        .collect()
+                })
+                .collect(),
+            ..ReplyKeyboardMarkup::default()
+        }
+    }
 }
 
 #[cfg(test)]
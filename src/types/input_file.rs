@@ -1,6 +1,15 @@
+use bytes::Bytes;
 use serde::{Deserialize, Serialize};
+use tokio::io::AsyncRead;
 
-use std::{borrow::Cow, path::PathBuf};
+use std::{
+    borrow::Cow,
+    fmt,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
 
 /// This object represents the contents of a file to be uploaded.
 ///
@@ -8,9 +17,29 @@ use std::{borrow::Cow, path::PathBuf};
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Deserialize)]
 pub enum InputFile {
     File(PathBuf),
-    Memory { file_name: String, data: Cow<'static, [u8]> },
+    Memory {
+        file_name: String,
+        data: Cow<'static, [u8]>,
+    },
     Url(String),
     FileId(String),
+
+    /// Cheaply cloneable bytes of a file, e.g. generated by a bot.
+    #[serde(skip)]
+    Bytes {
+        file_name: String,
+        data: Bytes,
+    },
+
+    /// A file read from a stream while it is uploaded, so that it is not
+    /// buffered in memory.
+    ///
+    /// See [`InputStream`].
+    #[serde(skip)]
+    Stream {
+        file_name: String,
+        stream: InputStream,
+    },
 }
 
 impl InputFile {
@@ -29,6 +58,41 @@ impl InputFile {
         Self::Memory { file_name: file_name.into(), data: data.into() }
     }
 
+    pub fn bytes<S, D>(file_name: S, data: D) -> Self
+    where
+        S: Into<String>,
+        D: Into<Bytes>,
+    {
+        Self::Bytes { file_name: file_name.into(), data: data.into() }
+    }
+
+    /// A file read from `reader` while it is uploaded.
+    ///
+    /// If the length of the file is known, use [`InputFile::stream_with_len`]
+    /// instead.
+    pub fn stream<S, R>(file_name: S, reader: R) -> Self
+    where
+        S: Into<String>,
+        R: AsyncRead + Send + 'static,
+    {
+        Self::Stream {
+            file_name: file_name.into(),
+            stream: InputStream::new(reader, None),
+        }
+    }
+
+    /// A file of `len` bytes read from `reader` while it is uploaded.
+    pub fn stream_with_len<S, R>(file_name: S, reader: R, len: u64) -> Self
+    where
+        S: Into<String>,
+        R: AsyncRead + Send + 'static,
+    {
+        Self::Stream {
+            file_name: file_name.into(),
+            stream: InputStream::new(reader, Some(len)),
+        }
+    }
+
     pub fn url<T>(url: T) -> Self
     where
         T: Into<String>,
@@ -83,13 +147,10 @@ impl Serialize for InputFile {
             InputFile::File(path) => {
                 // NOTE: file should be actually attached with
                 // multipart/form-data
-                serializer.serialize_str(
-                    // TODO: remove unwrap (?)
-                    &format!(
-                        "attach://{}",
-                        path.file_name().unwrap().to_string_lossy()
-                    ),
-                )
+                serializer.serialize_str(&format!(
+                    "attach://{}",
+                    path.file_name().unwrap_or_default().to_string_lossy()
+                ))
             }
             InputFile::Memory { data, .. } => {
                 // NOTE: file should be actually attached with
@@ -101,6 +162,85 @@ impl Serialize for InputFile {
             }
             InputFile::Url(url) => serializer.serialize_str(url),
             InputFile::FileId(id) => serializer.serialize_str(id),
+            InputFile::Bytes { file_name, .. }
+            | InputFile::Stream { file_name, .. } => {
+                // NOTE: file should be actually attached with
+                // multipart/form-data
+                serializer.serialize_str(&format!("attach://{}", file_name))
+            }
         }
     }
 }
+
+type Reader = Pin<Box<dyn AsyncRead + Send>>;
+
+/// A reader of a file to be uploaded with [`InputFile::Stream`].
+///
+/// A stream can be uploaded only once: clones of it share the same reader, so
+/// sending a request with a stream that is already uploaded fails with
+/// [`RequestError::InvalidInput`].
+///
+/// [`RequestError::InvalidInput`]: crate::RequestError::InvalidInput
+#[derive(Clone)]
+pub struct InputStream {
+    reader: Arc<Mutex<Option<Reader>>>,
+    pub(crate) len: Option<u64>,
+}
+
+impl InputStream {
+    pub fn new<R>(reader: R, len: Option<u64>) -> Self
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        Self { reader: Arc::new(Mutex::new(Some(Box::pin(reader)))), len }
+    }
+
+    /// Takes the reader, returning `None` if it is already taken.
+    pub(crate) fn take(&self) -> Option<Reader> {
+        self.reader.lock().unwrap().take()
+    }
+}
+
+impl fmt::Debug for InputStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InputStream").field("len", &self.len).finish()
+    }
+}
+
+/// Streams are equal if they share the same reader.
+impl PartialEq for InputStream {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.reader, &other.reader)
+    }
+}
+
+impl Eq for InputStream {}
+
+impl Hash for InputStream {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (Arc::as_ptr(&self.reader) as *const u8).hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streams() {
+        let file = InputFile::stream_with_len("a.txt", &b"abc"[..], 3);
+        let stream = match &file {
+            InputFile::Stream { stream, .. } => stream.clone(),
+            _ => unreachable!(),
+        };
+
+        assert_eq!(file.clone(), file);
+        assert_eq!(stream.len, Some(3));
+        assert!(stream.take().is_some());
+        assert!(stream.take().is_none());
+        assert_eq!(
+            serde_json::to_string(&file).unwrap(),
+            r#""attach://a.txt""#
+        );
+    }
+}
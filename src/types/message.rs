@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::types::{chat::{ChatKind, PublicChatKind}, Animation, Audio, Chat, ChatPublic, Contact, Document, Game, InlineKeyboardMarkup, Invoice, Location,
+use crate::types::{chat::{guild_id_from_raw, ChatKind, PublicChatKind}, Animation, ChannelKind, Audio, Chat, ChatPublic, Contact, Document, Game, InlineKeyboardMarkup, Invoice, Location,
                    MessageEntity, PassportData, PhotoSize, Poll, PublicChatChannel, PublicChatSupergroup, Sticker, SuccessfulPayment, True, User, Venue,
                    Video, VideoNote, Voice, ChatPrivate, TopicShare, CircleShareEntity, RichText, CircleLike, CircleComment, CirclePost};
 
@@ -32,7 +32,8 @@ impl Message {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Text),
                 kind: ChatKind::Public(ChatPublic {
                     title: None,
                     kind: PublicChatKind::Channel(PublicChatChannel { username: None }),
@@ -57,7 +58,8 @@ impl Message {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Dm),
                 kind: ChatKind::Private(ChatPrivate {
                     type_: (),
                     username: None,
@@ -568,8 +570,8 @@ mod getters {
         MessageGroupChatCreated, MessageInvoice, MessageLeftChatMember,
         MessageMigrate, MessageNewChatMembers, MessageNewChatPhoto,
         MessageNewChatTitle, MessagePassportData, MessagePinned,
-        MessageSuccessfulPayment, MessageSupergroupChatCreated, PhotoSize,
-        True, User,
+        MessageSuccessfulPayment, MessageSupergroupChatCreated,
+        ParsedEntities, PhotoSize, True, User,
     };
 
     /// Getters for [Message] fields from [telegram docs].
@@ -863,6 +865,18 @@ mod getters {
             }
         }
 
+        /// Returns an iterator over entities of the text together with the
+        /// parts of the text they cover.
+        pub fn parse_entities(&self) -> Option<ParsedEntities<'_>> {
+            Some(ParsedEntities::new(self.text()?, self.entities()?))
+        }
+
+        /// Returns an iterator over entities of the caption together with the
+        /// parts of the caption they cover.
+        pub fn parse_caption_entities(&self) -> Option<ParsedEntities<'_>> {
+            Some(ParsedEntities::new(self.caption()?, self.caption_entities()?))
+        }
+
         pub fn contact(&self) -> Option<&types::Contact> {
             match &self.kind {
                 Common(MessageCommon {
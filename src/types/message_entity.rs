@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::{ops::Range, slice};
 
 use crate::types::{Message, User};
 
@@ -38,12 +39,106 @@ pub enum MessageEntityKind {
     TextMention { user: User },
     Underline,
     Strikethrough,
+
+    /// A mention of a guild role.
+    RoleMention { role_id: i64 },
+
+    /// A mention of a guild channel.
+    ChannelMention { channel_id: i64 },
 }
 
 impl MessageEntity {
+    pub fn new(kind: MessageEntityKind, offset: usize, length: usize) -> Self {
+        Self { kind, offset, length }
+    }
+
+    /// Returns the text of the entity in the text or the caption of
+    /// `message`.
+    ///
+    /// Returns `None` if the message has neither a text nor a caption or if
+    /// the entity doesn't fit into it.
     pub fn text_from(&self, message: &Message) -> Option<String> {
-        let text = message.text();
-        Some(String::from(&text?[self.offset..self.offset + self.length]))
+        let text = message.text().or_else(|| message.caption())?;
+        self.text_in(text).map(String::from)
+    }
+
+    /// Returns the part of `text` covered by the entity.
+    ///
+    /// Returns `None` if the entity is out of bounds of `text` or splits a
+    /// surrogate pair.
+    pub fn text_in<'a>(&self, text: &'a str) -> Option<&'a str> {
+        self.byte_range(text).map(|range| &text[range])
+    }
+
+    /// Converts the UTF-16 offset and length of the entity into a range of
+    /// bytes of `text`.
+    ///
+    /// Returns `None` if the entity is out of bounds of `text` or splits a
+    /// surrogate pair.
+    pub fn byte_range(&self, text: &str) -> Option<Range<usize>> {
+        let start = utf16_to_byte_offset(text, 0, self.offset)?;
+        let end = utf16_to_byte_offset(
+            &text[start..],
+            self.offset,
+            self.offset.checked_add(self.length)?,
+        )?;
+        Some(start..start + end)
+    }
+}
+
+/// Returns the byte offset in `text` of the `target` UTF-16 offset, given
+/// that `text` starts at the `start` UTF-16 offset.
+fn utf16_to_byte_offset(
+    text: &str,
+    start: usize,
+    target: usize,
+) -> Option<usize> {
+    let mut utf16 = start;
+    for (byte, c) in text.char_indices() {
+        if utf16 >= target {
+            return if utf16 == target { Some(byte) } else { None };
+        }
+        utf16 += c.len_utf16();
+    }
+
+    if utf16 == target {
+        Some(text.len())
+    } else {
+        None
+    }
+}
+
+/// An iterator over entities of a text together with the parts of the text
+/// they cover.
+///
+/// Entities that don't fit into the text are skipped.
+///
+/// Returned by [`Message::parse_entities`] and
+/// [`Message::parse_caption_entities`].
+///
+/// [`Message::parse_entities`]: crate::types::Message::parse_entities
+/// [`Message::parse_caption_entities`]:
+/// crate::types::Message::parse_caption_entities
+#[derive(Clone, Debug)]
+pub struct ParsedEntities<'a> {
+    text: &'a str,
+    entities: slice::Iter<'a, MessageEntity>,
+}
+
+impl<'a> ParsedEntities<'a> {
+    pub fn new(text: &'a str, entities: &'a [MessageEntity]) -> Self {
+        Self { text, entities: entities.iter() }
+    }
+}
+
+impl<'a> Iterator for ParsedEntities<'a> {
+    type Item = (&'a MessageEntity, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let text = self.text;
+        self.entities
+            .by_ref()
+            .find_map(|entity| entity.text_in(text).map(|s| (entity, s)))
     }
 }
 
@@ -100,12 +195,53 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn text_in_utf16() {
+        // "😀" is 2 UTF-16 code units, "ф" and "中" are 1.
+        let text = "😀ф 中文 🦀rust";
+        let entity = |offset, length| {
+            MessageEntity::new(MessageEntityKind::Bold, offset, length)
+        };
+
+        assert_eq!(entity(0, 2).text_in(text), Some("😀"));
+        assert_eq!(entity(2, 1).text_in(text), Some("ф"));
+        assert_eq!(entity(4, 2).text_in(text), Some("中文"));
+        assert_eq!(entity(7, 6).text_in(text), Some("🦀rust"));
+        assert_eq!(entity(13, 0).text_in(text), Some(""));
+
+        // Splits a surrogate pair.
+        assert_eq!(entity(1, 2).text_in(text), None);
+        assert_eq!(entity(7, 1).text_in(text), None);
+        // Out of bounds.
+        assert_eq!(entity(12, 2).text_in(text), None);
+        assert_eq!(entity(14, 0).text_in(text), None);
+        assert_eq!(entity(usize::MAX, 1).text_in(text), None);
+    }
+
+    #[test]
+    fn parsed_entities() {
+        let text = "🦀 crab 中文";
+        let entities = [
+            MessageEntity::new(MessageEntityKind::Bold, 3, 4),
+            MessageEntity::new(MessageEntityKind::Italic, 1, 1),
+            MessageEntity::new(MessageEntityKind::Code, 8, 2),
+        ];
+
+        let parsed: Vec<_> = ParsedEntities::new(text, &entities).collect();
+        assert_eq!(
+            parsed,
+            vec![(&entities[0], "crab"), (&entities[2], "中文")]
+        );
+    }
+
     fn message() -> Message {
         Message {
             id: 0,
             date: 0,
             chat: Chat {
                 id: 0,
+                guild_id: None,
+                channel_kind: None,
                 kind: ChatKind::Private(ChatPrivate {
                     type_: (),
                     username: None,
@@ -122,6 +258,7 @@ mod tests {
                     last_name: None,
                     username: None,
                     language_code: None,
+                    ..User::default()
                 }),
                 forward_kind: ForwardKind::Origin(ForwardOrigin {
                     reply_to_message: None,
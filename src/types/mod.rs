@@ -3,6 +3,7 @@
 pub use allowed_update::*;
 pub use animation::*;
 pub use audio::*;
+pub use bot_command::*;
 pub use callback_game::*;
 pub use callback_query::*;
 pub use chat::*;
@@ -92,6 +93,7 @@ pub use webhook_info::*;
 mod allowed_update;
 mod animation;
 mod audio;
+mod bot_command;
 mod callback_game;
 mod callback_query;
 mod chat;
@@ -201,6 +203,9 @@ pub use circle_comment::*;
 mod circle_post;
 pub use circle_post::*;
 
+mod guild;
+pub use guild::*;
+
 mod guild_credit;
 pub use guild_credit::*;
 
@@ -14,8 +14,8 @@ pub struct PassportElementError {
 }
 
 // TODO: use different types?
-#[serde(tag = "source")]
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "source")]
 pub enum PassportElementErrorKind {
     #[serde(rename = "data")]
     DataField(PassportElementErrorDataField),
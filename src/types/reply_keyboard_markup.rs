@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::types::KeyboardButton;
+use crate::types::{InlineKeyboardButton, InlineKeyboardMarkup, KeyboardButton};
 
 /// This object represents a [custom keyboard] with reply options (see
 /// [Introduction to bots] for details and examples).
@@ -85,4 +85,37 @@ impl ReplyKeyboardMarkup {
         self.selective = val.into();
         self
     }
+
+    /// Creates an inline keyboard with the same layout, converting each
+    /// button with `f`.
+    ///
+    /// # Examples
+    /// ```
+    /// use teloxide::types::{
+    ///     InlineKeyboardButton, KeyboardButton, ReplyKeyboardMarkup,
+    /// };
+    ///
+    /// let reply = ReplyKeyboardMarkup::default()
+    ///     .append_row(vec![KeyboardButton::new("Yes"), KeyboardButton::new("No")]);
+    /// let inline = reply.to_inline_keyboard(|button| {
+    ///     InlineKeyboardButton::callback(
+    ///         button.text.clone(),
+    ///         button.text.to_lowercase(),
+    ///     )
+    /// });
+    ///
+    /// assert_eq!(inline.to_reply_keyboard(), reply);
+    /// ```
+    pub fn to_inline_keyboard<F>(&self, mut f: F) -> InlineKeyboardMarkup
+    where
+        F: FnMut(&KeyboardButton) -> InlineKeyboardButton,
+    {
+        InlineKeyboardMarkup {
+            inline_keyboard: self
+                .keyboard
+                .iter()
+                .map(|row| row.iter().map(&mut f).collect())
+                .collect(),
+        }
+    }
 }
@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::types::{CallbackQuery, Chat, ChosenInlineResult, InlineQuery, Message, Poll, PollAnswer, PreCheckoutQuery, ShippingQuery, User, ChatKind,
+use crate::types::{chat::guild_id_from_raw, CallbackQuery, ChannelKind, Chat, ChosenInlineResult, InlineQuery, Message, Poll, PollAnswer, PreCheckoutQuery, ShippingQuery, User, ChatKind,
                    ChatPublic, PublicChatKind, PublicChatChannel, MessageKind, MessageCommon, ForwardKind, ForwardOrigin, MediaKind, MediaText,
                    MessageEntity, PublicChatGroup, MediaPhoto, PhotoSize, ChatPrivate, VideoNote, MediaVideoNote, Voice, MediaVoice, MessageNewChatMembers,
                    MessageChatMembersOnline, MessageChatMembersOffline, MessageLeftChatMember, MediaSticker, Sticker, MessagePinned, MessageReaction,
@@ -134,7 +134,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Text),
                 kind: ChatKind::Public(ChatPublic {
                     title: None,
                     kind: PublicChatKind::Channel(PublicChatChannel { username: None }),
@@ -178,7 +179,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Text),
                 kind: ChatKind::Public(ChatPublic {
                     title: None,
                     kind: PublicChatKind::Channel(PublicChatChannel { username: None }),
@@ -221,7 +223,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Text),
                 kind: ChatKind::Public(ChatPublic {
                     title: None,
                     kind: PublicChatKind::Channel(PublicChatChannel { username: None }),
@@ -261,7 +264,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Text),
                 kind: ChatKind::Public(ChatPublic {
                     title: None,
                     kind: PublicChatKind::Channel(PublicChatChannel { username: None }),
@@ -301,7 +305,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Text),
                 kind: ChatKind::Public(ChatPublic {
                     title: None,
                     kind: PublicChatKind::Channel(PublicChatChannel { username: None }),
@@ -341,7 +346,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Text),
                 kind: ChatKind::Public(ChatPublic {
                     title: None,
                     kind: PublicChatKind::Channel(PublicChatChannel { username: None }),
@@ -379,7 +385,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Circle),
                 kind: ChatKind::Public(ChatPublic {
                     title: None,
                     kind: PublicChatKind::Channel(PublicChatChannel { username: None }),
@@ -417,7 +424,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Circle),
                 kind: ChatKind::Public(ChatPublic {
                     title: None,
                     kind: PublicChatKind::Channel(PublicChatChannel { username: None }),
@@ -454,7 +462,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Circle),
                 kind: ChatKind::Public(ChatPublic {
                     title: None,
                     kind: PublicChatKind::Channel(PublicChatChannel { username: None }),
@@ -509,7 +518,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Text),
                 kind: ChatKind::Public(ChatPublic {
                     title: None,
                     kind: PublicChatKind::Channel(PublicChatChannel { username: None }),
@@ -555,7 +565,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Text),
                 kind: ChatKind::Public(ChatPublic {
                     title: None,
                     kind: PublicChatKind::Channel(PublicChatChannel { username: None }),
@@ -594,7 +605,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Text),
                 kind: ChatKind::Public(ChatPublic {
                     title: None,
                     kind: PublicChatKind::Channel(PublicChatChannel { username: None }),
@@ -615,7 +627,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Text),
                 kind: ChatKind::Public(ChatPublic {
                     title: None,
                     kind: PublicChatKind::Channel(PublicChatChannel { username: None }),
@@ -651,7 +664,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Text),
                 kind: ChatKind::Public(ChatPublic {
                     title: None,
                     kind: PublicChatKind::Channel(PublicChatChannel { username: None }),
@@ -682,7 +696,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Text),
                 kind: ChatKind::Public(ChatPublic {
                     title: None,
                     kind: PublicChatKind::Channel(PublicChatChannel { username: None }),
@@ -713,7 +728,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Text),
                 kind: ChatKind::Public(ChatPublic {
                     title: None,
                     kind: PublicChatKind::Channel(PublicChatChannel { username: None }),
@@ -744,7 +760,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Text),
                 kind: ChatKind::Public(ChatPublic {
                     title: None,
                     kind: PublicChatKind::Channel(PublicChatChannel { username: None }),
@@ -777,7 +794,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Dm),
                 kind: ChatKind::Private(ChatPrivate {
                     type_: (),
                     username: Some(username.to_string()),
@@ -820,7 +838,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Dm),
                 kind: ChatKind::Private(ChatPrivate {
                     type_: (),
                     username: Some(username.to_string()),
@@ -877,7 +896,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Dm),
                 kind: ChatKind::Private(ChatPrivate {
                     type_: (),
                     username: Some(username.to_string()),
@@ -922,7 +942,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Dm),
                 kind: ChatKind::Private(ChatPrivate {
                     type_: (),
                     username: Some(username.to_string()),
@@ -964,7 +985,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Dm),
                 kind: ChatKind::Private(ChatPrivate {
                     type_: (),
                     username: Some(username.to_string()),
@@ -1004,7 +1026,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Dm),
                 kind: ChatKind::Private(ChatPrivate {
                     type_: (),
                     username: Some(username.to_string()),
@@ -1041,7 +1064,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Dm),
                 kind: ChatKind::Private(ChatPrivate {
                     type_: (),
                     username: Some(username.to_string()),
@@ -1061,7 +1085,8 @@ impl UpdateKind {
             date,
             chat: Chat {
                 id: channel_id,
-                guild_id,
+                guild_id: guild_id_from_raw(guild_id),
+                channel_kind: Some(ChannelKind::Dm),
                 kind: ChatKind::Private(ChatPrivate {
                     type_: (),
                     username: Some(username.to_string()),
@@ -1132,7 +1157,8 @@ mod test {
                 date: 1_569_518_342,
                 chat: Chat {
                     id: 218_485_655,
-                    guild_id: 0,
+                    guild_id: None,
+                    channel_kind: None,
                     kind: ChatKind::Private(ChatPrivate {
                         type_: (),
                         username: Some(String::from("WaffleLapkin")),
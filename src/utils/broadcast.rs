@@ -0,0 +1,830 @@
+//! Sending the same message to many chats.
+//!
+//! [`Broadcast`] resolves a set of targets (chats, all members of a guild or
+//! members of a role) into recipients and sends them a [`BroadcastMessage`]
+//! with a concurrency limit and rate limiting. Errors are collected per
+//! recipient into a [`BroadcastReport`] instead of interrupting the broadcast.
+//!
+//! ## Examples
+//! ```no_run
+//! use teloxide::{
+//!     types::ParseMode,
+//!     utils::broadcast::{Broadcast, BroadcastMessage},
+//!     Bot,
+//! };
+//!
+//! # async fn run() -> Result<(), teloxide::RequestError> {
+//! let bot = Bot::from_env();
+//!
+//! let report = Broadcast::new(
+//!     bot,
+//!     BroadcastMessage::text("<b>Server maintenance</b> at 12:00 UTC")
+//!         .parse_mode(ParseMode::HTML),
+//! )
+//! .to_guild_members(42)
+//! .concurrency(4)
+//! .send_with_progress(|progress| {
+//!     log::info!("{}/{} messages sent", progress.done, progress.total)
+//! })
+//! .await?;
+//!
+//! log::info!(
+//!     "Delivered: {}, blocked: {}, failed: {}",
+//!     report.delivered.len(),
+//!     report.blocked.len(),
+//!     report.failed.len()
+//! );
+//! # Ok(()) }
+//! ```
+//!
+//! [`Broadcast`]: crate::utils::broadcast::Broadcast
+//! [`BroadcastMessage`]: crate::utils::broadcast::BroadcastMessage
+//! [`BroadcastReport`]: crate::utils::broadcast::BroadcastReport
+
+use crate::{
+    instrumentation,
+    requests::{Request, ResponseResult},
+    types::{ChatId, InputFile, Message, ParseMode, ReplyMarkup},
+    ApiErrorKind, Bot, RequestError,
+};
+use futures::StreamExt;
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+use tokio::time::{delay_for, delay_until, Instant};
+
+/// A content of a [`BroadcastMessage`].
+///
+/// [`BroadcastMessage`]: crate::utils::broadcast::BroadcastMessage
+#[derive(Debug, Clone)]
+pub enum BroadcastContent {
+    Text(String),
+    Photo(InputFile),
+    Animation(InputFile),
+    Audio(InputFile),
+    Document(InputFile),
+    Video(InputFile),
+    Voice(InputFile),
+    Sticker(InputFile),
+}
+
+/// A message template, which is sent to every recipient of a [`Broadcast`].
+///
+/// [`Broadcast`]: crate::utils::broadcast::Broadcast
+#[derive(Debug, Clone)]
+pub struct BroadcastMessage {
+    pub content: BroadcastContent,
+
+    /// A caption of a media message. Ignored for text messages and stickers.
+    pub caption: Option<String>,
+    pub parse_mode: Option<ParseMode>,
+    pub reply_markup: Option<ReplyMarkup>,
+    pub disable_notification: Option<bool>,
+}
+
+impl BroadcastMessage {
+    pub fn new(content: BroadcastContent) -> Self {
+        Self {
+            content,
+            caption: None,
+            parse_mode: None,
+            reply_markup: None,
+            disable_notification: None,
+        }
+    }
+
+    pub fn text<T>(text: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::new(BroadcastContent::Text(text.into()))
+    }
+
+    pub fn photo(photo: InputFile) -> Self {
+        Self::new(BroadcastContent::Photo(photo))
+    }
+
+    pub fn animation(animation: InputFile) -> Self {
+        Self::new(BroadcastContent::Animation(animation))
+    }
+
+    pub fn audio(audio: InputFile) -> Self {
+        Self::new(BroadcastContent::Audio(audio))
+    }
+
+    pub fn document(document: InputFile) -> Self {
+        Self::new(BroadcastContent::Document(document))
+    }
+
+    pub fn video(video: InputFile) -> Self {
+        Self::new(BroadcastContent::Video(video))
+    }
+
+    pub fn voice(voice: InputFile) -> Self {
+        Self::new(BroadcastContent::Voice(voice))
+    }
+
+    pub fn sticker(sticker: InputFile) -> Self {
+        Self::new(BroadcastContent::Sticker(sticker))
+    }
+
+    pub fn caption<T>(mut self, val: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.caption = Some(val.into());
+        self
+    }
+
+    pub fn parse_mode(mut self, val: ParseMode) -> Self {
+        self.parse_mode = Some(val);
+        self
+    }
+
+    pub fn reply_markup<T>(mut self, val: T) -> Self
+    where
+        T: Into<ReplyMarkup>,
+    {
+        self.reply_markup = Some(val.into());
+        self
+    }
+
+    pub fn disable_notification(mut self, val: bool) -> Self {
+        self.disable_notification = Some(val);
+        self
+    }
+
+    async fn send_to(
+        &self,
+        bot: &Arc<Bot>,
+        chat_id: ChatId,
+    ) -> ResponseResult<Message> {
+        macro_rules! apply {
+            ($req:ident, caption) => {
+                if let Some(caption) = &self.caption {
+                    $req = $req.caption(caption.clone());
+                }
+            };
+            ($req:ident, parse_mode) => {
+                if let Some(parse_mode) = self.parse_mode {
+                    $req = $req.parse_mode(parse_mode);
+                }
+            };
+            ($req:ident, reply_markup) => {
+                if let Some(reply_markup) = &self.reply_markup {
+                    $req = $req.reply_markup(reply_markup.clone());
+                }
+            };
+            ($req:ident, disable_notification) => {
+                if let Some(val) = self.disable_notification {
+                    $req = $req.disable_notification(val);
+                }
+            };
+        }
+
+        macro_rules! send {
+            ($req:expr, $($field:ident),*) => {{
+                let mut req = $req;
+                $(apply!(req, $field);)*
+                req.send().await
+            }};
+        }
+
+        macro_rules! send_media {
+            ($method:ident, $file:expr) => {
+                send!(
+                    bot.$method(chat_id, $file.clone()),
+                    caption,
+                    parse_mode,
+                    reply_markup,
+                    disable_notification
+                )
+            };
+        }
+
+        match &self.content {
+            BroadcastContent::Text(text) => send!(
+                bot.send_message(chat_id, text.clone()),
+                parse_mode,
+                reply_markup,
+                disable_notification
+            ),
+            BroadcastContent::Photo(file) => send_media!(send_photo, file),
+            BroadcastContent::Animation(file) => {
+                send_media!(send_animation, file)
+            }
+            BroadcastContent::Audio(file) => send_media!(send_audio, file),
+            BroadcastContent::Document(file) => {
+                send_media!(send_document, file)
+            }
+            BroadcastContent::Video(file) => send_media!(send_video, file),
+            BroadcastContent::Voice(file) => send_media!(send_voice, file),
+            BroadcastContent::Sticker(file) => send!(
+                bot.send_sticker(chat_id, file.clone()),
+                reply_markup,
+                disable_notification
+            ),
+        }
+    }
+}
+
+impl From<String> for BroadcastMessage {
+    fn from(text: String) -> Self {
+        Self::text(text)
+    }
+}
+
+impl From<&str> for BroadcastMessage {
+    fn from(text: &str) -> Self {
+        Self::text(text)
+    }
+}
+
+/// A set of chats to send a [`BroadcastMessage`] to.
+///
+/// [`BroadcastMessage`]: crate::utils::broadcast::BroadcastMessage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BroadcastTarget {
+    /// The specified chats.
+    Chats(Vec<ChatId>),
+
+    /// Private chats with all members of the guild, except bots.
+    GuildMembers { guild_id: i64 },
+
+    /// Private chats with all members of the guild role, except bots.
+    RoleMembers { guild_id: i64, role_id: i64 },
+}
+
+/// A single recipient of a [`Broadcast`].
+///
+/// [`Broadcast`]: crate::utils::broadcast::Broadcast
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Recipient {
+    /// A chat, which was specified directly.
+    Chat(ChatId),
+
+    /// A user, who receives the message in a private chat (see
+    /// [`Bot::get_private_chat`]).
+    ///
+    /// [`Bot::get_private_chat`]: crate::Bot::get_private_chat
+    User(i64),
+}
+
+/// A progress of a running [`Broadcast`].
+///
+/// [`Broadcast`]: crate::utils::broadcast::Broadcast
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BroadcastProgress {
+    /// A number of all recipients.
+    pub total: usize,
+
+    /// A number of recipients already handled, successfully or not.
+    pub done: usize,
+
+    /// A number of recipients the message was delivered to.
+    pub delivered: usize,
+}
+
+/// A result of a finished [`Broadcast`].
+///
+/// [`Broadcast`]: crate::utils::broadcast::Broadcast
+#[derive(Debug, Default)]
+pub struct BroadcastReport {
+    /// A number of all recipients.
+    pub total: usize,
+
+    /// Recipients the message was delivered to with the sent messages.
+    pub delivered: Vec<(Recipient, Message)>,
+
+    /// Recipients who have blocked the bot ([`ApiErrorKind::BotBlocked`]).
+    ///
+    /// [`ApiErrorKind::BotBlocked`]: crate::ApiErrorKind::BotBlocked
+    pub blocked: Vec<Recipient>,
+
+    /// Recipients with deactivated accounts
+    /// ([`ApiErrorKind::UserDeactivated`]).
+    ///
+    /// [`ApiErrorKind::UserDeactivated`]: crate::ApiErrorKind::UserDeactivated
+    pub deactivated: Vec<Recipient>,
+
+    /// Recipients the message wasn't delivered to because of other errors.
+    pub failed: Vec<(Recipient, RequestError)>,
+}
+
+/// Sends the same message to many chats.
+///
+/// See [the module-level documentation](crate::utils::broadcast).
+pub struct Broadcast {
+    bot: Arc<Bot>,
+    message: BroadcastMessage,
+    targets: Vec<BroadcastTarget>,
+    concurrency: usize,
+    rate_limit: u32,
+    page_size: u32,
+    max_retries: u32,
+}
+
+impl Broadcast {
+    /// Creates a broadcast of `message` without targets.
+    ///
+    /// By default, 8 messages are sent concurrently, but not more than 30
+    /// messages per second.
+    pub fn new<M>(bot: Arc<Bot>, message: M) -> Self
+    where
+        M: Into<BroadcastMessage>,
+    {
+        Self {
+            bot,
+            message: message.into(),
+            targets: Vec::new(),
+            concurrency: 8,
+            rate_limit: 30,
+            page_size: 100,
+            max_retries: 3,
+        }
+    }
+
+    pub fn target(mut self, target: BroadcastTarget) -> Self {
+        self.targets.push(target);
+        self
+    }
+
+    pub fn to_chats<I, C>(self, chats: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<ChatId>,
+    {
+        self.target(BroadcastTarget::Chats(
+            chats.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    pub fn to_guild_members(self, guild_id: i64) -> Self {
+        self.target(BroadcastTarget::GuildMembers { guild_id })
+    }
+
+    pub fn to_role_members(self, guild_id: i64, role_id: i64) -> Self {
+        self.target(BroadcastTarget::RoleMembers { guild_id, role_id })
+    }
+
+    /// A maximum number of messages being sent at the same time.
+    ///
+    /// [`send`] fails with [`RequestError::InvalidInput`] if it's zero.
+    ///
+    /// [`send`]: crate::utils::broadcast::Broadcast::send
+    /// [`RequestError::InvalidInput`]: crate::RequestError::InvalidInput
+    pub fn concurrency(mut self, val: usize) -> Self {
+        self.concurrency = val;
+        self
+    }
+
+    /// A maximum number of messages sent per second.
+    ///
+    /// [`send`] fails with [`RequestError::InvalidInput`] if it's zero.
+    ///
+    /// [`send`]: crate::utils::broadcast::Broadcast::send
+    /// [`RequestError::InvalidInput`]: crate::RequestError::InvalidInput
+    pub fn rate_limit(mut self, val: u32) -> Self {
+        self.rate_limit = val;
+        self
+    }
+
+    /// A number of members requested at once by [`Bot::get_guild_members`].
+    ///
+    /// [`send`] fails with [`RequestError::InvalidInput`] if it's zero or
+    /// greater than `i32::MAX`.
+    ///
+    /// [`Bot::get_guild_members`]: crate::Bot::get_guild_members
+    /// [`send`]: crate::utils::broadcast::Broadcast::send
+    /// [`RequestError::InvalidInput`]: crate::RequestError::InvalidInput
+    pub fn page_size(mut self, val: u32) -> Self {
+        self.page_size = val;
+        self
+    }
+
+    /// How many times a message is resent after
+    /// [`RequestError::RetryAfter`].
+    ///
+    /// [`RequestError::RetryAfter`]: crate::RequestError::RetryAfter
+    pub fn max_retries(mut self, val: u32) -> Self {
+        self.max_retries = val;
+        self
+    }
+
+    /// Sends the message to all targets.
+    ///
+    /// Returns an error only if the settings are invalid or the targets
+    /// cannot be resolved into recipients; errors of individual sends are
+    /// collected into [`BroadcastReport`].
+    ///
+    /// [`BroadcastReport`]: crate::utils::broadcast::BroadcastReport
+    pub async fn send(self) -> ResponseResult<BroadcastReport> {
+        self.send_with_progress(|_| {}).await
+    }
+
+    /// Sends the message to all targets, calling `on_progress` after each
+    /// recipient has been handled.
+    pub async fn send_with_progress<F>(
+        self,
+        on_progress: F,
+    ) -> ResponseResult<BroadcastReport>
+    where
+        F: Fn(BroadcastProgress) + Send + Sync,
+    {
+        self.validate()?;
+        let recipients = self.resolve_recipients().await?;
+        let total = recipients.len();
+
+        let report =
+            Mutex::new(BroadcastReport { total, ..Default::default() });
+        let done = AtomicUsize::new(0);
+        let delivered = AtomicUsize::new(0);
+        let limiter =
+            RateLimiter::new(Duration::from_secs(1) / self.rate_limit);
+
+        futures::stream::iter(recipients)
+            .for_each_concurrent(self.concurrency, |recipient| {
+                let (this, report, done, delivered, limiter, on_progress) =
+                    (&self, &report, &done, &delivered, &limiter, &on_progress);
+
+                async move {
+                    let result = this.deliver(&recipient, limiter).await;
+
+                    {
+                        let mut report = report.lock().unwrap();
+                        match result {
+                            Ok(message) => {
+                                delivered.fetch_add(1, Ordering::SeqCst);
+                                report.delivered.push((recipient, message));
+                            }
+                            Err(RequestError::ApiError {
+                                kind: ApiErrorKind::BotBlocked,
+                                ..
+                            }) => report.blocked.push(recipient),
+                            Err(RequestError::ApiError {
+                                kind: ApiErrorKind::UserDeactivated,
+                                ..
+                            }) => report.deactivated.push(recipient),
+                            Err(error) => {
+                                log::warn!(
+                                    "Cannot broadcast to {:?}: {}",
+                                    recipient,
+                                    error
+                                );
+                                report.failed.push((recipient, error));
+                            }
+                        }
+                    }
+
+                    on_progress(BroadcastProgress {
+                        total,
+                        done: done.fetch_add(1, Ordering::SeqCst) + 1,
+                        delivered: delivered.load(Ordering::SeqCst),
+                    });
+                }
+            })
+            .await;
+
+        Ok(report.into_inner().unwrap())
+    }
+
+    fn validate(&self) -> ResponseResult<()> {
+        let error = if self.concurrency == 0 {
+            "`concurrency` must be positive"
+        } else if self.rate_limit == 0 {
+            "`rate_limit` must be positive"
+        } else if self.page_size == 0 || self.page_size > i32::MAX as u32 {
+            "`page_size` must be in 1..=i32::MAX"
+        } else {
+            return Ok(());
+        };
+
+        Err(RequestError::InvalidInput(error.to_owned()))
+    }
+
+    async fn resolve_recipients(&self) -> ResponseResult<Vec<Recipient>> {
+        let mut recipients = Vec::new();
+        let mut seen = HashSet::new();
+        let mut push = |recipient: Recipient| {
+            if seen.insert(recipient.clone()) {
+                recipients.push(recipient);
+            }
+        };
+
+        for target in &self.targets {
+            match target {
+                BroadcastTarget::Chats(chats) => chats
+                    .iter()
+                    .cloned()
+                    .map(Recipient::Chat)
+                    .for_each(&mut push),
+                BroadcastTarget::GuildMembers { guild_id } => {
+                    let mut after = None;
+                    loop {
+                        let page = self
+                            .bot
+                            .get_guild_members(
+                                *guild_id,
+                                after,
+                                Some(self.page_size as i32),
+                            )
+                            .send()
+                            .await?;
+
+                        after = page.last().map(|member| member.user.id);
+                        let is_last = page.len() < self.page_size as usize;
+
+                        page.into_iter()
+                            .filter(|member| !member.user.is_bot)
+                            .map(|member| Recipient::User(member.user.id))
+                            .for_each(&mut push);
+
+                        if is_last || after.is_none() {
+                            break;
+                        }
+                    }
+                }
+                BroadcastTarget::RoleMembers { guild_id, role_id } => self
+                    .bot
+                    .get_role_members(*guild_id, *role_id)
+                    .send()
+                    .await?
+                    .into_iter()
+                    .filter(|member| !member.user.is_bot)
+                    .map(|member| Recipient::User(member.user.id))
+                    .for_each(&mut push),
+            }
+        }
+
+        Ok(recipients)
+    }
+
+    async fn deliver(
+        &self,
+        recipient: &Recipient,
+        limiter: &RateLimiter,
+    ) -> ResponseResult<Message> {
+        let chat_id = match recipient {
+            Recipient::Chat(chat_id) => chat_id.clone(),
+            Recipient::User(user_id) => {
+                let chat = self
+                    .retrying(limiter, || async {
+                        self.bot.get_private_chat(*user_id).send().await
+                    })
+                    .await?;
+                ChatId::Id(chat.id)
+            }
+        };
+
+        self.retrying(limiter, || {
+            self.message.send_to(&self.bot, chat_id.clone())
+        })
+        .await
+    }
+
+    /// Calls `f`, respecting the rate limit and retrying after
+    /// [`RequestError::RetryAfter`].
+    async fn retrying<T, F, Fut>(
+        &self,
+        limiter: &RateLimiter,
+        f: F,
+    ) -> ResponseResult<T>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = ResponseResult<T>>,
+    {
+        let mut retries = 0;
+        loop {
+            limiter.wait().await;
+            match f().await {
+                Err(RequestError::RetryAfter(secs))
+                    if retries < self.max_retries =>
+                {
+                    retries += 1;
+                    instrumentation::retry();
+                    delay_for(Duration::from_secs(secs.max(0) as u64)).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Spreads requests evenly so that two of them are at least `interval` apart.
+struct RateLimiter {
+    interval: Duration,
+    next: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    fn new(interval: Duration) -> Self {
+        Self { interval, next: Mutex::new(Instant::now()) }
+    }
+
+    async fn wait(&self) {
+        let at = {
+            let mut next = self.next.lock().unwrap();
+            let at = std::cmp::max(*next, Instant::now());
+            *next = at + self.interval;
+            at
+        };
+        delay_until(at).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::Replay;
+    use serde_json::{json, Value};
+    use tokio::time;
+
+    /// A recorded request, answered with `response`.
+    fn request(method_name: &str, params: Value, response: Value) -> String {
+        json!({
+            "kind": "request",
+            "method_name": method_name,
+            "params": params,
+            "response": response.to_string(),
+        })
+        .to_string()
+    }
+
+    fn sent(chat_id: i64) -> String {
+        request(
+            "sendMessage",
+            json!({ "chat_id": chat_id, "text": "hi" }),
+            json!({
+                "ok": true,
+                "result": {
+                    "message_id": 1,
+                    "chat": { "id": chat_id, "type": "private" },
+                    "date": 0,
+                    "text": "hi",
+                },
+            }),
+        )
+    }
+
+    fn not_sent(chat_id: i64, error_code: u16, description: &str) -> String {
+        request(
+            "sendMessage",
+            json!({ "chat_id": chat_id, "text": "hi" }),
+            json!({
+                "ok": false,
+                "error_code": error_code,
+                "description": description,
+            }),
+        )
+    }
+
+    fn retry_after(chat_id: i64, secs: i32) -> String {
+        request(
+            "sendMessage",
+            json!({ "chat_id": chat_id, "text": "hi" }),
+            json!({
+                "ok": false,
+                "error_code": 429,
+                "description": "Too Many Requests",
+                "response_parameters": { "retry_after": secs },
+            }),
+        )
+    }
+
+    fn member(id: i64, is_bot: bool) -> Value {
+        json!({
+            "user": { "id": id, "is_bot": is_bot, "first_name": "Ferris" },
+            "status": "member",
+        })
+    }
+
+    fn private_chat(user_id: i64, chat_id: i64) -> String {
+        request(
+            "getPrivateChat",
+            json!({ "user_id": user_id }),
+            json!({
+                "ok": true,
+                "result": { "id": chat_id, "type": "private" },
+            }),
+        )
+    }
+
+    /// A broadcast of "hi" answered by a recording of `requests`, sending
+    /// messages one by one.
+    fn broadcast_of(requests: &[String]) -> (Broadcast, Arc<Replay>) {
+        let requests = requests.join("\n");
+        let replay = Replay::from_reader(requests.as_bytes()).unwrap();
+        let bot = Bot::new("").with_transport(Arc::clone(&replay));
+        (Broadcast::new(bot, "hi").concurrency(1), replay)
+    }
+
+    #[tokio::test]
+    async fn errors_are_collected() {
+        let (broadcast, replay) = broadcast_of(&[
+            sent(1),
+            not_sent(2, 403, "Forbidden: bot was blocked by the user"),
+            not_sent(3, 401, "Unauthorized: user is deactivated"),
+            request("sendMessage", Value::Null, Value::Null),
+        ]);
+
+        let progress = Mutex::new(Vec::new());
+        let report = broadcast
+            .to_chats(vec![1, 2, 3, 4])
+            .send_with_progress(|p| progress.lock().unwrap().push(p))
+            .await
+            .unwrap();
+        replay.assert_finished();
+
+        assert_eq!(report.total, 4);
+        let delivered: Vec<_> =
+            report.delivered.iter().map(|(r, _)| r.clone()).collect();
+        assert_eq!(delivered, vec![Recipient::Chat(ChatId::Id(1))]);
+        assert_eq!(report.blocked, vec![Recipient::Chat(ChatId::Id(2))]);
+        assert_eq!(report.deactivated, vec![Recipient::Chat(ChatId::Id(3))]);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, Recipient::Chat(ChatId::Id(4)));
+
+        let progress = progress.into_inner().unwrap();
+        assert_eq!(
+            progress,
+            (1..=4)
+                .map(|done| BroadcastProgress { total: 4, done, delivered: 1 })
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn guild_members_are_paged() {
+        let (broadcast, replay) = broadcast_of(&[
+            request(
+                "getGuildMembers",
+                json!({ "guild_id": 7, "limit": 2 }),
+                json!({
+                    "ok": true,
+                    "result": [member(10, false), member(11, true)],
+                }),
+            ),
+            request(
+                "getGuildMembers",
+                json!({ "guild_id": 7, "after": 11, "limit": 2 }),
+                json!({ "ok": true, "result": [member(12, false)] }),
+            ),
+            private_chat(10, 100),
+            sent(100),
+            private_chat(12, 120),
+            sent(120),
+        ]);
+
+        let report =
+            broadcast.to_guild_members(7).page_size(2).send().await.unwrap();
+        replay.assert_finished();
+
+        let delivered: Vec<_> =
+            report.delivered.into_iter().map(|(r, _)| r).collect();
+        assert_eq!(delivered, vec![Recipient::User(10), Recipient::User(12)]);
+    }
+
+    #[tokio::test]
+    async fn retry_after_is_respected() {
+        time::pause();
+
+        let (broadcast, replay) = broadcast_of(&[retry_after(1, 5), sent(1)]);
+        let start = Instant::now();
+        let report = broadcast.to_chats(vec![1]).send().await.unwrap();
+        replay.assert_finished();
+        assert_eq!(report.delivered.len(), 1);
+        assert!(start.elapsed() >= Duration::from_secs(5));
+
+        let (broadcast, replay) =
+            broadcast_of(&[retry_after(1, 5), retry_after(1, 5)]);
+        let report =
+            broadcast.to_chats(vec![1]).max_retries(1).send().await.unwrap();
+        replay.assert_finished();
+        match report.failed.as_slice() {
+            [(_, RequestError::RetryAfter(5))] => {}
+            failed => panic!("unexpected failures: {:?}", failed),
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_settings() {
+        let invalid = |configure: fn(Broadcast) -> Broadcast| async move {
+            let (broadcast, replay) = broadcast_of(&[]);
+            match configure(broadcast).to_chats(vec![1]).send().await {
+                Err(RequestError::InvalidInput(_)) => {}
+                other => panic!("unexpected result: {:?}", other),
+            }
+            replay.assert_finished();
+        };
+
+        invalid(|broadcast| broadcast.concurrency(0)).await;
+        invalid(|broadcast| broadcast.rate_limit(0)).await;
+        invalid(|broadcast| broadcast.page_size(0)).await;
+        invalid(|broadcast| broadcast.page_size(u32::MAX)).await;
+    }
+}
@@ -0,0 +1,373 @@
+//! Typed callback data of inline keyboard buttons.
+//!
+//! Instead of inventing an encoding for every keyboard and matching raw
+//! strings of [`CallbackQuery::data`], implement [`CallbackData`] for a
+//! serializable type, create buttons with
+//! [`InlineKeyboardButton::typed_callback`] and receive decoded values with
+//! [`DispatcherHandlerRxExt::callback_data`].
+//!
+//! The platform limits callback data to [`MAX_CALLBACK_DATA_LEN`] bytes.
+//! Values which don't fit can be kept on the bot's side in a
+//! [`CallbackStorage`], so that a button carries only a short key.
+//!
+//! ## Examples
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use teloxide::{
+//!     types::InlineKeyboardButton, utils::callback_data::CallbackData,
+//! };
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! enum RoleAction {
+//!     Grant { role_id: i64 },
+//!     Revoke { role_id: i64 },
+//! }
+//!
+//! impl CallbackData for RoleAction {
+//!     const PREFIX: &'static str = "role";
+//! }
+//!
+//! let action = RoleAction::Grant { role_id: 42 };
+//! let data = action.encode().unwrap();
+//! assert_eq!(data, r#"role:{"Grant":{"role_id":42}}"#);
+//! assert_eq!(RoleAction::decode(&data), Some(action));
+//!
+//! let button = InlineKeyboardButton::typed_callback(
+//!     "Grant".to_string(),
+//!     &RoleAction::Grant { role_id: 42 },
+//! )
+//! .unwrap();
+//! ```
+//!
+//! Handling the buttons:
+//! ```no_run
+//! # use serde::{Deserialize, Serialize};
+//! # use teloxide::utils::callback_data::CallbackData;
+//! # #[derive(Serialize, Deserialize)]
+//! # enum RoleAction { Grant { role_id: i64 }, Revoke { role_id: i64 } }
+//! # impl CallbackData for RoleAction { const PREFIX: &'static str = "role"; }
+//! use std::sync::Arc;
+//! use teloxide::{prelude::*, types::CallbackQuery};
+//!
+//! # async fn run(bot: Arc<Bot>) {
+//! Dispatcher::new(bot)
+//!     .callback_queries_handler(|rx: DispatcherHandlerRx<CallbackQuery>| {
+//!         rx.callback_data::<RoleAction>().for_each(|(cx, action)| {
+//!             async move {
+//!                 match action {
+//!                     RoleAction::Grant { role_id } => { /* ... */ }
+//!                     RoleAction::Revoke { role_id } => { /* ... */ }
+//!                 }
+//!             }
+//!         })
+//!     })
+//!     .dispatch()
+//!     .await;
+//! # }
+//! ```
+//!
+//! [`CallbackQuery::data`]: crate::types::CallbackQuery::data
+//! [`InlineKeyboardButton::typed_callback`]:
+//! crate::types::InlineKeyboardButton::typed_callback
+//! [`DispatcherHandlerRxExt::callback_data`]:
+//! crate::dispatching::DispatcherHandlerRxExt::callback_data
+
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+use thiserror::Error;
+
+/// The maximum length of callback data in bytes.
+pub const MAX_CALLBACK_DATA_LEN: usize = 64;
+
+/// A type which can be sent as callback data of an inline keyboard button.
+///
+/// Values are encoded as `PREFIX:JSON`, or as `PREFIX#KEY` if they are kept
+/// in a [`CallbackStorage`].
+///
+/// Be aware that a bad client can send arbitrary data, so decoding can fail
+/// or produce a value the bot has never sent.
+pub trait CallbackData: Serialize + DeserializeOwned {
+    /// A prefix distinguishing callback data of this type from callback data
+    /// of other types.
+    ///
+    /// Must not contain `:` and `#`.
+    const PREFIX: &'static str;
+
+    /// Encodes the value.
+    ///
+    /// Returns [`CallbackDataError::TooLong`] if the result doesn't fit into
+    /// [`MAX_CALLBACK_DATA_LEN`] bytes.
+    fn encode(&self) -> Result<String, CallbackDataError> {
+        let payload = serde_json::to_string(self)?;
+        check_len(format!("{}:{}", Self::PREFIX, payload))
+    }
+
+    /// Encodes the value, putting it into `storage` if it's too long.
+    ///
+    /// Returns [`CallbackDataError::TooLong`] if even a key of `storage`
+    /// doesn't fit into [`MAX_CALLBACK_DATA_LEN`] bytes together with the
+    /// prefix. The value isn't kept in `storage` then.
+    fn encode_with<S>(&self, storage: &S) -> Result<String, CallbackDataError>
+    where
+        S: CallbackStorage + ?Sized,
+    {
+        let payload = serde_json::to_string(self)?;
+        let data = format!("{}:{}", Self::PREFIX, payload);
+        if data.len() <= MAX_CALLBACK_DATA_LEN {
+            return Ok(data);
+        }
+
+        let key = storage.store(payload);
+        check_len(format!("{}#{}", Self::PREFIX, key)).map_err(|err| {
+            storage.remove(&key);
+            err
+        })
+    }
+
+    /// Decodes a value encoded with [`CallbackData::encode`].
+    ///
+    /// Returns `None` if `data` has another prefix or is malformed.
+    fn decode(data: &str) -> Option<Self> {
+        let payload = strip_prefix(data, Self::PREFIX, ':')?;
+        serde_json::from_str(payload).ok()
+    }
+
+    /// Decodes a value encoded with [`CallbackData::encode_with`].
+    ///
+    /// Returns `None` if `data` has another prefix, is malformed or its value
+    /// isn't in `storage` anymore.
+    fn decode_with<S>(data: &str, storage: &S) -> Option<Self>
+    where
+        S: CallbackStorage + ?Sized,
+    {
+        match strip_prefix(data, Self::PREFIX, '#') {
+            Some(key) => serde_json::from_str(&storage.load(key)?).ok(),
+            None => Self::decode(data),
+        }
+    }
+}
+
+/// An error occurred while encoding [`CallbackData`].
+#[derive(Debug, Error)]
+pub enum CallbackDataError {
+    #[error(
+        "Callback data is {len} bytes long, the maximum is {max}",
+        max = MAX_CALLBACK_DATA_LEN
+    )]
+    TooLong { len: usize },
+
+    #[error("Failed to serialize callback data: {0}")]
+    Serialize(#[from] serde_json::Error),
+}
+
+/// A storage of callback data which doesn't fit into a button.
+///
+/// For a storage based on a simple hash map, see [`InMemCallbackStorage`].
+pub trait CallbackStorage: Send + Sync {
+    /// Stores `payload` and returns a key to load it with.
+    ///
+    /// The key must be short enough to fit into a button together with a
+    /// prefix.
+    fn store(&self, payload: String) -> String;
+
+    /// Loads a payload stored with `key`.
+    fn load(&self, key: &str) -> Option<String>;
+
+    /// Removes a payload stored with `key`.
+    fn remove(&self, key: &str);
+}
+
+/// A memory storage of callback data, which keeps up to `capacity` last
+/// payloads.
+///
+/// ## Note
+/// All the payloads will be lost after you restart your bot, so buttons of
+/// old messages will stop working.
+#[derive(Debug)]
+pub struct InMemCallbackStorage {
+    inner: Mutex<InMemInner>,
+    capacity: usize,
+}
+
+#[derive(Debug, Default)]
+struct InMemInner {
+    next_key: u64,
+    payloads: HashMap<String, String>,
+    keys: VecDeque<String>,
+}
+
+impl InMemCallbackStorage {
+    pub const DEFAULT_CAPACITY: usize = 10_000;
+
+    #[must_use]
+    pub fn new() -> Arc<Self> {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Arc<Self> {
+        Arc::new(Self { inner: Mutex::new(InMemInner::default()), capacity })
+    }
+}
+
+impl CallbackStorage for InMemCallbackStorage {
+    fn store(&self, payload: String) -> String {
+        let mut inner = self.inner.lock().unwrap();
+
+        let key = format!("{:x}", inner.next_key);
+        inner.next_key += 1;
+
+        while inner.keys.len() >= self.capacity.max(1) {
+            if let Some(old) = inner.keys.pop_front() {
+                inner.payloads.remove(&old);
+            }
+        }
+        inner.keys.push_back(key.clone());
+        inner.payloads.insert(key.clone(), payload);
+        key
+    }
+
+    fn load(&self, key: &str) -> Option<String> {
+        self.inner.lock().unwrap().payloads.get(key).cloned()
+    }
+
+    fn remove(&self, key: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.payloads.remove(key).is_some() {
+            inner.keys.retain(|old| old != key);
+        }
+    }
+}
+
+fn check_len(data: String) -> Result<String, CallbackDataError> {
+    if data.len() > MAX_CALLBACK_DATA_LEN {
+        Err(CallbackDataError::TooLong { len: data.len() })
+    } else {
+        Ok(data)
+    }
+}
+
+/// Strips `PREFIX` followed by `separator` from `data`.
+fn strip_prefix<'a>(
+    data: &'a str,
+    prefix: &str,
+    separator: char,
+) -> Option<&'a str> {
+    if !data.starts_with(prefix) {
+        return None;
+    }
+
+    let rest = &data[prefix.len()..];
+    if rest.starts_with(separator) {
+        Some(&rest[separator.len_utf8()..])
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Action {
+        Next,
+        Page(u32),
+        Note(String),
+    }
+
+    impl CallbackData for Action {
+        const PREFIX: &'static str = "a";
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Other(u32);
+
+    impl CallbackData for Other {
+        const PREFIX: &'static str = "o";
+    }
+
+    #[test]
+    fn round_trip() {
+        for action in
+            &[Action::Next, Action::Page(3), Action::Note("中文".into())]
+        {
+            let data = action.encode().unwrap();
+            assert_eq!(Action::decode(&data).as_ref(), Some(action));
+        }
+        assert_eq!(Action::Page(3).encode().unwrap(), r#"a:{"Page":3}"#);
+    }
+
+    #[test]
+    fn prefixes_are_distinguished() {
+        let data = Other(3).encode().unwrap();
+        assert_eq!(Action::decode(&data), None);
+        assert_eq!(Other::decode(&data), Some(Other(3)));
+        assert_eq!(Action::decode("ab:\"Next\""), None);
+        assert_eq!(Action::decode("a:garbage"), None);
+    }
+
+    #[test]
+    fn overflow() {
+        let action = Action::Note("x".repeat(64));
+        match action.encode() {
+            Err(CallbackDataError::TooLong { len }) => assert_eq!(len, 77),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn storage() {
+        let storage = InMemCallbackStorage::new();
+
+        let short = Action::Page(1);
+        let data = short.encode_with(&*storage).unwrap();
+        assert_eq!(data, short.encode().unwrap());
+
+        let long = Action::Note("😀".repeat(20));
+        let data = long.encode_with(&*storage).unwrap();
+        assert_eq!(data, "a#0");
+        assert_eq!(Action::decode_with(&data, &*storage), Some(long));
+        assert_eq!(
+            Action::decode_with(&short.encode().unwrap(), &*storage),
+            Some(short)
+        );
+        assert_eq!(Action::decode_with("a#1", &*storage), None);
+    }
+
+    #[test]
+    fn storage_overflow() {
+        #[derive(Serialize, Deserialize, Debug)]
+        struct Long(u32);
+
+        impl CallbackData for Long {
+            const PREFIX: &'static str = concat!(
+                "long_long_long_long_long_long_long_long_long_long_long_",
+                "long_pre"
+            );
+        }
+
+        let storage = InMemCallbackStorage::new();
+        match Long(1).encode_with(&*storage) {
+            Err(CallbackDataError::TooLong { len }) => assert_eq!(len, 65),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(storage.load("0"), None);
+        assert!(storage.inner.lock().unwrap().keys.is_empty());
+    }
+
+    #[test]
+    fn storage_eviction() {
+        let storage = InMemCallbackStorage::with_capacity(2);
+        let keys: Vec<_> =
+            (0..3).map(|i| storage.store(i.to_string())).collect();
+
+        assert_eq!(storage.load(&keys[0]), None);
+        assert_eq!(storage.load(&keys[1]).as_deref(), Some("1"));
+        assert_eq!(storage.load(&keys[2]).as_deref(), Some("2"));
+    }
+}
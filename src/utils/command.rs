@@ -49,6 +49,51 @@
 //! assert!(result.is_none());
 //! ```
 //!
+//! Commands with typed arguments implement [`TypedCommand`]:
+//! ```
+//! use std::time::Duration;
+//! use teloxide::utils::command::{
+//!     Arguments, ParseError, TypedCommand, UserMention,
+//! };
+//!
+//! #[derive(Debug, PartialEq)]
+//! enum AdminCommand {
+//!     Ban { user: UserMention, duration: Duration },
+//!     Roll(u8),
+//! }
+//!
+//! impl TypedCommand for AdminCommand {
+//!     fn from_parts(
+//!         command: &str,
+//!         args: Arguments<'_>,
+//!     ) -> Result<Self, ParseError> {
+//!         match command {
+//!             "ban" => {
+//!                 let (user, duration) = args.parse()?;
+//!                 Ok(AdminCommand::Ban { user, duration })
+//!             }
+//!             "roll" => args.parse().map(|(max,)| AdminCommand::Roll(max)),
+//!             _ => Err(ParseError::UnknownCommand(command.to_owned())),
+//!         }
+//!     }
+//! }
+//!
+//! assert_eq!(
+//!     AdminCommand::parse_typed("/ban @spammer 3h", "MyBotName"),
+//!     Ok(AdminCommand::Ban {
+//!         user: UserMention::Username("spammer".to_owned()),
+//!         duration: Duration::from_secs(3 * 60 * 60)
+//!     })
+//! );
+//!
+//! let error = AdminCommand::parse_typed("/roll 1000", "MyBotName");
+//! assert_eq!(
+//!     error.unwrap_err().to_string(),
+//!     "Argument #1 (\"1000\") is invalid: number too large to fit in target \
+//!      type"
+//! );
+//! ```
+//!
 //! See [examples/admin_bot] as a more complicated examples.
 //!
 //! [`parse_command`]: crate::utils::command::parse_command
@@ -56,8 +101,22 @@
 //! crate::utils::command::parse_command_with_prefix
 //! [examples/admin_bot]: https://github.com/teloxide/teloxide/blob/master/examples/miltiple_handlers_bot/
 
+pub use args::{
+    Arguments, CommandArgument, CommandArguments, FromArgument, ParseError,
+    Rest, UserMention,
+};
+pub use guard::{Requirement, RoleGuard};
+pub use help::{CommandInfo, Help, HelpFormat};
+pub use parser::{CommandParser, ParsedCommand};
 pub use teloxide_macros::BotCommand;
 
+use crate::types::Message;
+
+mod args;
+mod guard;
+mod help;
+mod parser;
+
 /// An enumeration of bot's commands.
 ///
 /// ## Example
@@ -107,6 +166,164 @@ pub trait BotCommand: Sized {
             N: Into<String>;
 }
 
+/// A command with typed arguments.
+///
+/// Unlike [`BotCommand`], which leaves arguments as strings, a type
+/// implementing this trait parses arguments into its fields, usually with
+/// [`Arguments::parse`]. An [`Err`] describes what is wrong with a command and
+/// can be shown to a user.
+///
+/// Note that this trait is implemented by hand rather than derived:
+/// `#[derive(BotCommand)]` comes from the `teloxide-macros` crate and doesn't
+/// know about typed arguments, so it can't be extended from here.
+///
+/// Commands are parsed with [`CommandParser`], which can be configured in
+/// [`TypedCommand::parser`]:
+/// ```
+/// use teloxide::utils::command::{
+///     Arguments, CommandParser, ParseError, TypedCommand,
+/// };
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Command {
+///     Help,
+/// }
+///
+/// impl TypedCommand for Command {
+///     fn from_parts(
+///         command: &str,
+///         args: Arguments<'_>,
+///     ) -> Result<Self, ParseError> {
+///         match command {
+///             "help" => args.parse().map(|()| Command::Help),
+///             _ => Err(ParseError::UnknownCommand(command.to_owned())),
+///         }
+///     }
+///
+///     fn parser(parser: CommandParser) -> CommandParser {
+///         parser.case_insensitive().alias("start", "help")
+///     }
+/// }
+///
+/// assert_eq!(Command::parse_typed("/Start", "MyBot"), Ok(Command::Help));
+/// ```
+pub trait TypedCommand: Sized {
+    /// A prefix of commands.
+    const PREFIX: &'static str = "/";
+
+    /// A separator of arguments; whitespace is used if `None`.
+    const SEPARATOR: Option<char> = None;
+
+    /// Creates a command from its name (without a prefix and a bot name) and
+    /// arguments.
+    ///
+    /// Aliases are already replaced with their commands.
+    fn from_parts(command: &str, args: Arguments<'_>)
+        -> Result<Self, ParseError>;
+
+    /// Information about the commands, used to render [`Help`].
+    fn commands() -> Vec<CommandInfo> {
+        Vec::new()
+    }
+
+    /// Configures a parser of the commands, e.g. adds aliases or makes it
+    /// case-insensitive.
+    ///
+    /// The parser passed in has [`TypedCommand::PREFIX`] set.
+    fn parser(parser: CommandParser) -> CommandParser {
+        parser
+    }
+
+    /// Parses a text into a command.
+    fn parse_typed<N>(text: &str, bot_name: N) -> Result<Self, ParseError>
+    where
+        N: Into<String>,
+    {
+        from_parsed(parser::<Self, _>(bot_name).parse(text)?)
+    }
+
+    /// Parses a text of `message` into a command, taking into account a
+    /// leading mention of the bot.
+    ///
+    /// See [`CommandParser::parse_message`].
+    fn parse_message<N>(
+        message: &Message,
+        bot_name: N,
+    ) -> Result<Self, ParseError>
+    where
+        N: Into<String>,
+    {
+        from_parsed(parser::<Self, _>(bot_name).parse_message(message)?)
+    }
+}
+
+pub(crate) fn parser<C, N>(bot_name: N) -> CommandParser
+where
+    C: TypedCommand,
+    N: Into<String>,
+{
+    C::parser(CommandParser::new(bot_name).prefix(C::PREFIX))
+}
+
+pub(crate) fn from_parsed<C>(
+    command: ParsedCommand<'_>,
+) -> Result<C, ParseError>
+where
+    C: TypedCommand,
+{
+    let ParsedCommand { name, args } = command;
+    match C::SEPARATOR {
+        Some(separator) => {
+            C::from_parts(&name, args.with_separator(separator))
+        }
+        None => C::from_parts(&name, args),
+    }
+}
+
+/// Splits a string into a command name and its arguments.
+///
+/// Fails with [`ParseError::NotACommand`] if `text` doesn't start with
+/// `prefix` and with [`ParseError::WrongBotName`] if the command is addressed
+/// to another bot.
+///
+/// ## Example
+/// ```
+/// use teloxide::utils::command::split_command;
+///
+/// let (command, args) =
+///     split_command("/", "/roll@my_bot 1 6", "my_bot").unwrap();
+/// assert_eq!(command, "roll");
+/// assert_eq!(args.parse::<(u8, u8)>().unwrap(), (1, 6));
+/// ```
+pub fn split_command<'a, N>(
+    prefix: &str,
+    text: &'a str,
+    bot_name: N,
+) -> Result<(&'a str, Arguments<'a>), ParseError>
+where
+    N: AsRef<str>,
+{
+    if !text.starts_with(prefix) {
+        return Err(ParseError::NotACommand);
+    }
+
+    let text = &text[prefix.len()..];
+    let end = text.find(char::is_whitespace).unwrap_or(text.len());
+    let mut splited = text[..end].splitn(2, '@');
+    let command = splited.next().unwrap_or_default();
+    match splited.next() {
+        Some(name) if name != bot_name.as_ref() => {
+            return Err(ParseError::WrongBotName(name.to_owned()))
+        }
+        _ => {}
+    }
+    if command.is_empty() {
+        return Err(ParseError::NotACommand);
+    }
+
+    Ok((command, Arguments::new(&text[end..])))
+}
+
 /// Parses a string into a command with args.
 ///
 /// It calls [`parse_command_with_prefix`] with the default prefix `/`.
@@ -128,28 +345,8 @@ pub fn parse_command<N>(text: &str, bot_name: N) -> Option<(&str, Vec<&str>)>
         N: AsRef<str>,
 {
     parse_command_with_prefix("/", text, bot_name)
-    // parse_buff_command(text)
 }
 
-// pub fn parse_buff_command<'a>(
-//     text: &'a str,
-// ) -> Option<(&'a str, Vec<&'a str>)>
-// {
-//     lazy_static! {
-//         static ref HASHTAG_REGEX : Regex = Regex::new(
-//                 r"\$\{/[^\$\{]*\}"
-//             ).unwrap();
-//     }
-//     let h: Vec<&str> = HASHTAG_REGEX.find_iter(text).map(|mat| mat.as_str().replace("${/", "").replace("}", "").as_str()).collect();
-//     if h.len() > 0 {
-//         let command = h[0].clone();
-//         Some((command, vec![]))
-//     } else {
-//         None
-//     }
-//
-// }
-
 /// Parses a string into a command with args (custom prefix).
 ///
 /// `prefix`: symbols, which denote start of a command.
@@ -171,7 +368,6 @@ pub fn parse_command_with_prefix<'a, N>(
     where
         N: AsRef<str>,
 {
-    println!("edwin #177");
     if !text.starts_with(prefix) {
         return None;
     }
@@ -207,10 +403,65 @@ mod tests {
         assert_eq!(actual, expected)
     }
 
+    #[test]
+    fn split_command_errors() {
+        assert_eq!(
+            split_command("/", "hello", "").unwrap_err(),
+            ParseError::NotACommand
+        );
+        assert_eq!(
+            split_command("/", "/ hello", "").unwrap_err(),
+            ParseError::NotACommand
+        );
+        assert_eq!(
+            split_command("/", "/start@OtherBot", "MyBot").unwrap_err(),
+            ParseError::WrongBotName("OtherBot".to_owned())
+        );
+    }
+
+    #[test]
+    fn typed_command() {
+        #[derive(Debug, PartialEq)]
+        enum Command {
+            Roll(u8, Option<u8>),
+            Say(Rest),
+        }
+
+        impl TypedCommand for Command {
+            fn from_parts(
+                command: &str,
+                args: Arguments<'_>,
+            ) -> Result<Self, ParseError> {
+                match command {
+                    "roll" => args.parse().map(|(a, b)| Command::Roll(a, b)),
+                    "say" => args.parse().map(|(text,)| Command::Say(text)),
+                    _ => Err(ParseError::UnknownCommand(command.to_owned())),
+                }
+            }
+        }
+
+        assert_eq!(
+            Command::parse_typed("/roll@MyBot 6", "MyBot"),
+            Ok(Command::Roll(6, None))
+        );
+        assert_eq!(
+            Command::parse_typed("/say hello  world", ""),
+            Ok(Command::Say(Rest("hello  world".to_owned())))
+        );
+        assert_eq!(
+            Command::parse_typed("/roll", ""),
+            Err(ParseError::TooFewArguments { expected: 1, found: 0 })
+        );
+        assert_eq!(
+            Command::parse_typed("/kick", ""),
+            Err(ParseError::UnknownCommand("kick".to_owned()))
+        );
+    }
+
     #[test]
     fn parse_command_with_args() {
-        #[command(rename = "lowercase")]
         #[derive(BotCommand, Debug, PartialEq)]
+        #[command(rename = "lowercase")]
         enum DefaultCommands {
             Start,
             Help,
@@ -224,8 +475,8 @@ mod tests {
 
     #[test]
     fn attribute_prefix() {
-        #[command(rename = "lowercase")]
         #[derive(BotCommand, Debug, PartialEq)]
+        #[command(rename = "lowercase")]
         enum DefaultCommands {
             #[command(prefix = "!")]
             Start,
@@ -240,8 +491,8 @@ mod tests {
 
     #[test]
     fn many_attributes() {
-        #[command(rename = "lowercase")]
         #[derive(BotCommand, Debug, PartialEq)]
+        #[command(rename = "lowercase")]
         enum DefaultCommands {
             #[command(prefix = "!", description = "desc")]
             Start,
@@ -257,12 +508,12 @@ mod tests {
 
     #[test]
     fn global_attributes() {
+        #[derive(BotCommand, Debug, PartialEq)]
         #[command(
         prefix = "!",
         rename = "lowercase",
         description = "Bot commands"
         )]
-        #[derive(BotCommand, Debug, PartialEq)]
         enum DefaultCommands {
             #[command(prefix = "/")]
             Start,
@@ -285,8 +536,8 @@ mod tests {
 
     #[test]
     fn parse_command_with_bot_name() {
-        #[command(rename = "lowercase")]
         #[derive(BotCommand, Debug, PartialEq)]
+        #[command(rename = "lowercase")]
         enum DefaultCommands {
             #[command(prefix = "/")]
             Start,
@@ -0,0 +1,478 @@
+use std::{borrow::Cow, fmt, str::FromStr, time::Duration};
+use thiserror::Error;
+
+/// An error occurred while parsing a command.
+///
+/// The messages are meant to be shown to a user who has sent the command.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A text doesn't start with a prefix of commands.
+    #[error("Not a command")]
+    NotACommand,
+
+    /// A command is addressed to another bot.
+    #[error("The command is addressed to @{0}")]
+    WrongBotName(String),
+
+    #[error("Unknown command: {0}")]
+    UnknownCommand(String),
+
+    #[error("Too few arguments: expected {expected}, found {found}")]
+    TooFewArguments { expected: usize, found: usize },
+
+    #[error("Too many arguments: expected {expected}, found {found}")]
+    TooManyArguments { expected: usize, found: usize },
+
+    /// An argument at `position` (starting from 1) cannot be parsed.
+    #[error("Argument #{position} ({value:?}) is invalid: {message}")]
+    IncorrectFormat { position: usize, value: String, message: String },
+
+    #[error("A quoted argument is not closed")]
+    UnclosedQuote,
+}
+
+/// Arguments of a command.
+///
+/// Arguments are separated by whitespace (or by a custom separator, see
+/// [`Arguments::with_separator`]). An argument containing separators can be
+/// quoted: `"New York"`, where `\"` and `\\` denote `"` and `\`.
+///
+/// ## Examples
+/// ```
+/// use std::time::Duration;
+/// use teloxide::utils::command::{Arguments, Rest};
+///
+/// let (hours, text): (u8, Rest) =
+///     Arguments::new("5 \"good night\" everyone").parse().unwrap();
+/// assert_eq!(hours, 5);
+/// assert_eq!(text, Rest("\"good night\" everyone".to_string()));
+///
+/// let (city, duration, note): (String, Duration, Option<String>) =
+///     Arguments::new("\"New York\", 1h30m")
+///         .with_separator(',')
+///         .parse()
+///         .unwrap();
+/// assert_eq!(city, "New York");
+/// assert_eq!(duration, Duration::from_secs(90 * 60));
+/// assert_eq!(note, None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Arguments<'a> {
+    rest: &'a str,
+    separator: Option<char>,
+    taken: usize,
+}
+
+impl<'a> Arguments<'a> {
+    /// Creates whitespace-separated arguments.
+    pub fn new(text: &'a str) -> Self {
+        Self { rest: text.trim_start(), separator: None, taken: 0 }
+    }
+
+    /// Separates arguments with `separator` instead of whitespace.
+    ///
+    /// Whitespace around arguments is trimmed.
+    pub fn with_separator(self, separator: char) -> Self {
+        Self { separator: Some(separator), ..self }
+    }
+
+    /// Returns `true` if there are no more arguments.
+    pub fn is_empty(&self) -> bool {
+        self.rest.is_empty()
+    }
+
+    /// A number of arguments taken so far.
+    pub fn taken(&self) -> usize {
+        self.taken
+    }
+
+    /// Takes the next argument, removing quotes.
+    pub fn next_raw(&mut self) -> Result<Option<Cow<'a, str>>, ParseError> {
+        if self.rest.is_empty() {
+            return Ok(None);
+        }
+
+        let (argument, rest) = if self.rest.starts_with('"') {
+            let (argument, rest) = unquote(&self.rest[1..])?;
+            (Cow::Owned(argument), rest)
+        } else {
+            let end = self
+                .rest
+                .find(|c| self.is_separator(c))
+                .unwrap_or(self.rest.len());
+            (Cow::Borrowed(self.rest[..end].trim_end()), &self.rest[end..])
+        };
+
+        self.rest = self.skip_separator(rest);
+        self.taken += 1;
+        Ok(Some(argument))
+    }
+
+    /// Takes all the remaining text as is.
+    pub fn rest(&mut self) -> &'a str {
+        let rest = self.rest.trim_end();
+        if !rest.is_empty() {
+            self.taken += 1;
+        }
+        self.rest = "";
+        rest
+    }
+
+    /// Parses all the arguments into `T`, usually a tuple.
+    ///
+    /// Fails if some arguments are left.
+    pub fn parse<T>(mut self) -> Result<T, ParseError>
+    where
+        T: CommandArguments,
+    {
+        let result = T::parse_arguments(&mut self)?;
+
+        let expected = self.taken;
+        let mut found = expected;
+        while self.next_raw()?.is_some() {
+            found += 1;
+        }
+        if found > expected {
+            return Err(ParseError::TooManyArguments { expected, found });
+        }
+
+        Ok(result)
+    }
+
+    fn is_separator(&self, c: char) -> bool {
+        match self.separator {
+            Some(separator) => c == separator,
+            None => c.is_whitespace(),
+        }
+    }
+
+    /// Skips a separator after an argument at the start of `rest`.
+    fn skip_separator(&self, rest: &'a str) -> &'a str {
+        let rest = match self.separator {
+            Some(separator) => {
+                let rest = rest.trim_start();
+                if rest.starts_with(separator) {
+                    &rest[separator.len_utf8()..]
+                } else {
+                    rest
+                }
+            }
+            None => rest,
+        };
+
+        rest.trim_start()
+    }
+}
+
+/// Reads a quoted argument up to the closing quote, returning it and the
+/// text after the quote.
+fn unquote(text: &str) -> Result<(String, &str), ParseError> {
+    let mut argument = String::new();
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((argument, &text[i + 1..])),
+            '\\' => match chars.next() {
+                Some((_, c)) if c == '"' || c == '\\' => argument.push(c),
+                Some((_, c)) => {
+                    argument.push('\\');
+                    argument.push(c);
+                }
+                None => break,
+            },
+            c => argument.push(c),
+        }
+    }
+
+    Err(ParseError::UnclosedQuote)
+}
+
+/// A type which can be parsed from a single argument of a command.
+///
+/// It's like [`FromStr`], but errors are messages which can be shown to a
+/// user.
+///
+/// [`FromStr`]: std::str::FromStr
+pub trait FromArgument: Sized {
+    fn from_argument(s: &str) -> Result<Self, String>;
+}
+
+macro_rules! impl_from_argument {
+    ($($t:ty),*) => {
+        $(
+            impl FromArgument for $t {
+                fn from_argument(s: &str) -> Result<Self, String> {
+                    <$t as FromStr>::from_str(s).map_err(|e| e.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_argument!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64,
+    bool, char, String
+);
+
+/// A user mentioned in a command: `@username` or an ID.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum UserMention {
+    Id(i64),
+
+    /// A username without `@`.
+    Username(String),
+}
+
+impl FromArgument for UserMention {
+    fn from_argument(s: &str) -> Result<Self, String> {
+        if s.starts_with('@') && s.len() > 1 {
+            Ok(UserMention::Username(s[1..].to_owned()))
+        } else {
+            s.parse()
+                .map(UserMention::Id)
+                .map_err(|_| "expected @username or a user ID".to_owned())
+        }
+    }
+}
+
+impl fmt::Display for UserMention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UserMention::Id(id) => write!(f, "{}", id),
+            UserMention::Username(username) => write!(f, "@{}", username),
+        }
+    }
+}
+
+/// Parses durations like `90` (seconds), `30s`, `15m`, `5h`, `2d` and
+/// `1h30m`.
+impl FromArgument for Duration {
+    fn from_argument(s: &str) -> Result<Self, String> {
+        const ERROR: &str = "expected a duration like 30s, 15m, 5h or 2d";
+
+        if let Ok(secs) = s.parse() {
+            return Ok(Duration::from_secs(secs));
+        }
+        if s.is_empty() {
+            return Err(ERROR.to_owned());
+        }
+
+        let mut secs = 0u64;
+        let mut rest = s;
+        while !rest.is_empty() {
+            let digits =
+                rest.find(|c: char| !c.is_ascii_digit()).ok_or(ERROR)?;
+            let number: u64 = rest[..digits].parse().map_err(|_| ERROR)?;
+            let mut chars = rest[digits..].chars();
+            let unit = match chars.next() {
+                Some('s') => 1,
+                Some('m') => 60,
+                Some('h') => 60 * 60,
+                Some('d') => 24 * 60 * 60,
+                _ => return Err(ERROR.to_owned()),
+            };
+
+            secs = number
+                .checked_mul(unit)
+                .and_then(|n| secs.checked_add(n))
+                .ok_or("the duration is too long")?;
+            rest = chars.as_str();
+        }
+
+        Ok(Duration::from_secs(secs))
+    }
+}
+
+/// All the remaining text of a command, taken as is.
+///
+/// Can be empty.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Rest(pub String);
+
+/// A type which can be taken from [`Arguments`] as a field of a command.
+///
+/// Implemented for types implementing [`FromArgument`] (required arguments),
+/// `Option`s of them (optional arguments) and [`Rest`].
+pub trait CommandArgument: Sized {
+    /// Whether the argument must be present.
+    const REQUIRED: bool = true;
+
+    fn take(args: &mut Arguments<'_>) -> Result<Self, ParseError>;
+}
+
+impl<T> CommandArgument for T
+where
+    T: FromArgument,
+{
+    fn take(args: &mut Arguments<'_>) -> Result<Self, ParseError> {
+        let found = args.taken();
+        match <Option<T> as CommandArgument>::take(args)? {
+            Some(value) => Ok(value),
+            None => {
+                Err(ParseError::TooFewArguments { expected: found + 1, found })
+            }
+        }
+    }
+}
+
+impl<T> CommandArgument for Option<T>
+where
+    T: FromArgument,
+{
+    const REQUIRED: bool = false;
+
+    fn take(args: &mut Arguments<'_>) -> Result<Self, ParseError> {
+        let position = args.taken() + 1;
+        match args.next_raw()? {
+            Some(value) => {
+                T::from_argument(&value).map(Some).map_err(|message| {
+                    ParseError::IncorrectFormat {
+                        position,
+                        value: value.into_owned(),
+                        message,
+                    }
+                })
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl CommandArgument for Rest {
+    const REQUIRED: bool = false;
+
+    fn take(args: &mut Arguments<'_>) -> Result<Self, ParseError> {
+        Ok(Rest(args.rest().to_owned()))
+    }
+}
+
+/// A type which can be parsed from all [`Arguments`] of a command.
+///
+/// Implemented for `()` and tuples of up to 8 [`CommandArgument`]s.
+pub trait CommandArguments: Sized {
+    fn parse_arguments(args: &mut Arguments<'_>) -> Result<Self, ParseError>;
+}
+
+macro_rules! impl_command_arguments {
+    ($($t:ident),*) => {
+        impl<$($t),*> CommandArguments for ($($t,)*)
+        where
+            $($t: CommandArgument,)*
+        {
+            #[allow(unused_variables)]
+            fn parse_arguments(
+                args: &mut Arguments<'_>,
+            ) -> Result<Self, ParseError> {
+                let expected =
+                    0 $(+ <$t as CommandArgument>::REQUIRED as usize)*;
+                let result = ($(
+                    <$t as CommandArgument>::take(args).map_err(|error| {
+                        match error {
+                            ParseError::TooFewArguments { found, .. } => {
+                                ParseError::TooFewArguments { expected, found }
+                            }
+                            error => error,
+                        }
+                    })?,
+                )*);
+
+                Ok(result)
+            }
+        }
+    };
+}
+
+impl_command_arguments!();
+impl_command_arguments!(A);
+impl_command_arguments!(A, B);
+impl_command_arguments!(A, B, C);
+impl_command_arguments!(A, B, C, D);
+impl_command_arguments!(A, B, C, D, E);
+impl_command_arguments!(A, B, C, D, E, F);
+impl_command_arguments!(A, B, C, D, E, F, G);
+impl_command_arguments!(A, B, C, D, E, F, G, H);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse<T: CommandArguments>(text: &str) -> Result<T, ParseError> {
+        Arguments::new(text).parse()
+    }
+
+    #[test]
+    fn typed_arguments() {
+        assert_eq!(
+            parse::<(UserMention, Duration)>("@waffle 5h"),
+            Ok((
+                UserMention::Username("waffle".to_owned()),
+                Duration::from_secs(5 * 60 * 60)
+            ))
+        );
+        assert_eq!(parse::<(u8,)>("  42  "), Ok((42,)));
+        assert_eq!(parse::<()>(""), Ok(()));
+    }
+
+    #[test]
+    fn quotes() {
+        assert_eq!(
+            parse::<(String, String)>(r#""a \"b\" \\ c" d"#),
+            Ok((r#"a "b" \ c"#.to_owned(), "d".to_owned()))
+        );
+        assert_eq!(parse::<(String,)>("\"abc"), Err(ParseError::UnclosedQuote));
+    }
+
+    #[test]
+    fn separator() {
+        let args = Arguments::new(" a b , \"c, d\" ,e").with_separator(',');
+        assert_eq!(
+            args.parse::<(String, String, String)>(),
+            Ok(("a b".to_owned(), "c, d".to_owned(), "e".to_owned()))
+        );
+    }
+
+    #[test]
+    fn optional_and_rest() {
+        assert_eq!(
+            parse::<(u8, Option<u8>, Rest)>("1"),
+            Ok((1, None, Rest(String::new())))
+        );
+        assert_eq!(
+            parse::<(u8, Option<u8>, Rest)>("1 2 three  four "),
+            Ok((1, Some(2), Rest("three  four".to_owned())))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            parse::<(u8, u8, Option<u8>)>("1"),
+            Err(ParseError::TooFewArguments { expected: 2, found: 1 })
+        );
+        assert_eq!(
+            parse::<(u8,)>("1 2 3"),
+            Err(ParseError::TooManyArguments { expected: 1, found: 3 })
+        );
+        assert_eq!(
+            parse::<(u8, Duration)>("1 5y"),
+            Err(ParseError::IncorrectFormat {
+                position: 2,
+                value: "5y".to_owned(),
+                message: "expected a duration like 30s, 15m, 5h or 2d"
+                    .to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn durations() {
+        let parse = Duration::from_argument;
+        assert_eq!(parse("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse("1h30m"), Ok(Duration::from_secs(90 * 60)));
+        assert_eq!(parse("2d"), Ok(Duration::from_secs(2 * 24 * 60 * 60)));
+        assert!(parse("h").is_err());
+        assert!(parse("5").is_ok());
+        assert!(parse("5x").is_err());
+        assert!(parse("").is_err());
+    }
+}
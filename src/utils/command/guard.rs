@@ -0,0 +1,360 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    dispatching::UpdateWithCx,
+    requests::{Request, ResponseResult},
+    types::{ChatMember, ChatMemberStatus, Message},
+    utils::command::{CommandInfo, TypedCommand},
+};
+
+/// A requirement for a caller of a command, checked by [`RoleGuard`].
+///
+/// Creators of chats meet all the requirements.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Requirement {
+    /// A guild role with this name.
+    RoleName(String),
+
+    /// A guild role with this ID.
+    RoleId(i64),
+
+    /// All bits of this mask in permissions of the caller's guild roles.
+    Permissions(i64),
+
+    /// A creator or an administrator of the chat.
+    Administrator,
+}
+
+impl Requirement {
+    /// Returns `true` if `member` meets the requirement.
+    pub fn is_met_by(&self, member: &ChatMember) -> bool {
+        if member.status == ChatMemberStatus::Creator {
+            return true;
+        }
+
+        let roles = member.roles.as_deref().unwrap_or_default();
+        match self {
+            Requirement::RoleName(name) => {
+                roles.iter().any(|role| &role.name == name)
+            }
+            Requirement::RoleId(id) => roles.iter().any(|role| role.id == *id),
+            Requirement::Permissions(mask) => {
+                let permissions =
+                    roles.iter().fold(0, |acc, role| acc | role.permissions);
+                permissions & mask == *mask
+            }
+            Requirement::Administrator => {
+                member.status == ChatMemberStatus::Administrator
+            }
+        }
+    }
+}
+
+/// Lets through only commands whose callers meet requirements of the
+/// commands (see [`CommandInfo::require_role`] and
+/// [`CommandInfo::admin_only`]).
+///
+/// Callers are resolved with [`Bot::get_chat_member`] and cached for a
+/// minute by default.
+///
+/// Requirements are set in [`TypedCommand::commands`] rather than with a
+/// `#[command(require_role = "...")]` attribute, because
+/// `#[derive(BotCommand)]` comes from the `teloxide-macros` crate, which
+/// doesn't support them.
+///
+/// ## Examples
+/// ```no_run
+/// use teloxide::{
+///     prelude::*,
+///     utils::command::{CommandInfo, RoleGuard, TypedCommand},
+/// };
+/// # use teloxide::utils::command::{Arguments, ParseError};
+///
+/// enum Command {
+///     Ban,
+/// }
+///
+/// impl TypedCommand for Command {
+///     // ...
+/// #    fn from_parts(
+/// #        command: &str,
+/// #        args: Arguments<'_>,
+/// #    ) -> Result<Self, ParseError> {
+/// #        args.parse().map(|()| Command::Ban)
+/// #    }
+///
+///     fn commands() -> Vec<CommandInfo> {
+///         vec![CommandInfo::new("ban").require_role("Moderator")]
+///     }
+/// }
+///
+/// # async fn run(rx: DispatcherHandlerRx<Message>) {
+/// let guard = RoleGuard::of::<Command>()
+///     .denial_message("Only moderators can ban users.");
+/// rx.typed_commands_with_guard::<Command, _>("MyBot", guard, None)
+///     .for_each(|(cx, command)| async move { /* ... */ })
+///     .await;
+/// # }
+/// ```
+///
+/// [`Bot::get_chat_member`]: crate::Bot::get_chat_member
+#[derive(Debug)]
+pub struct RoleGuard {
+    commands: Vec<CommandInfo>,
+    cache_ttl: Duration,
+    denial_message: Option<String>,
+    members: Mutex<HashMap<(i64, i64), (Instant, ChatMember)>>,
+}
+
+impl RoleGuard {
+    pub fn new(commands: Vec<CommandInfo>) -> Self {
+        Self {
+            commands,
+            cache_ttl: Duration::from_secs(60),
+            denial_message: Some(
+                "You are not allowed to use this command.".to_owned(),
+            ),
+            members: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// A guard for [`TypedCommand::commands`].
+    pub fn of<C>() -> Self
+    where
+        C: TypedCommand,
+    {
+        Self::new(C::commands())
+    }
+
+    /// How long a resolved caller is cached.
+    pub fn cache_for(mut self, val: Duration) -> Self {
+        self.cache_ttl = val;
+        self
+    }
+
+    /// A reply to a caller who is not allowed to use a command.
+    pub fn denial_message<T>(mut self, val: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.denial_message = Some(val.into());
+        self
+    }
+
+    /// Ignores commands of callers who are not allowed to use them, without
+    /// replying.
+    pub fn deny_silently(mut self) -> Self {
+        self.denial_message = None;
+        self
+    }
+
+    /// Returns `true` if a sender of `cx` is allowed to use `command`.
+    pub async fn is_allowed(
+        &self,
+        cx: &UpdateWithCx<Message>,
+        command: &str,
+    ) -> ResponseResult<bool> {
+        let info = match self
+            .commands
+            .iter()
+            .find(|info| info.name.eq_ignore_ascii_case(command))
+        {
+            Some(info) if info.is_restricted() => info,
+            _ => return Ok(true),
+        };
+        let user_id = match cx.update.from() {
+            Some(user) => user.id,
+            None => return Ok(false),
+        };
+
+        let member = self.member(cx, user_id).await?;
+        Ok(info.is_allowed_for(&member))
+    }
+
+    /// Checks a caller of `command`, replying with the denial message if the
+    /// caller is not allowed to use it.
+    ///
+    /// If the caller cannot be resolved, the command is denied.
+    pub(crate) async fn check(
+        &self,
+        cx: &UpdateWithCx<Message>,
+        command: &str,
+    ) -> bool {
+        match self.is_allowed(cx, command).await {
+            Ok(true) => return true,
+            Ok(false) => {}
+            Err(error) => {
+                log::error!("Cannot check a caller of /{}: {}", command, error)
+            }
+        }
+
+        if let Some(text) = &self.denial_message {
+            if let Err(error) = cx.reply_to(text.as_str()).send().await {
+                log::error!("Cannot deny /{}: {}", command, error);
+            }
+        }
+        false
+    }
+
+    async fn member(
+        &self,
+        cx: &UpdateWithCx<Message>,
+        user_id: i64,
+    ) -> ResponseResult<ChatMember> {
+        let chat = &cx.update.chat;
+        let key = (chat.id, user_id);
+        let ttl = self.cache_ttl;
+        let cached = self
+            .members
+            .lock()
+            .unwrap()
+            .get(&key)
+            .filter(|(at, _)| at.elapsed() < ttl)
+            .map(|(_, member)| member.clone());
+        if let Some(member) = cached {
+            return Ok(member);
+        }
+
+        let member = cx
+            .bot
+            .get_chat_member(Some(chat.id), chat.guild_id, user_id)
+            .send()
+            .await?;
+
+        let mut members = self.members.lock().unwrap();
+        members.retain(|_, (at, _)| at.elapsed() < ttl);
+        members.insert(key, (Instant::now(), member.clone()));
+        Ok(member)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{net::Replay, types::GuildRole, Bot};
+    use std::sync::Arc;
+
+    const MESSAGE: &str = r#"{
+        "message_id":2,
+        "from":{"id":3,"is_bot":false,"first_name":"Ferris"},
+        "chat":{"id":-5,"type":"group"},
+        "date":0,
+        "text":"/ban"
+    }"#;
+
+    const HELPER: &str = r#"
+{"kind":"request","method_name":"getChatMember","params":null,"response":"{\"ok\":true,\"result\":{\"user\":{\"id\":3,\"is_bot\":false,\"first_name\":\"Ferris\"},\"status\":\"member\",\"roles\":[{\"id\":1,\"name\":\"Helper\",\"position\":0,\"permissions\":0,\"color\":0}]}}"}
+"#;
+
+    const MODERATOR: &str = r#"
+{"kind":"request","method_name":"getChatMember","params":null,"response":"{\"ok\":true,\"result\":{\"user\":{\"id\":3,\"is_bot\":false,\"first_name\":\"Ferris\"},\"status\":\"member\",\"roles\":[{\"id\":1,\"name\":\"Moderator\",\"position\":0,\"permissions\":0,\"color\":0}]}}"}
+"#;
+
+    const DENIAL: &str = r#"
+{"kind":"request","method_name":"sendMessage","params":{"chat_id":-5,"text":"Moderators only.","reply_to_message_id":2},"response":null}
+"#;
+
+    fn guard_of_ban() -> RoleGuard {
+        RoleGuard::new(vec![CommandInfo::new("ban").require_role("Moderator")])
+    }
+
+    /// Checks `command` with `guard` against a recording of `requests`.
+    async fn check(
+        guard: &RoleGuard,
+        command: &str,
+        requests: &[&str],
+    ) -> bool {
+        let requests = requests.concat();
+        let replay = Replay::from_reader(requests.trim().as_bytes()).unwrap();
+        let bot = Bot::new("").with_transport(Arc::clone(&replay));
+        let cx = UpdateWithCx::new(bot, serde_json::from_str(MESSAGE).unwrap());
+
+        let allowed = guard.check(&cx, command).await;
+        replay.assert_finished();
+        allowed
+    }
+
+    #[tokio::test]
+    async fn denial_is_replied() {
+        let guard = guard_of_ban().denial_message("Moderators only.");
+        assert!(!check(&guard, "ban", &[HELPER, DENIAL]).await);
+        // The caller is cached.
+        assert!(!check(&guard, "ban", &[DENIAL]).await);
+        // Unrestricted commands don't need the caller.
+        assert!(check(&guard, "help", &[]).await);
+
+        let guard = guard_of_ban().deny_silently();
+        assert!(!check(&guard, "ban", &[HELPER]).await);
+    }
+
+    #[tokio::test]
+    async fn callers_are_cached() {
+        let guard = guard_of_ban();
+        assert!(check(&guard, "ban", &[MODERATOR]).await);
+        assert!(check(&guard, "BAN", &[]).await);
+
+        let guard = guard_of_ban().cache_for(Duration::from_secs(0));
+        assert!(check(&guard, "ban", &[MODERATOR]).await);
+        assert!(check(&guard, "ban", &[MODERATOR]).await);
+    }
+
+    fn member(status: ChatMemberStatus, roles: Vec<GuildRole>) -> ChatMember {
+        ChatMember {
+            status,
+            roles: Some(roles),
+            ..ChatMember::new(1, None, None)
+        }
+    }
+
+    fn role(id: i64, name: &str, permissions: i64) -> GuildRole {
+        GuildRole::new(id, name.to_owned(), 0, permissions, 0)
+    }
+
+    #[test]
+    fn requirements() {
+        let moderator = member(
+            ChatMemberStatus::Member,
+            vec![role(1, "Moderator", 0b011), role(2, "Helper", 0b100)],
+        );
+
+        assert!(
+            Requirement::RoleName("Moderator".to_owned()).is_met_by(&moderator)
+        );
+        assert!(
+            !Requirement::RoleName("Admin".to_owned()).is_met_by(&moderator)
+        );
+        assert!(Requirement::RoleId(2).is_met_by(&moderator));
+        assert!(Requirement::Permissions(0b101).is_met_by(&moderator));
+        assert!(!Requirement::Permissions(0b1000).is_met_by(&moderator));
+        assert!(!Requirement::Administrator.is_met_by(&moderator));
+
+        let creator = member(ChatMemberStatus::Creator, vec![]);
+        assert!(Requirement::RoleId(3).is_met_by(&creator));
+        assert!(Requirement::Administrator.is_met_by(&creator));
+    }
+
+    #[test]
+    fn commands() {
+        let admin = member(ChatMemberStatus::Administrator, vec![]);
+        let moderator =
+            member(ChatMemberStatus::Member, vec![role(1, "Moderator", 0)]);
+
+        let ban =
+            CommandInfo::new("ban").admin_only().require_role("Moderator");
+        assert!(ban.is_restricted());
+        assert!(!ban.is_allowed_for(&admin));
+        assert!(!ban.is_allowed_for(&moderator));
+
+        let warn = CommandInfo::new("warn").require_role("Moderator");
+        assert!(warn.is_allowed_for(&moderator));
+        assert!(!warn.is_allowed_for(&admin));
+
+        let help = CommandInfo::new("help");
+        assert!(!help.is_restricted());
+        assert!(help.is_allowed_for(&moderator));
+    }
+}
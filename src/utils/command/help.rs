@@ -0,0 +1,414 @@
+use crate::{
+    dispatching::UpdateWithCx,
+    requests::{Request, ResponseResult},
+    types::{BotCommand, ChatMember, Message, ParseMode},
+    utils::{
+        command::{Requirement, TypedCommand},
+        html, markdown,
+    },
+};
+
+/// Information about a command, used to render help and to publish commands
+/// with [`Bot::set_my_commands`].
+///
+/// ## Examples
+/// ```
+/// use teloxide::utils::command::CommandInfo;
+///
+/// let info = CommandInfo::new("ban")
+///     .description("ban a user.")
+///     .args_schema("<user> [duration]")
+///     .admin_only();
+/// assert_eq!(info.usage(), "/ban <user> [duration]");
+/// ```
+///
+/// [`Bot::set_my_commands`]: crate::Bot::set_my_commands
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CommandInfo {
+    /// A name of the command without a prefix.
+    pub name: String,
+
+    /// A prefix of the command, `/` by default.
+    pub prefix: String,
+
+    pub description: Option<String>,
+
+    /// A human-readable description of arguments, e.g. `<user> [duration]`.
+    pub args_schema: Option<String>,
+
+    /// Hidden commands are not listed in help and are not published.
+    pub hidden: bool,
+
+    /// Admin-only commands are marked in help and are not published.
+    ///
+    /// They are allowed only to creators and administrators of chats by
+    /// [`RoleGuard`].
+    ///
+    /// [`RoleGuard`]: crate::utils::command::RoleGuard
+    pub admin_only: bool,
+
+    /// Requirements for callers of the command, checked by [`RoleGuard`].
+    ///
+    /// Commands with requirements are not published.
+    ///
+    /// [`RoleGuard`]: crate::utils::command::RoleGuard
+    pub requirements: Vec<Requirement>,
+}
+
+impl CommandInfo {
+    pub fn new<N>(name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            prefix: "/".to_owned(),
+            description: None,
+            args_schema: None,
+            hidden: false,
+            admin_only: false,
+            requirements: Vec::new(),
+        }
+    }
+
+    pub fn prefix<P>(mut self, val: P) -> Self
+    where
+        P: Into<String>,
+    {
+        self.prefix = val.into();
+        self
+    }
+
+    pub fn description<D>(mut self, val: D) -> Self
+    where
+        D: Into<String>,
+    {
+        self.description = Some(val.into());
+        self
+    }
+
+    pub fn args_schema<A>(mut self, val: A) -> Self
+    where
+        A: Into<String>,
+    {
+        self.args_schema = Some(val.into());
+        self
+    }
+
+    pub fn hidden(mut self) -> Self {
+        self.hidden = true;
+        self
+    }
+
+    pub fn admin_only(mut self) -> Self {
+        self.admin_only = true;
+        self
+    }
+
+    /// Requires a caller to have a guild role named `name`.
+    pub fn require_role<N>(self, name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        self.require(Requirement::RoleName(name.into()))
+    }
+
+    /// Requires a caller to have all bits of `mask` in permissions of their
+    /// guild roles.
+    pub fn require_permission(self, mask: i64) -> Self {
+        self.require(Requirement::Permissions(mask))
+    }
+
+    pub fn require(mut self, val: Requirement) -> Self {
+        self.requirements.push(val);
+        self
+    }
+
+    /// Returns `true` if the command is admin-only or has requirements.
+    pub fn is_restricted(&self) -> bool {
+        self.admin_only || !self.requirements.is_empty()
+    }
+
+    /// Returns `true` if `member` is allowed to call the command.
+    pub fn is_allowed_for(&self, member: &ChatMember) -> bool {
+        (!self.admin_only || Requirement::Administrator.is_met_by(member))
+            && self.requirements.iter().all(|req| req.is_met_by(member))
+    }
+
+    /// The command with its prefix and arguments, e.g.
+    /// `/ban <user> [duration]`.
+    pub fn usage(&self) -> String {
+        match &self.args_schema {
+            Some(args) => format!("{}{} {}", self.prefix, self.name, args),
+            None => format!("{}{}", self.prefix, self.name),
+        }
+    }
+
+    /// Returns `true` if `topic` (e.g. `ban` or `/ban`) names this command.
+    fn is_named(&self, topic: &str) -> bool {
+        let topic = if topic.starts_with(&self.prefix) {
+            &topic[self.prefix.len()..]
+        } else {
+            topic
+        };
+
+        topic.eq_ignore_ascii_case(&self.name)
+    }
+}
+
+/// A format of rendered help.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HelpFormat {
+    Plain,
+    Html,
+    MarkdownV2,
+}
+
+impl HelpFormat {
+    /// A parse mode of messages with help in this format.
+    pub fn parse_mode(self) -> Option<ParseMode> {
+        match self {
+            HelpFormat::Plain => None,
+            HelpFormat::Html => Some(ParseMode::HTML),
+            HelpFormat::MarkdownV2 => Some(ParseMode::MarkdownV2),
+        }
+    }
+
+    fn bold(self, s: &str) -> String {
+        match self {
+            HelpFormat::Plain => s.to_owned(),
+            HelpFormat::Html => html::bold(&html::escape(s)),
+            HelpFormat::MarkdownV2 => markdown::bold(&markdown::escape(s)),
+        }
+    }
+
+    fn code(self, s: &str) -> String {
+        match self {
+            HelpFormat::Plain => s.to_owned(),
+            HelpFormat::Html => html::code_inline(s),
+            HelpFormat::MarkdownV2 => markdown::code_inline(s),
+        }
+    }
+
+    fn escape(self, s: &str) -> String {
+        match self {
+            HelpFormat::Plain => s.to_owned(),
+            HelpFormat::Html => html::escape(s),
+            HelpFormat::MarkdownV2 => markdown::escape(s),
+        }
+    }
+}
+
+/// Help for a list of commands, answering `/help [command]`.
+///
+/// ## Examples
+/// ```
+/// use teloxide::utils::command::{CommandInfo, Help, HelpFormat};
+///
+/// let help = Help::new(vec![
+///     CommandInfo::new("help").description("show this text."),
+///     CommandInfo::new("ban")
+///         .description("ban a user.")
+///         .args_schema("<user>"),
+///     CommandInfo::new("debug").hidden(),
+/// ])
+/// .header("Commands:");
+///
+/// assert_eq!(
+///     help.render(None),
+///     "Commands:\n/help - show this text.\n/ban <user> - ban a user."
+/// );
+/// assert_eq!(help.render(Some("ban")), "/ban <user>\nban a user.");
+///
+/// let help = help.format(HelpFormat::Html);
+/// assert_eq!(
+///     help.render(Some("/ban")),
+///     "<code>/ban &lt;user&gt;</code>\nban a user."
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Help {
+    commands: Vec<CommandInfo>,
+    header: Option<String>,
+    format: HelpFormat,
+}
+
+impl Help {
+    pub fn new(commands: Vec<CommandInfo>) -> Self {
+        Self { commands, header: None, format: HelpFormat::Plain }
+    }
+
+    /// Help for [`TypedCommand::commands`].
+    pub fn of<C>() -> Self
+    where
+        C: TypedCommand,
+    {
+        Self::new(C::commands())
+    }
+
+    /// A text before the list of commands.
+    pub fn header<H>(mut self, val: H) -> Self
+    where
+        H: Into<String>,
+    {
+        self.header = Some(val.into());
+        self
+    }
+
+    /// A format of rendered help ([`HelpFormat::Plain`] by default).
+    pub fn format(mut self, val: HelpFormat) -> Self {
+        self.format = val;
+        self
+    }
+
+    pub fn commands(&self) -> &[CommandInfo] {
+        &self.commands
+    }
+
+    /// Renders the list of commands, or help for a command named `topic`.
+    pub fn render(&self, topic: Option<&str>) -> String {
+        let topic = match topic {
+            Some(topic) => topic,
+            None => return self.render_list(),
+        };
+
+        match self.commands.iter().find(|info| info.is_named(topic)) {
+            Some(info) => self.render_command(info),
+            None => format!(
+                "{}\n\n{}",
+                self.format.escape(&format!("Unknown command: {}", topic)),
+                self.render_list()
+            ),
+        }
+    }
+
+    /// Answers `cx` with help for `topic` (see [`Help::render`]).
+    pub async fn reply(
+        &self,
+        cx: &UpdateWithCx<Message>,
+        topic: Option<&str>,
+    ) -> ResponseResult<Message> {
+        let request = cx.answer(self.render(topic));
+        match self.format.parse_mode() {
+            Some(parse_mode) => request.parse_mode(parse_mode).send().await,
+            None => request.send().await,
+        }
+    }
+
+    /// Commands to be published with [`Bot::set_my_commands`].
+    ///
+    /// Hidden and restricted commands, as well as commands with a prefix
+    /// other than `/`, are skipped. Commands without a description are
+    /// described by their usage.
+    ///
+    /// [`Bot::set_my_commands`]: crate::Bot::set_my_commands
+    pub fn bot_commands(&self) -> Vec<BotCommand> {
+        self.commands
+            .iter()
+            .filter(|info| !info.hidden && !info.is_restricted())
+            .filter(|info| info.prefix == "/")
+            .map(|info| {
+                let description =
+                    info.description.clone().unwrap_or_else(|| info.usage());
+                BotCommand::new(info.name.to_lowercase(), description)
+            })
+            .collect()
+    }
+
+    fn render_list(&self) -> String {
+        let lines = self
+            .commands
+            .iter()
+            .filter(|info| !info.hidden)
+            .map(|info| self.render_line(info));
+
+        self.header
+            .iter()
+            .map(|header| self.format.escape(header))
+            .chain(lines)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn render_line(&self, info: &CommandInfo) -> String {
+        let mut line = self.format.code(&info.usage());
+        if let Some(description) = &info.description {
+            line += &self.format.escape(&format!(" - {}", description));
+        }
+        if info.admin_only {
+            line += &self.format.escape(" (admins only)");
+        }
+        line
+    }
+
+    fn render_command(&self, info: &CommandInfo) -> String {
+        let mut text = self.format.code(&info.usage());
+        if let Some(description) = &info.description {
+            text += "\n";
+            text += &self.format.escape(description);
+        }
+        if info.admin_only {
+            text += "\n";
+            text += &self.format.bold("Admins only.");
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn help() -> Help {
+        Help::new(vec![
+            CommandInfo::new("help").description("show this text."),
+            CommandInfo::new("ban")
+                .description("ban a user.")
+                .args_schema("<user>")
+                .admin_only(),
+            CommandInfo::new("debug").hidden(),
+            CommandInfo::new("roll").prefix("!").args_schema("<max>"),
+        ])
+    }
+
+    #[test]
+    fn plain() {
+        let help = help();
+
+        assert_eq!(
+            help.render(None),
+            "/help - show this text.\n/ban <user> - ban a user. (admins \
+             only)\n!roll <max>"
+        );
+        assert_eq!(
+            help.render(Some("/BAN")),
+            "/ban <user>\nban a user.\nAdmins only."
+        );
+        assert_eq!(help.render(Some("debug")), "/debug");
+        assert!(help.render(Some("kick")).starts_with("Unknown command: kick"));
+    }
+
+    #[test]
+    fn markdown() {
+        let help =
+            help().header("Use_commands:").format(HelpFormat::MarkdownV2);
+
+        assert_eq!(
+            help.render(None),
+            "Use\\_commands:\n`/help` \\- show this text\\.\n`/ban <user>` \\- \
+             ban a user\\. \\(admins only\\)\n`!roll <max>`"
+        );
+        assert_eq!(
+            help.render(Some("ban")),
+            "`/ban <user>`\nban a user\\.\n*Admins only\\.*"
+        );
+    }
+
+    #[test]
+    fn bot_commands() {
+        assert_eq!(
+            help().bot_commands(),
+            vec![BotCommand::new("help", "show this text.")]
+        );
+    }
+}
@@ -0,0 +1,330 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use crate::types::{Message, MessageEntity, MessageEntityKind};
+
+use super::{Arguments, ParseError};
+
+/// A configurable parser of commands.
+///
+/// Understands:
+///  - `/command@BotName args`, where `@BotName` is optional;
+///  - `${/command} args`, a command token inserted by the client, possibly
+///    in the middle of a text, unless the text begins with a command;
+///  - `@BotName /command args`, a command addressed with a leading mention of
+///    a bot (see [`CommandParser::parse_message`]).
+///
+/// ## Examples
+/// ```
+/// use teloxide::utils::command::CommandParser;
+///
+/// let parser = CommandParser::new("MyBot")
+///     .case_insensitive()
+///     .alias("start", "help");
+///
+/// let command = parser.parse("/HELP@MyBot me").unwrap();
+/// assert_eq!(command.name, "help");
+/// assert_eq!(command.args.parse::<(String,)>().unwrap().0, "me");
+///
+/// let command = parser.parse("Please, ${/start} now").unwrap();
+/// assert_eq!(command.name, "help");
+/// assert_eq!(command.args.parse::<(String,)>().unwrap().0, "now");
+/// ```
+#[derive(Debug, Clone)]
+pub struct CommandParser {
+    bot_name: String,
+    prefix: String,
+    case_insensitive: bool,
+    aliases: HashMap<String, String>,
+}
+
+/// A command returned by [`CommandParser`].
+#[derive(Debug, Clone)]
+pub struct ParsedCommand<'a> {
+    /// A name of the command without a prefix and a bot name.
+    ///
+    /// Aliases are replaced with their commands; the name is lowercase if
+    /// the parser is case-insensitive.
+    pub name: Cow<'a, str>,
+
+    pub args: Arguments<'a>,
+}
+
+impl CommandParser {
+    /// Creates a parser of commands with the `/` prefix, addressed to a bot
+    /// named `bot_name`.
+    pub fn new<N>(bot_name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            bot_name: bot_name.into(),
+            prefix: "/".to_owned(),
+            case_insensitive: false,
+            aliases: HashMap::new(),
+        }
+    }
+
+    /// Sets a prefix of commands (the default is `/`).
+    pub fn prefix<P>(mut self, prefix: P) -> Self
+    where
+        P: Into<String>,
+    {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Matches commands ignoring the case.
+    ///
+    /// Returned names are lowercase.
+    pub fn case_insensitive(mut self) -> Self {
+        self.case_insensitive = true;
+        self.aliases = self
+            .aliases
+            .into_iter()
+            .map(|(alias, command)| {
+                (alias.to_lowercase(), command.to_lowercase())
+            })
+            .collect();
+        self
+    }
+
+    /// Makes `alias` another name of `command`.
+    pub fn alias<A, C>(mut self, alias: A, command: C) -> Self
+    where
+        A: Into<String>,
+        C: Into<String>,
+    {
+        let (mut alias, mut command) = (alias.into(), command.into());
+        if self.case_insensitive {
+            alias = alias.to_lowercase();
+            command = command.to_lowercase();
+        }
+
+        self.aliases.insert(alias, command);
+        self
+    }
+
+    /// Parses a text into a command.
+    pub fn parse<'a>(
+        &self,
+        text: &'a str,
+    ) -> Result<ParsedCommand<'a>, ParseError> {
+        let (token, args) = self.find_command(text)?;
+
+        let mut splited = token.splitn(2, '@');
+        let name = splited.next().unwrap_or_default();
+        match splited.next() {
+            Some(bot_name) if !self.is_our_name(bot_name) => {
+                return Err(ParseError::WrongBotName(bot_name.to_owned()))
+            }
+            _ => {}
+        }
+        if name.is_empty() {
+            return Err(ParseError::NotACommand);
+        }
+
+        Ok(ParsedCommand {
+            name: self.resolve(name),
+            args: Arguments::new(args),
+        })
+    }
+
+    /// Parses a text of `message` into a command.
+    ///
+    /// See [`CommandParser::parse_with_entities`].
+    pub fn parse_message<'a>(
+        &self,
+        message: &'a Message,
+    ) -> Result<ParsedCommand<'a>, ParseError> {
+        let text = message.text().ok_or(ParseError::NotACommand)?;
+        self.parse_with_entities(text, message.entities().unwrap_or_default())
+    }
+
+    /// Parses a text with `entities` into a command.
+    ///
+    /// If the text starts with a mention of a user
+    /// ([`MessageEntityKind::Mention`] or [`MessageEntityKind::TextMention`]),
+    /// the rest of the text is parsed as a command addressed to this user.
+    /// Commands addressed to other users fail with
+    /// [`ParseError::WrongBotName`].
+    ///
+    /// [`MessageEntityKind::Mention`]:
+    /// crate::types::MessageEntityKind::Mention
+    /// [`MessageEntityKind::TextMention`]:
+    /// crate::types::MessageEntityKind::TextMention
+    pub fn parse_with_entities<'a>(
+        &self,
+        text: &'a str,
+        entities: &[MessageEntity],
+    ) -> Result<ParsedCommand<'a>, ParseError> {
+        let mention = entities
+            .iter()
+            .find(|entity| entity.offset == 0)
+            .and_then(|entity| {
+                let range = entity.byte_range(text)?;
+                let name = match &entity.kind {
+                    MessageEntityKind::Mention => {
+                        text[range.clone()].trim_start_matches('@')
+                    }
+                    MessageEntityKind::TextMention { user } => {
+                        user.username.as_deref().unwrap_or_default()
+                    }
+                    _ => return None,
+                };
+                Some((name, &text[range.end..]))
+            });
+
+        match mention {
+            Some((name, rest)) => {
+                let command = self.parse(rest.trim_start())?;
+                if self.is_our_name(name) {
+                    Ok(command)
+                } else {
+                    Err(ParseError::WrongBotName(name.to_owned()))
+                }
+            }
+            None => self.parse(text),
+        }
+    }
+
+    /// Finds a command token (a name with an optional bot name), returning it
+    /// and the text after it.
+    fn find_command<'a>(
+        &self,
+        text: &'a str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        let platform_prefix = format!("${{{}", self.prefix);
+        // A leading command takes precedence over an embedded token, which
+        // can be a part of arguments.
+        if text.starts_with(&self.prefix) && !text.starts_with(&platform_prefix)
+        {
+            let text = &text[self.prefix.len()..];
+            let end = text.find(char::is_whitespace).unwrap_or(text.len());
+            return Ok((&text[..end], &text[end..]));
+        }
+
+        let start =
+            text.find(&platform_prefix).ok_or(ParseError::NotACommand)?;
+        let token = &text[start + platform_prefix.len()..];
+        let end = token.find('}').ok_or(ParseError::NotACommand)?;
+        Ok((token[..end].trim(), &token[end + 1..]))
+    }
+
+    /// Applies case-insensitivity and aliases to `name`.
+    fn resolve<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        let name = if self.case_insensitive {
+            Cow::Owned(name.to_lowercase())
+        } else {
+            Cow::Borrowed(name)
+        };
+
+        match self.aliases.get(name.as_ref()) {
+            Some(command) => Cow::Owned(command.clone()),
+            None => name,
+        }
+    }
+
+    /// Bot names are case-insensitive.
+    fn is_our_name(&self, name: &str) -> bool {
+        name.eq_ignore_ascii_case(&self.bot_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::User;
+
+    fn parse(parser: &CommandParser, text: &str) -> Result<String, ParseError> {
+        parser.parse(text).map(|command| command.name.into_owned())
+    }
+
+    #[test]
+    fn suffix_bot_name() {
+        let parser = CommandParser::new("MyBot");
+
+        assert_eq!(parse(&parser, "/start@mybot"), Ok("start".to_owned()));
+        assert_eq!(
+            parse(&parser, "/start@OtherBot"),
+            Err(ParseError::WrongBotName("OtherBot".to_owned()))
+        );
+        assert_eq!(parse(&parser, "start"), Err(ParseError::NotACommand));
+        assert_eq!(parse(&parser, "/ start"), Err(ParseError::NotACommand));
+    }
+
+    #[test]
+    fn platform_syntax() {
+        let parser = CommandParser::new("MyBot");
+
+        let command = parser.parse("Do ${/roll@MyBot} 1 6").unwrap();
+        assert_eq!(command.name, "roll");
+        assert_eq!(command.args.parse::<(u8, u8)>(), Ok((1, 6)));
+
+        assert_eq!(parse(&parser, "${/start"), Err(ParseError::NotACommand));
+        assert_eq!(
+            parse(&parser, "${/start@OtherBot}"),
+            Err(ParseError::WrongBotName("OtherBot".to_owned()))
+        );
+
+        // An embedded token is an argument of a leading command.
+        let mut command = parser.parse("/say hi ${/ban}").unwrap();
+        assert_eq!(command.name, "say");
+        assert_eq!(command.args.rest().trim(), "hi ${/ban}");
+        assert_eq!(parse(&parser, "${/ban} /say"), Ok("ban".to_owned()));
+    }
+
+    #[test]
+    fn case_and_aliases() {
+        let parser = CommandParser::new("")
+            .alias("Ban", "kick")
+            .case_insensitive()
+            .alias("HI", "start");
+
+        assert_eq!(parse(&parser, "/BAN"), Ok("kick".to_owned()));
+        assert_eq!(parse(&parser, "/hi"), Ok("start".to_owned()));
+        assert_eq!(parse(&parser, "/Help"), Ok("help".to_owned()));
+
+        let parser = CommandParser::new("").alias("hi", "start");
+        assert_eq!(parse(&parser, "/Hi"), Ok("Hi".to_owned()));
+    }
+
+    #[test]
+    fn mentions() {
+        let parser = CommandParser::new("MyBot");
+        let mention =
+            |len| MessageEntity::new(MessageEntityKind::Mention, 0, len);
+
+        let command = parser
+            .parse_with_entities("@MyBot /ban 5h", &[mention(6)])
+            .unwrap();
+        assert_eq!(command.name, "ban");
+        assert_eq!(command.args.parse::<(String,)>(), Ok(("5h".to_owned(),)));
+
+        assert_eq!(
+            parser
+                .parse_with_entities("@OtherBot /ban", &[mention(9)])
+                .unwrap_err(),
+            ParseError::WrongBotName("OtherBot".to_owned())
+        );
+        assert_eq!(
+            parser
+                .parse_with_entities("@MyBot hello", &[mention(6)])
+                .unwrap_err(),
+            ParseError::NotACommand
+        );
+
+        let user = User {
+            id: 1,
+            is_bot: true,
+            first_name: "My bot".to_owned(),
+            username: Some("MyBot".to_owned()),
+            ..User::default()
+        };
+        let text_mention =
+            MessageEntity::new(MessageEntityKind::TextMention { user }, 0, 6);
+        let command = parser
+            .parse_with_entities("My bot /start", &[text_mention])
+            .unwrap();
+        assert_eq!(command.name, "start");
+    }
+}
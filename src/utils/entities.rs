@@ -0,0 +1,485 @@
+//! Common machinery for converting [`MessageEntity`]s into markup and back.
+//!
+//! See [`html::render_entities`], [`html::parse_entities`],
+//! [`markdown::render_entities`] and [`markdown::parse_entities`].
+//!
+//! [`MessageEntity`]: crate::types::MessageEntity
+//! [`html::render_entities`]: crate::utils::html::render_entities
+//! [`html::parse_entities`]: crate::utils::html::parse_entities
+//! [`markdown::render_entities`]: crate::utils::markdown::render_entities
+//! [`markdown::parse_entities`]: crate::utils::markdown::parse_entities
+
+use thiserror::Error;
+
+use crate::types::{MessageEntity, MessageEntityKind, User};
+
+/// An error occurred while parsing markup into entities.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum ParseError {
+    #[error("An entity started with `{token}` isn't closed")]
+    UnclosedEntity { token: String },
+
+    #[error("Unexpected closing tag `</{tag}>`")]
+    UnexpectedClosingTag { tag: String },
+
+    #[error("Unsupported tag `<{tag}>`")]
+    UnsupportedTag { tag: String },
+
+    #[error("The `<{tag}>` tag requires the `{attribute}` attribute")]
+    MissingAttribute { tag: String, attribute: &'static str },
+
+    #[error("Unsupported HTML entity `&{entity};`")]
+    UnsupportedHtmlEntity { entity: String },
+
+    #[error("Unexpected end of input")]
+    UnexpectedEnd,
+}
+
+/// A markup language entities are rendered into.
+pub(crate) trait Markup {
+    /// Escapes text outside of code entities.
+    fn escape(s: &str) -> String;
+
+    /// Escapes text inside of code entities.
+    fn escape_code(s: &str) -> String;
+
+    /// Returns the opening and the closing tokens of an entity, or `None` if
+    /// the entity can't be represented in this markup.
+    fn tokens(kind: &MessageEntityKind) -> Option<(String, String)>;
+
+    /// Appends `token` to `out`; `after_token` is `true` if the previous
+    /// thing appended was a token as well.
+    fn push_token(out: &mut String, token: &str, after_token: bool) {
+        let _ = after_token;
+        out.push_str(token);
+    }
+}
+
+/// An entity converted into a range of bytes of the text.
+struct Span {
+    start: usize,
+    end: usize,
+    open: String,
+    close: String,
+    is_code: bool,
+}
+
+/// Renders `text` with `entities` into markup `M`.
+///
+/// Entities that don't fit into the text, empty entities and entities that
+/// can't be represented in `M` are skipped. Overlapping entities are split so
+/// that the result is properly nested.
+pub(crate) fn render<M>(text: &str, entities: &[MessageEntity]) -> String
+where
+    M: Markup,
+{
+    let mut spans: Vec<Span> = entities
+        .iter()
+        .filter_map(|entity| {
+            let range = entity.byte_range(text)?;
+            if range.start == range.end {
+                return None;
+            }
+            let (open, close) = M::tokens(&entity.kind)?;
+            let is_code = matches!(
+                entity.kind,
+                MessageEntityKind::Code | MessageEntityKind::Pre { .. }
+            );
+            Some(Span {
+                start: range.start,
+                end: range.end,
+                open,
+                close,
+                is_code,
+            })
+        })
+        .collect();
+    // Outer entities go first.
+    spans.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+
+    let mut bounds: Vec<usize> = spans
+        .iter()
+        .flat_map(|span| vec![span.start, span.end])
+        .chain(vec![0, text.len()])
+        .collect();
+    bounds.sort();
+    bounds.dedup();
+
+    let mut out = String::with_capacity(text.len());
+    let mut stack: Vec<usize> = Vec::new();
+    let mut next = 0;
+    let mut after_token = false;
+
+    for (i, &pos) in bounds.iter().enumerate() {
+        // Close the entities ending here. Entities above them on the stack
+        // that continue further are closed too and then reopened.
+        let mut reopen = Vec::new();
+        while stack.iter().any(|&s| spans[s].end == pos) {
+            let s = stack.pop().expect("the stack isn't empty");
+            M::push_token(&mut out, &spans[s].close, after_token);
+            after_token = true;
+            if spans[s].end != pos {
+                reopen.push(s);
+            }
+        }
+        for s in reopen.into_iter().rev() {
+            M::push_token(&mut out, &spans[s].open, after_token);
+            stack.push(s);
+        }
+
+        while next < spans.len() && spans[next].start == pos {
+            M::push_token(&mut out, &spans[next].open, after_token);
+            after_token = true;
+            stack.push(next);
+            next += 1;
+        }
+
+        if let Some(&end) = bounds.get(i + 1) {
+            let segment = &text[pos..end];
+            if stack.iter().any(|&s| spans[s].is_code) {
+                out.push_str(&M::escape_code(segment));
+            } else {
+                out.push_str(&M::escape(segment));
+            }
+            after_token = false;
+        }
+    }
+
+    out
+}
+
+/// Returns the kind of an entity linking to `url`.
+///
+/// Links to `tg://user?id=<id>` are mentions of users.
+pub(crate) fn link_kind(url: String) -> MessageEntityKind {
+    let user_id = ["tg://user?id=", "tg://user/?id="]
+        .iter()
+        .find(|prefix| url.starts_with(*prefix))
+        .and_then(|prefix| url[prefix.len()..].parse().ok());
+
+    match user_id {
+        Some(id) => MessageEntityKind::TextMention {
+            user: User { id, ..User::default() },
+        },
+        None => MessageEntityKind::TextLink { url },
+    }
+}
+
+/// An entity which is opened, but not closed yet.
+struct OpenEntity {
+    token: String,
+    /// `None` for tokens that don't produce an entity.
+    kind: Option<MessageEntityKind>,
+    offset: usize,
+}
+
+/// A builder of text and entities used by parsers.
+#[derive(Default)]
+pub(crate) struct EntitiesBuilder {
+    text: String,
+    len_utf16: usize,
+    open: Vec<OpenEntity>,
+    entities: Vec<MessageEntity>,
+}
+
+impl EntitiesBuilder {
+    pub(crate) fn push_str(&mut self, s: &str) {
+        self.text.push_str(s);
+        self.len_utf16 += s.encode_utf16().count();
+    }
+
+    pub(crate) fn push_char(&mut self, c: char) {
+        self.text.push(c);
+        self.len_utf16 += c.len_utf16();
+    }
+
+    /// Opens an entity started with `token`.
+    pub(crate) fn open(
+        &mut self,
+        token: &str,
+        kind: Option<MessageEntityKind>,
+    ) {
+        self.open.push(OpenEntity {
+            token: token.to_owned(),
+            kind,
+            offset: self.len_utf16,
+        });
+    }
+
+    /// Returns `true` if an entity started with `token` is open.
+    pub(crate) fn is_open(&self, token: &str) -> bool {
+        self.open.iter().any(|e| e.token == token)
+    }
+
+    /// A length of the text built so far in UTF-16 code units.
+    pub(crate) fn len_utf16(&self) -> usize {
+        self.len_utf16
+    }
+
+    /// Returns the token and the offset of the innermost open entity.
+    pub(crate) fn last_open(&self) -> Option<(&str, usize)> {
+        self.open.last().map(|e| (e.token.as_str(), e.offset))
+    }
+
+    /// Replaces the kind of the innermost open entity.
+    pub(crate) fn set_last_kind(&mut self, kind: MessageEntityKind) {
+        if let Some(entity) = self.open.last_mut() {
+            entity.kind = Some(kind);
+        }
+    }
+
+    /// Closes the innermost entity started with `token`, setting its kind if
+    /// `kind` is `Some`.
+    ///
+    /// Returns `false` if there is no such entity.
+    pub(crate) fn close(
+        &mut self,
+        token: &str,
+        kind: Option<MessageEntityKind>,
+    ) -> bool {
+        let pos = match self.open.iter().rposition(|e| e.token == token) {
+            Some(pos) => pos,
+            None => return false,
+        };
+
+        let entity = self.open.remove(pos);
+        let length = self.len_utf16 - entity.offset;
+        if let Some(kind) = kind.or(entity.kind) {
+            if length > 0 {
+                self.entities.push(MessageEntity::new(
+                    kind,
+                    entity.offset,
+                    length,
+                ));
+            }
+        }
+        true
+    }
+
+    /// Closes the innermost open entity if it's started with `token`.
+    ///
+    /// Returns `false` if the innermost entity is started with another token
+    /// or there are no open entities.
+    pub(crate) fn close_last(&mut self, token: &str) -> bool {
+        match self.open.last() {
+            Some(entity) if entity.token == token => self.close(token, None),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn build(
+        mut self,
+    ) -> Result<(String, Vec<MessageEntity>), ParseError> {
+        if let Some(entity) = self.open.pop() {
+            return Err(ParseError::UnclosedEntity { token: entity.token });
+        }
+
+        // Outer entities go first.
+        self.entities.sort_by(|a, b| {
+            a.offset.cmp(&b.offset).then(b.length.cmp(&a.length))
+        });
+        Ok((self.text, self.entities))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+    use super::*;
+    use crate::utils::{html, markdown};
+
+    /// Characters random texts are made of: multi-byte, surrogate pairs and
+    /// characters special in HTML or Markdown V2.
+    const CHARS: &[char] = &[
+        'a', 'z', ' ', '\n', 'ф', 'я', '中', '文', '😀', '🦀', '<', '>', '&',
+        '"', '*', '_', '~', '`', '[', ']', '(', ')', '\\', '.', '!', '#',
+    ];
+
+    const KINDS: &[&str] =
+        &["b", "i", "u", "s", "code", "pre", "pre_lang", "link", "mention"];
+
+    fn kind(name: &str, rng: &mut StdRng) -> MessageEntityKind {
+        match name {
+            "b" => MessageEntityKind::Bold,
+            "i" => MessageEntityKind::Italic,
+            "u" => MessageEntityKind::Underline,
+            "s" => MessageEntityKind::Strikethrough,
+            "code" => MessageEntityKind::Code,
+            "pre" => MessageEntityKind::Pre { language: None },
+            "pre_lang" => {
+                MessageEntityKind::Pre { language: Some("rust".to_owned()) }
+            }
+            "link" => MessageEntityKind::TextLink {
+                url: ["https://example.com/a_(b)", "https://ex.com/?q=`\\&\""]
+                    .choose(rng)
+                    .unwrap()
+                    .to_string(),
+            },
+            _ => MessageEntityKind::TextMention {
+                user: User { id: rng.gen_range(1, 1000), ..User::default() },
+            },
+        }
+    }
+
+    /// Generates a random text with properly nested entities, the way the
+    /// platform produces them.
+    fn random_text(rng: &mut StdRng) -> (String, Vec<MessageEntity>) {
+        let len = rng.gen_range(0, 40);
+        let chars: Vec<char> =
+            (0..len).map(|_| *CHARS.choose(rng).unwrap()).collect();
+
+        // UTF-16 offsets of chars.
+        let mut offsets = vec![0];
+        for c in &chars {
+            offsets.push(offsets.last().unwrap() + c.len_utf16());
+        }
+
+        let mut entities = Vec::new();
+        random_entities(rng, &offsets, 0, len, &[], &mut entities);
+        (chars.into_iter().collect(), entities)
+    }
+
+    fn random_entities(
+        rng: &mut StdRng,
+        offsets: &[usize],
+        start: usize,
+        end: usize,
+        outer: &[&str],
+        entities: &mut Vec<MessageEntity>,
+    ) {
+        let candidates: Vec<&str> = KINDS
+            .iter()
+            .copied()
+            .filter(|name| !outer.contains(name))
+            .filter(|name| {
+                let is_link = *name == "link" || *name == "mention";
+                !(is_link
+                    && (outer.contains(&"link") || outer.contains(&"mention")))
+            })
+            .collect();
+        if outer.len() >= 3 || candidates.is_empty() {
+            return;
+        }
+
+        let mut pos = start;
+        while pos < end {
+            let len = rng.gen_range(1, end - pos + 1);
+            if rng.gen_bool(0.5) {
+                let name = *candidates.choose(rng).unwrap();
+                entities.push(MessageEntity::new(
+                    kind(name, rng),
+                    offsets[pos],
+                    offsets[pos + len] - offsets[pos],
+                ));
+
+                // Code can't contain other entities.
+                if !name.starts_with("code") && !name.starts_with("pre") {
+                    let mut outer = outer.to_vec();
+                    outer.push(name);
+                    random_entities(
+                        rng,
+                        offsets,
+                        pos,
+                        pos + len,
+                        &outer,
+                        entities,
+                    );
+                }
+            }
+            pos += len;
+        }
+    }
+
+    fn sorted(
+        (text, mut entities): (String, Vec<MessageEntity>),
+    ) -> (String, Vec<MessageEntity>) {
+        entities.sort_by_key(|e| {
+            (e.offset, std::cmp::Reverse(e.length), format!("{:?}", e.kind))
+        });
+        (text, entities)
+    }
+
+    #[test]
+    fn text_in_matches_chars() {
+        for seed in 0..500 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let (text, _) = random_text(&mut rng);
+            let chars: Vec<char> = text.chars().collect();
+
+            let start = rng.gen_range(0, chars.len() + 1);
+            let end = rng.gen_range(start, chars.len() + 1);
+            let offset: usize =
+                chars[..start].iter().map(|c| c.len_utf16()).sum();
+            let length: usize =
+                chars[start..end].iter().map(|c| c.len_utf16()).sum();
+
+            let entity =
+                MessageEntity::new(MessageEntityKind::Bold, offset, length);
+            let expected: String = chars[start..end].iter().collect();
+            assert_eq!(entity.text_in(&text), Some(expected.as_str()));
+        }
+    }
+
+    #[test]
+    fn html_round_trip() {
+        for seed in 0..1000 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let (text, entities) = random_text(&mut rng);
+
+            let rendered = html::render_entities(&text, &entities);
+            let parsed = html::parse_entities(&rendered)
+                .unwrap_or_else(|err| panic!("{}: {:?}", err, rendered));
+            assert_eq!(
+                sorted(parsed),
+                sorted((text, entities)),
+                "{:?}",
+                rendered
+            );
+        }
+    }
+
+    #[test]
+    fn markdown_round_trip() {
+        for seed in 0..1000 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let (text, entities) = random_text(&mut rng);
+
+            let rendered = markdown::render_entities(&text, &entities);
+            let parsed = markdown::parse_entities(&rendered)
+                .unwrap_or_else(|err| panic!("{}: {:?}", err, rendered));
+            assert_eq!(
+                sorted(parsed),
+                sorted((text, entities)),
+                "{:?}",
+                rendered
+            );
+        }
+    }
+
+    #[test]
+    fn overlapping_entities_are_split() {
+        let entities = [
+            MessageEntity::new(MessageEntityKind::Bold, 0, 4),
+            MessageEntity::new(MessageEntityKind::Italic, 2, 4),
+        ];
+
+        assert_eq!(
+            html::render_entities("ab😀ef", &entities),
+            "<b>ab<i>😀</i></b><i>ef</i>"
+        );
+    }
+
+    #[test]
+    fn unrepresentable_entities_are_skipped() {
+        let entities = [
+            MessageEntity::new(MessageEntityKind::Mention, 0, 4),
+            MessageEntity::new(MessageEntityKind::Bold, 5, 2),
+        ];
+
+        assert_eq!(
+            markdown::render_entities("@bob 中文", &entities),
+            "@bob *中文*"
+        );
+    }
+}
@@ -0,0 +1,259 @@
+//! Reusing IDs of uploaded files.
+//!
+//! Every time a bot sends a file from a disk or a memory, the file is
+//! uploaded again, although the platform returns an ID of the uploaded file
+//! which can be sent instead. A [`FileIdCache`] set with
+//! [`Bot::with_file_id_cache`] remembers these IDs and substitutes them for
+//! files which have already been uploaded.
+//!
+//! Files on a disk are recognized by their paths, sizes and modification
+//! times, files in a memory are recognized by their contents. Streams,
+//! URLs and file IDs are sent as is.
+//!
+//! ## Examples
+//! ```no_run
+//! use teloxide::{
+//!     prelude::*, types::InputFile, utils::file_id_cache::FileIdCache,
+//! };
+//!
+//! # async fn run(chat_id: i64) -> ResponseResult<()> {
+//! let bot = Bot::from_env().with_file_id_cache(FileIdCache::in_memory());
+//!
+//! // The first photo is uploaded, the second one is sent by its ID.
+//! for _ in 0..2 {
+//!     bot.send_photo(chat_id, InputFile::file("cat.png")).send().await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Bot::with_file_id_cache`]: crate::Bot::with_file_id_cache
+
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::UNIX_EPOCH,
+};
+
+use crate::types::{InputFile, Message};
+
+/// A storage of IDs of uploaded files.
+///
+/// Implement it to keep IDs between restarts of a bot, e.g. in a database.
+/// For a storage based on a simple hash map, see [`InMemFileIdStorage`].
+pub trait FileIdStorage: Send + Sync {
+    /// Loads an ID of a file stored with `key`.
+    fn load(&self, key: &str) -> Option<String>;
+
+    /// Stores an ID of a file with `key`.
+    fn store(&self, key: String, file_id: String);
+
+    /// Removes an ID of a file which is rejected by the platform.
+    fn remove(&self, key: &str);
+}
+
+/// A memory storage of IDs of uploaded files.
+///
+/// ## Note
+/// All the IDs will be lost after you restart your bot, so all the files will
+/// be uploaded again.
+#[derive(Debug, Default)]
+pub struct InMemFileIdStorage {
+    file_ids: Mutex<HashMap<String, String>>,
+}
+
+impl InMemFileIdStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl FileIdStorage for InMemFileIdStorage {
+    fn load(&self, key: &str) -> Option<String> {
+        self.file_ids.lock().unwrap().get(key).cloned()
+    }
+
+    fn store(&self, key: String, file_id: String) {
+        self.file_ids.lock().unwrap().insert(key, file_id);
+    }
+
+    fn remove(&self, key: &str) {
+        self.file_ids.lock().unwrap().remove(key);
+    }
+}
+
+/// A cache of IDs of uploaded files, see the [module-level docs].
+///
+/// [module-level docs]: crate::utils::file_id_cache
+#[derive(Clone)]
+pub struct FileIdCache {
+    storage: Arc<dyn FileIdStorage>,
+}
+
+impl FileIdCache {
+    pub fn new<S>(storage: S) -> Self
+    where
+        S: FileIdStorage + 'static,
+    {
+        Self { storage: Arc::new(storage) }
+    }
+
+    /// A cache with [`InMemFileIdStorage`].
+    pub fn in_memory() -> Self {
+        Self::new(InMemFileIdStorage::new())
+    }
+
+    /// Loads an ID of `file` uploaded as `kind`, returning it together with
+    /// a key of the file.
+    ///
+    /// Returns `None` if the file cannot be cached.
+    pub(crate) async fn lookup(
+        &self,
+        kind: FileKind,
+        file: &InputFile,
+    ) -> Option<(String, Option<String>)> {
+        let key = file_key(kind, file).await?;
+        let file_id = self.storage.load(&key);
+        Some((key, file_id))
+    }
+
+    /// Stores an ID of a file uploaded as `kind` with `message`.
+    pub(crate) fn remember(
+        &self,
+        kind: FileKind,
+        key: String,
+        message: &Message,
+    ) {
+        if let Some(file_id) = kind.file_id(message) {
+            self.storage.store(key, file_id.to_owned());
+        }
+    }
+
+    pub(crate) fn forget(&self, key: &str) {
+        self.storage.remove(key);
+    }
+}
+
+impl fmt::Debug for FileIdCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FileIdCache")
+    }
+}
+
+/// How a file is sent.
+///
+/// The same file sent as a photo and as a document has different IDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum FileKind {
+    Animation,
+    Audio,
+    Document,
+    Photo,
+    Sticker,
+    Video,
+    VideoNote,
+    Voice,
+}
+
+impl FileKind {
+    fn name(self) -> &'static str {
+        match self {
+            FileKind::Animation => "animation",
+            FileKind::Audio => "audio",
+            FileKind::Document => "document",
+            FileKind::Photo => "photo",
+            FileKind::Sticker => "sticker",
+            FileKind::Video => "video",
+            FileKind::VideoNote => "video_note",
+            FileKind::Voice => "voice",
+        }
+    }
+
+    /// An ID of a file of this kind in `message`.
+    fn file_id(self, message: &Message) -> Option<&str> {
+        let file_id = match self {
+            FileKind::Animation => &message.animation()?.file_id,
+            FileKind::Audio => &message.audio()?.file_id,
+            FileKind::Document => &message.document()?.file_id,
+            // The last photo is the original one, the others are thumbnails.
+            FileKind::Photo => &message.photo()?.last()?.file_id,
+            FileKind::Sticker => &message.sticker()?.file_id,
+            FileKind::Video => &message.video()?.file_id,
+            FileKind::VideoNote => &message.video_note()?.file_id,
+            FileKind::Voice => &message.voice()?.file_id,
+        };
+
+        Some(file_id)
+    }
+}
+
+/// A key of `file` sent as `kind`, or `None` if the file cannot be cached.
+async fn file_key(kind: FileKind, file: &InputFile) -> Option<String> {
+    let key = match file {
+        InputFile::File(path) => {
+            let path = tokio::fs::canonicalize(path).await.ok()?;
+            let metadata = tokio::fs::metadata(&path).await.ok()?;
+            let modified =
+                metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+            format!(
+                "path:{}:{}:{}",
+                metadata.len(),
+                modified.as_nanos(),
+                path.display()
+            )
+        }
+        InputFile::Memory { data, .. } => content_key(data),
+        InputFile::Bytes { data, .. } => content_key(data),
+        InputFile::Url(_) | InputFile::FileId(_) | InputFile::Stream { .. } => {
+            return None
+        }
+    };
+
+    Some(format!("{}:{}", kind.name(), key))
+}
+
+/// A key of a file with `data`, which is stable between restarts of a bot.
+fn content_key(data: &[u8]) -> String {
+    let hash: String = Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("content:{}:{}", data.len(), hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn keys() {
+        let memory = InputFile::memory("a.png", &b"abc"[..]);
+        let bytes = InputFile::bytes("b.png", &b"abc"[..]);
+        let photo = file_key(FileKind::Photo, &memory).await.unwrap();
+
+        assert_eq!(
+            photo,
+            "photo:content:3:\
+             ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(file_key(FileKind::Photo, &bytes).await.unwrap(), photo);
+        assert_ne!(file_key(FileKind::Document, &memory).await.unwrap(), photo);
+
+        let url = InputFile::url("https://example.com/a.png");
+        assert_eq!(file_key(FileKind::Photo, &url).await, None);
+        let missing = InputFile::file("/nonexistent/a.png");
+        assert_eq!(file_key(FileKind::Photo, &missing).await, None);
+    }
+
+    #[test]
+    fn storage() {
+        let storage = InMemFileIdStorage::new();
+        storage.store("key".to_owned(), "id".to_owned());
+        assert_eq!(storage.load("key"), Some("id".to_owned()));
+
+        storage.remove("key");
+        assert_eq!(storage.load("key"), None);
+    }
+}
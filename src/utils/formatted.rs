@@ -0,0 +1,59 @@
+//! Formatted text bound to its parse mode.
+//!
+//! [`utils::html`] and [`utils::markdown`] work with raw strings, so it's easy
+//! to forget to escape user input, to escape it twice or to send HTML with
+//! [`ParseMode::MarkdownV2`]. A [`FormattedText`] is rendered from a
+//! [`TextBuilder`], which escapes the text automatically, and carries its
+//! parse mode, which is set together with the text by
+//! [`SendMessage::formatted`], [`SendPhoto::caption_formatted`] and similar
+//! methods.
+//!
+//! ## Examples
+//! ```no_run
+//! use teloxide::{prelude::*, utils::text_builder::TextBuilder};
+//!
+//! # async fn run(bot: std::sync::Arc<Bot>, name: &str) {
+//! let text = TextBuilder::new().plain("Hello, ").bold(name).html();
+//! bot.send_message(0, "").formatted(text).send().await;
+//! # }
+//! ```
+//!
+//! [`utils::html`]: crate::utils::html
+//! [`utils::markdown`]: crate::utils::markdown
+//! [`ParseMode::MarkdownV2`]: crate::types::ParseMode::MarkdownV2
+//! [`TextBuilder`]: crate::utils::text_builder::TextBuilder
+//! [`SendMessage::formatted`]: crate::requests::SendMessage::formatted
+//! [`SendPhoto::caption_formatted`]:
+//! crate::requests::SendPhoto::caption_formatted
+
+use crate::types::ParseMode;
+
+/// A text rendered by [`TextBuilder::html`] or [`TextBuilder::markdown_v2`]
+/// together with its parse mode.
+///
+/// [`TextBuilder::html`]: crate::utils::text_builder::TextBuilder::html
+/// [`TextBuilder::markdown_v2`]:
+/// crate::utils::text_builder::TextBuilder::markdown_v2
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct FormattedText {
+    text: String,
+    parse_mode: ParseMode,
+}
+
+impl FormattedText {
+    pub(crate) fn new(text: String, parse_mode: ParseMode) -> Self {
+        Self { text, parse_mode }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn parse_mode(&self) -> ParseMode {
+        self.parse_mode
+    }
+
+    pub fn into_parts(self) -> (String, ParseMode) {
+        (self.text, self.parse_mode)
+    }
+}
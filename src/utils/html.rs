@@ -1,7 +1,10 @@
 //! Utils for working with the [HTML message style][spec].
 //!
 //! [spec]: https://core.telegram.org/bots/api#html-style
-use crate::types::User;
+use crate::{
+    types::{MessageEntity, MessageEntityKind, User},
+    utils::entities::{self, EntitiesBuilder, Markup, ParseError},
+};
 use std::string::String;
 
 /// Applies the bold font style to the string.
@@ -91,6 +94,241 @@ pub fn user_mention_or_link(user: &User) -> String {
     }
 }
 
+/// Renders `text` with `entities` into the HTML message style.
+///
+/// Entities that can't be represented in HTML (mentions, hashtags, etc.) are
+/// skipped, overlapping entities are split.
+pub fn render_entities(text: &str, entities: &[MessageEntity]) -> String {
+    entities::render::<Html>(text, entities)
+}
+
+/// Parses a text in the HTML message style into a plain text and its
+/// entities.
+///
+/// This is the inverse of [`render_entities`].
+///
+/// [`render_entities`]: crate::utils::html::render_entities
+pub fn parse_entities(
+    html: &str,
+) -> Result<(String, Vec<MessageEntity>), ParseError> {
+    let mut builder = EntitiesBuilder::default();
+    let mut rest = html;
+
+    while let Some(c) = rest.chars().next() {
+        match c {
+            '<' => {
+                let end = rest.find('>').ok_or(ParseError::UnexpectedEnd)?;
+                parse_tag(&mut builder, &rest[1..end])?;
+                rest = &rest[end + 1..];
+            }
+            '&' => {
+                let end = rest.find(';').ok_or(ParseError::UnexpectedEnd)?;
+                builder.push_char(parse_html_entity(&rest[1..end])?);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                let end = rest.find(['<', '&']).unwrap_or(rest.len());
+                builder.push_str(&rest[..end]);
+                rest = &rest[end..];
+            }
+        }
+    }
+
+    builder.build()
+}
+
+struct Html;
+
+impl Markup for Html {
+    fn escape(s: &str) -> String {
+        escape(s)
+    }
+
+    fn escape_code(s: &str) -> String {
+        escape(s)
+    }
+
+    fn tokens(kind: &MessageEntityKind) -> Option<(String, String)> {
+        let tag = |name: &str| (format!("<{}>", name), format!("</{}>", name));
+        let link = |url: &str| {
+            (
+                format!("<a href=\"{}\">", escape(url).replace("\"", "&quot;")),
+                "</a>".to_owned(),
+            )
+        };
+
+        match kind {
+            MessageEntityKind::Bold => Some(tag("b")),
+            MessageEntityKind::Italic => Some(tag("i")),
+            MessageEntityKind::Underline => Some(tag("u")),
+            MessageEntityKind::Strikethrough => Some(tag("s")),
+            MessageEntityKind::Code => Some(tag("code")),
+            MessageEntityKind::Pre { language: None } => Some(tag("pre")),
+            MessageEntityKind::Pre { language: Some(language) } => Some((
+                format!(
+                    "<pre><code class=\"language-{}\">",
+                    escape(language).replace("\"", "&quot;")
+                ),
+                "</code></pre>".to_owned(),
+            )),
+            MessageEntityKind::TextLink { url } => Some(link(url)),
+            MessageEntityKind::TextMention { user } => {
+                Some(link(&format!("tg://user?id={}", user.id)))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Returns the canonical name of a supported tag.
+fn canonical_tag(name: &str) -> Option<&'static str> {
+    match name {
+        "b" | "strong" => Some("b"),
+        "i" | "em" => Some("i"),
+        "u" | "ins" => Some("u"),
+        "s" | "strike" | "del" => Some("s"),
+        "code" => Some("code"),
+        "pre" => Some("pre"),
+        "a" => Some("a"),
+        _ => None,
+    }
+}
+
+/// Handles a tag without the angle brackets.
+fn parse_tag(
+    builder: &mut EntitiesBuilder,
+    tag: &str,
+) -> Result<(), ParseError> {
+    if let Some(name) = tag.strip_prefix('/') {
+        let name = name.trim().to_lowercase();
+        return match canonical_tag(&name) {
+            Some(token) if builder.close_last(token) => Ok(()),
+            Some(_) => Err(ParseError::UnexpectedClosingTag { tag: name }),
+            None => Err(ParseError::UnsupportedTag { tag: name }),
+        };
+    }
+
+    let (name, attributes) = match tag.find(char::is_whitespace) {
+        Some(pos) => (&tag[..pos], &tag[pos..]),
+        None => (tag, ""),
+    };
+    let name = name.to_lowercase();
+    let token = canonical_tag(&name)
+        .ok_or_else(|| ParseError::UnsupportedTag { tag: name.clone() })?;
+
+    let kind = match token {
+        "b" => MessageEntityKind::Bold,
+        "i" => MessageEntityKind::Italic,
+        "u" => MessageEntityKind::Underline,
+        "s" => MessageEntityKind::Strikethrough,
+        "pre" => MessageEntityKind::Pre { language: None },
+        "code" => match builder.last_open() {
+            // `<pre><code class="language-...">` is a code block with a
+            // language.
+            Some(("pre", offset)) if offset == builder.len_utf16() => {
+                let language = attribute(attributes, "class")?
+                    .filter(|class| class.starts_with("language-"))
+                    .map(|class| class["language-".len()..].to_owned());
+                if language.is_some() {
+                    builder.set_last_kind(MessageEntityKind::Pre { language });
+                }
+                builder.open(token, None);
+                return Ok(());
+            }
+            _ => MessageEntityKind::Code,
+        },
+        _ => {
+            let href = attribute(attributes, "href")?.ok_or(
+                ParseError::MissingAttribute { tag: name, attribute: "href" },
+            )?;
+            entities::link_kind(href)
+        }
+    };
+
+    builder.open(token, Some(kind));
+    Ok(())
+}
+
+/// Returns the unescaped value of the attribute `name`.
+fn attribute(
+    attributes: &str,
+    name: &str,
+) -> Result<Option<String>, ParseError> {
+    let mut rest = attributes.trim_start();
+
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = &rest[..key_end];
+        rest = rest[key_end..].trim_start();
+
+        let mut value = "";
+        if rest.starts_with('=') {
+            rest = rest[1..].trim_start();
+            let (start, end) = match rest.chars().next() {
+                Some(quote @ '"') | Some(quote @ '\'') => {
+                    let end = rest[1..]
+                        .find(quote)
+                        .ok_or(ParseError::UnexpectedEnd)?;
+                    (1, end + 1)
+                }
+                _ => (0, rest.find(char::is_whitespace).unwrap_or(rest.len())),
+            };
+            value = &rest[start..end];
+            rest = rest[(end + start).min(rest.len())..].trim_start();
+        }
+
+        if key.eq_ignore_ascii_case(name) {
+            return unescape(value).map(Some);
+        }
+    }
+
+    Ok(None)
+}
+
+/// Replaces HTML entities in `s` with the corresponding characters.
+fn unescape(s: &str) -> Result<String, ParseError> {
+    let mut res = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        res.push_str(&rest[..start]);
+        let end = rest[start..].find(';').ok_or(ParseError::UnexpectedEnd)?;
+        res.push(parse_html_entity(&rest[start + 1..start + end])?);
+        rest = &rest[start + end + 1..];
+    }
+
+    res.push_str(rest);
+    Ok(res)
+}
+
+/// Parses an HTML entity without `&` and `;`.
+fn parse_html_entity(entity: &str) -> Result<char, ParseError> {
+    let code = if let Some(hex) =
+        entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X"))
+    {
+        u32::from_str_radix(hex, 16).ok()
+    } else if let Some(decimal) = entity.strip_prefix('#') {
+        decimal.parse().ok()
+    } else {
+        None
+    };
+
+    match (entity, code) {
+        ("lt", _) => Ok('<'),
+        ("gt", _) => Ok('>'),
+        ("amp", _) => Ok('&'),
+        ("quot", _) => Ok('"'),
+        (_, Some(code)) => std::char::from_u32(code).ok_or_else(|| {
+            ParseError::UnsupportedHtmlEntity { entity: entity.to_owned() }
+        }),
+        _ => {
+            Err(ParseError::UnsupportedHtmlEntity { entity: entity.to_owned() })
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -195,6 +433,7 @@ mod tests {
             last_name: None,
             username: Some("abcd".to_string()),
             language_code: None,
+            ..User::default()
         };
         assert_eq!(user_mention_or_link(&user_with_username), "@abcd");
         let user_without_username = User {
@@ -204,10 +443,68 @@ mod tests {
             last_name: None,
             username: None,
             language_code: None,
+            ..User::default()
         };
         assert_eq!(
             user_mention_or_link(&user_without_username),
             r#"<a href="tg://user/?id=123456789">Name</a>"#
         )
     }
+
+    #[test]
+    fn test_parse_entities() {
+        let (text, entities) = parse_entities(concat!(
+            "<strong>中文</strong> &lt;&#128512;&gt; ",
+            "<pre><code class=\"language-rust\">fn</code></pre> ",
+            "<a href='tg://user?id=42'>bob</a>",
+        ))
+        .unwrap();
+
+        assert_eq!(text, "中文 <😀> fn bob");
+        assert_eq!(
+            entities,
+            vec![
+                MessageEntity::new(MessageEntityKind::Bold, 0, 2),
+                MessageEntity::new(
+                    MessageEntityKind::Pre { language: Some("rust".into()) },
+                    8,
+                    2
+                ),
+                MessageEntity::new(
+                    MessageEntityKind::TextMention {
+                        user: User { id: 42, ..User::default() }
+                    },
+                    11,
+                    3
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_entities_errors() {
+        assert_eq!(
+            parse_entities("<b>foo"),
+            Err(ParseError::UnclosedEntity { token: "b".into() })
+        );
+        assert_eq!(
+            parse_entities("<b><i>foo</b></i>"),
+            Err(ParseError::UnexpectedClosingTag { tag: "b".into() })
+        );
+        assert_eq!(
+            parse_entities("<span>foo</span>"),
+            Err(ParseError::UnsupportedTag { tag: "span".into() })
+        );
+        assert_eq!(
+            parse_entities("<a>foo</a>"),
+            Err(ParseError::MissingAttribute {
+                tag: "a".into(),
+                attribute: "href"
+            })
+        );
+        assert_eq!(
+            parse_entities("foo&nbsp;bar"),
+            Err(ParseError::UnsupportedHtmlEntity { entity: "nbsp".into() })
+        );
+    }
 }
@@ -0,0 +1,391 @@
+//! Laying out buttons of inline and reply keyboards.
+//!
+//! ## Examples
+//! ```
+//! use teloxide::{
+//!     types::InlineKeyboardButton, utils::keyboard::KeyboardBuilder,
+//! };
+//!
+//! let markup = KeyboardBuilder::new()
+//!     .columns(2)
+//!     .callback("Members", "members")
+//!     .callback("Roles", "roles")
+//!     .callback("Channels", "channels")
+//!     .row(vec![InlineKeyboardButton::mini_program(
+//!         "Open the app".to_string(),
+//!         "app_id".to_string(),
+//!     )])
+//!     .build()
+//!     .unwrap();
+//!
+//! let rows: Vec<_> = markup
+//!     .inline_keyboard
+//!     .iter()
+//!     .map(|row| row.iter().map(|b| b.text.as_str()).collect::<Vec<_>>())
+//!     .collect();
+//! assert_eq!(
+//!     rows,
+//!     vec![vec!["Members", "Roles"], vec!["Channels"], vec!["Open the app"]]
+//! );
+//! ```
+
+use thiserror::Error;
+
+use crate::{
+    types::{
+        InlineKeyboardButton, InlineKeyboardButtonKind, InlineKeyboardMarkup,
+        KeyboardButton, ReplyKeyboardMarkup,
+    },
+    utils::callback_data::MAX_CALLBACK_DATA_LEN,
+};
+
+/// A button which can be laid out by [`KeyboardBuilder`].
+pub trait LayoutButton: Sized {
+    /// A keyboard made of the buttons.
+    type Markup;
+
+    /// The maximum number of buttons in a row.
+    const MAX_BUTTONS_PER_ROW: usize;
+
+    /// The maximum number of buttons in a keyboard.
+    const MAX_BUTTONS: usize;
+
+    /// Checks the button, `row` and `column` are used for error reporting.
+    fn validate(&self, row: usize, column: usize) -> Result<(), KeyboardError>;
+
+    /// Creates a keyboard of `rows`.
+    fn into_markup(rows: Vec<Vec<Self>>) -> Self::Markup;
+}
+
+impl LayoutButton for InlineKeyboardButton {
+    type Markup = InlineKeyboardMarkup;
+
+    const MAX_BUTTONS_PER_ROW: usize = 8;
+    const MAX_BUTTONS: usize = 100;
+
+    fn validate(&self, row: usize, column: usize) -> Result<(), KeyboardError> {
+        check_text(&self.text, row, column)?;
+
+        match &self.kind {
+            InlineKeyboardButtonKind::CallbackData(data)
+                if data.is_empty() || data.len() > MAX_CALLBACK_DATA_LEN =>
+            {
+                Err(KeyboardError::InvalidCallbackData {
+                    row,
+                    column,
+                    len: data.len(),
+                })
+            }
+            InlineKeyboardButtonKind::MiniProgram(app_id)
+                if app_id.is_empty() =>
+            {
+                Err(KeyboardError::EmptyAppId { row, column })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn into_markup(rows: Vec<Vec<Self>>) -> InlineKeyboardMarkup {
+        InlineKeyboardMarkup { inline_keyboard: rows }
+    }
+}
+
+impl LayoutButton for KeyboardButton {
+    type Markup = ReplyKeyboardMarkup;
+
+    const MAX_BUTTONS_PER_ROW: usize = 12;
+    const MAX_BUTTONS: usize = 300;
+
+    fn validate(&self, row: usize, column: usize) -> Result<(), KeyboardError> {
+        check_text(&self.text, row, column)
+    }
+
+    fn into_markup(rows: Vec<Vec<Self>>) -> ReplyKeyboardMarkup {
+        ReplyKeyboardMarkup { keyboard: rows, ..ReplyKeyboardMarkup::default() }
+    }
+}
+
+/// An error returned by [`KeyboardBuilder::build`].
+///
+/// Rows and columns are counted from 0.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum KeyboardError {
+    #[error("Row #{row} has {count} buttons, the maximum is {max}")]
+    TooManyButtonsInRow { row: usize, count: usize, max: usize },
+
+    #[error("The keyboard has {count} buttons, the maximum is {max}")]
+    TooManyButtons { count: usize, max: usize },
+
+    #[error("The text of the button at ({row}, {column}) is empty")]
+    EmptyText { row: usize, column: usize },
+
+    #[error(
+        "The callback data of the button at ({row}, {column}) is {len} bytes \
+         long, it must be 1-{max} bytes",
+        max = MAX_CALLBACK_DATA_LEN
+    )]
+    InvalidCallbackData { row: usize, column: usize, len: usize },
+
+    #[error("The app ID of the button at ({row}, {column}) is empty")]
+    EmptyAppId { row: usize, column: usize },
+}
+
+/// A builder arranging a flat list of buttons into rows.
+///
+/// Buttons added with [`button`] fill rows of [`columns`] buttons, while
+/// [`row`] adds a row as is.
+///
+/// [`button`]: KeyboardBuilder::button
+/// [`columns`]: KeyboardBuilder::columns
+/// [`row`]: KeyboardBuilder::row
+#[derive(Clone, Debug)]
+pub struct KeyboardBuilder<B> {
+    rows: Vec<Vec<B>>,
+    columns: usize,
+
+    /// `true` if the last row is filled by [`KeyboardBuilder::button`].
+    filling: bool,
+}
+
+impl<B> Default for KeyboardBuilder<B> {
+    fn default() -> Self {
+        Self { rows: Vec::new(), columns: 1, filling: false }
+    }
+}
+
+impl<B> KeyboardBuilder<B>
+where
+    B: LayoutButton,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A number of buttons in rows filled by [`button`], at least 1.
+    ///
+    /// Affects only buttons added after the call.
+    ///
+    /// [`button`]: KeyboardBuilder::button
+    pub fn columns(mut self, val: usize) -> Self {
+        self.columns = val.max(1);
+        self
+    }
+
+    /// Adds a button to the last row or to a new row if the last one is full.
+    pub fn button(mut self, button: B) -> Self {
+        match self.rows.last_mut() {
+            Some(row) if self.filling && row.len() < self.columns => {
+                row.push(button)
+            }
+            _ => {
+                self.rows.push(vec![button]);
+                self.filling = true;
+            }
+        }
+        self
+    }
+
+    /// Adds buttons one by one, see [`button`].
+    ///
+    /// [`button`]: KeyboardBuilder::button
+    pub fn buttons<I>(self, buttons: I) -> Self
+    where
+        I: IntoIterator<Item = B>,
+    {
+        buttons.into_iter().fold(self, Self::button)
+    }
+
+    /// Adds a row as is. The next button will start a new row.
+    pub fn row(mut self, buttons: Vec<B>) -> Self {
+        self.rows.push(buttons);
+        self.filling = false;
+        self
+    }
+
+    /// Checks the limits and builds a keyboard.
+    pub fn build(self) -> Result<B::Markup, KeyboardError> {
+        let mut count = 0;
+        for (i, row) in self.rows.iter().enumerate() {
+            if row.len() > B::MAX_BUTTONS_PER_ROW {
+                return Err(KeyboardError::TooManyButtonsInRow {
+                    row: i,
+                    count: row.len(),
+                    max: B::MAX_BUTTONS_PER_ROW,
+                });
+            }
+            for (j, button) in row.iter().enumerate() {
+                button.validate(i, j)?;
+            }
+            count += row.len();
+        }
+
+        if count > B::MAX_BUTTONS {
+            return Err(KeyboardError::TooManyButtons {
+                count,
+                max: B::MAX_BUTTONS,
+            });
+        }
+
+        let rows = self.rows.into_iter().filter(|row| !row.is_empty());
+        Ok(B::into_markup(rows.collect()))
+    }
+}
+
+impl KeyboardBuilder<InlineKeyboardButton> {
+    /// Adds a callback button, see [`button`].
+    ///
+    /// [`button`]: KeyboardBuilder::button
+    pub fn callback<T, D>(self, text: T, data: D) -> Self
+    where
+        T: Into<String>,
+        D: Into<String>,
+    {
+        self.button(InlineKeyboardButton::callback(text.into(), data.into()))
+    }
+
+    /// Adds a URL button, see [`button`].
+    ///
+    /// [`button`]: KeyboardBuilder::button
+    pub fn url<T, U>(self, text: T, url: U) -> Self
+    where
+        T: Into<String>,
+        U: Into<String>,
+    {
+        self.button(InlineKeyboardButton::url(text.into(), url.into()))
+    }
+
+    /// Adds a button opening a mini program, see [`button`].
+    ///
+    /// [`button`]: KeyboardBuilder::button
+    pub fn mini_program<T, A>(self, text: T, app_id: A) -> Self
+    where
+        T: Into<String>,
+        A: Into<String>,
+    {
+        self.button(InlineKeyboardButton::mini_program(
+            text.into(),
+            app_id.into(),
+        ))
+    }
+}
+
+impl KeyboardBuilder<KeyboardButton> {
+    /// Adds a text button, see [`button`].
+    ///
+    /// [`button`]: KeyboardBuilder::button
+    pub fn text<T>(self, text: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.button(KeyboardButton::new(text))
+    }
+}
+
+fn check_text(
+    text: &str,
+    row: usize,
+    column: usize,
+) -> Result<(), KeyboardError> {
+    if text.is_empty() {
+        Err(KeyboardError::EmptyText { row, column })
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns() {
+        let markup = KeyboardBuilder::new()
+            .columns(3)
+            .buttons((1..=7).map(|i| KeyboardButton::new(i.to_string())))
+            .row(vec![KeyboardButton::new("Cancel")])
+            .columns(2)
+            .text("a")
+            .text("b")
+            .build()
+            .unwrap();
+
+        let texts: Vec<Vec<_>> = markup
+            .keyboard
+            .iter()
+            .map(|row| row.iter().map(|b| b.text.as_str()).collect())
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                vec!["1", "2", "3"],
+                vec!["4", "5", "6"],
+                vec!["7"],
+                vec!["Cancel"],
+                vec!["a", "b"],
+            ]
+        );
+    }
+
+    #[test]
+    fn inline_limits() {
+        let too_wide = KeyboardBuilder::new()
+            .row(
+                (0..9)
+                    .map(|i| {
+                        InlineKeyboardButton::callback(
+                            i.to_string(),
+                            i.to_string(),
+                        )
+                    })
+                    .collect(),
+            )
+            .build();
+        assert_eq!(
+            too_wide,
+            Err(KeyboardError::TooManyButtonsInRow {
+                row: 0,
+                count: 9,
+                max: 8
+            })
+        );
+
+        let too_many = KeyboardBuilder::new()
+            .columns(8)
+            .buttons((0..101).map(|i| {
+                InlineKeyboardButton::callback(i.to_string(), i.to_string())
+            }))
+            .build();
+        assert_eq!(
+            too_many,
+            Err(KeyboardError::TooManyButtons { count: 101, max: 100 })
+        );
+    }
+
+    #[test]
+    fn inline_buttons() {
+        let long_data = KeyboardBuilder::new()
+            .callback("ok", "ok")
+            .callback("long", "x".repeat(65))
+            .build();
+        assert_eq!(
+            long_data,
+            Err(KeyboardError::InvalidCallbackData {
+                row: 1,
+                column: 0,
+                len: 65
+            })
+        );
+
+        let no_app_id = KeyboardBuilder::new().mini_program("app", "").build();
+        assert_eq!(
+            no_app_id,
+            Err(KeyboardError::EmptyAppId { row: 0, column: 0 })
+        );
+
+        let no_text = KeyboardBuilder::<KeyboardButton>::new().text("").build();
+        assert_eq!(
+            no_text,
+            Err(KeyboardError::EmptyText { row: 0, column: 0 })
+        );
+    }
+}
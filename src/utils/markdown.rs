@@ -1,7 +1,10 @@
 //! Utils for working with the [Markdown V2 message style][spec].
 //!
 //! [spec]: https://core.telegram.org/bots/api#markdownv2-style
-use crate::types::User;
+use crate::{
+    types::{MessageEntity, MessageEntityKind, User},
+    utils::entities::{self, EntitiesBuilder, Markup, ParseError},
+};
 use std::string::String;
 
 /// Applies the bold font style to the string.
@@ -128,6 +131,197 @@ pub fn user_mention_or_link(user: &User) -> String {
     }
 }
 
+/// Renders `text` with `entities` into the Markdown V2 message style.
+///
+/// Entities that can't be represented in Markdown V2 (mentions, hashtags,
+/// etc.) are skipped, overlapping entities are split.
+pub fn render_entities(text: &str, entities: &[MessageEntity]) -> String {
+    entities::render::<MarkdownV2>(text, entities)
+}
+
+/// Parses a text in the Markdown V2 message style into a plain text and its
+/// entities.
+///
+/// This is the inverse of [`render_entities`].
+///
+/// [`render_entities`]: crate::utils::markdown::render_entities
+pub fn parse_entities(
+    markdown: &str,
+) -> Result<(String, Vec<MessageEntity>), ParseError> {
+    let chars: Vec<char> = markdown.chars().collect();
+    let mut builder = EntitiesBuilder::default();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() => {
+                builder.push_char(chars[i + 1]);
+                i += 1;
+            }
+            // `\r` separates adjacent italic and underline tokens and is
+            // ignored.
+            '\r' if i > 0
+                && is_unescaped(&chars, i - 1, '_')
+                && chars.get(i + 1) == Some(&'_') => {}
+            '*' => toggle(&mut builder, "*", MessageEntityKind::Bold),
+            '~' => toggle(&mut builder, "~", MessageEntityKind::Strikethrough),
+            '_' if chars.get(i + 1) == Some(&'_') => {
+                toggle(&mut builder, "__", MessageEntityKind::Underline);
+                i += 1;
+            }
+            '_' => toggle(&mut builder, "_", MessageEntityKind::Italic),
+            '`' => {
+                i = parse_code(&mut builder, &chars, i)?;
+                continue;
+            }
+            '[' => builder.open("[", None),
+            ']' if builder.is_open("[") && chars.get(i + 1) == Some(&'(') => {
+                let end = find_unescaped(&chars, i + 2, &[')'])
+                    .ok_or(ParseError::UnexpectedEnd)?;
+                let url = unescape(&chars[i + 2..end]);
+                builder.close("[", Some(entities::link_kind(url)));
+                i = end;
+            }
+            c => builder.push_char(c),
+        }
+        i += 1;
+    }
+
+    builder.build()
+}
+
+struct MarkdownV2;
+
+impl Markup for MarkdownV2 {
+    fn escape(s: &str) -> String {
+        escape(&s.replace(r"\", r"\\")).replace("\r", "\\\r")
+    }
+
+    fn escape_code(s: &str) -> String {
+        escape_code(s)
+    }
+
+    fn tokens(kind: &MessageEntityKind) -> Option<(String, String)> {
+        let symmetric = |token: &str| (token.to_owned(), token.to_owned());
+        let link = |url: &str| {
+            let url = escape_link_url(&url.replace(r"\", r"\\"));
+            ("[".to_owned(), format!("]({})", url))
+        };
+
+        match kind {
+            MessageEntityKind::Bold => Some(symmetric("*")),
+            MessageEntityKind::Italic => Some(symmetric("_")),
+            MessageEntityKind::Underline => Some(symmetric("__")),
+            MessageEntityKind::Strikethrough => Some(symmetric("~")),
+            MessageEntityKind::Code => Some(symmetric("`")),
+            MessageEntityKind::Pre { language } => Some((
+                format!("```{}\n", language.as_deref().unwrap_or("")),
+                "```".to_owned(),
+            )),
+            MessageEntityKind::TextLink { url } => Some(link(url)),
+            MessageEntityKind::TextMention { user } => {
+                Some(link(&format!("tg://user?id={}", user.id)))
+            }
+            _ => None,
+        }
+    }
+
+    fn push_token(out: &mut String, token: &str, after_token: bool) {
+        // `___` is ambiguous, so italic and underline tokens are separated.
+        if after_token && out.ends_with('_') && token.starts_with('_') {
+            out.push('\r');
+        }
+        out.push_str(token);
+    }
+}
+
+/// Closes the entity started with `token` if it's open, opens it otherwise.
+fn toggle(builder: &mut EntitiesBuilder, token: &str, kind: MessageEntityKind) {
+    if !builder.close(token, None) {
+        builder.open(token, Some(kind));
+    }
+}
+
+/// Parses an inline code or a code block starting at `start` and returns the
+/// index right after it.
+fn parse_code(
+    builder: &mut EntitiesBuilder,
+    chars: &[char],
+    start: usize,
+) -> Result<usize, ParseError> {
+    let token: &[char] = if chars[start..].starts_with(&['`', '`', '`']) {
+        &['`', '`', '`']
+    } else {
+        &['`']
+    };
+    let body_start = start + token.len();
+    let end = find_unescaped(chars, body_start, token).ok_or_else(|| {
+        ParseError::UnclosedEntity { token: token.iter().collect() }
+    })?;
+    let mut body = &chars[body_start..end];
+
+    let kind = if token.len() == 1 {
+        MessageEntityKind::Code
+    } else {
+        // The first line of a code block is a language if it's a single
+        // word.
+        let mut language = None;
+        if let Some(pos) = body.iter().position(|&c| c == '\n') {
+            if !body[..pos].iter().any(|&c| c.is_whitespace() || c == '\\') {
+                language = Some(body[..pos].iter().collect::<String>())
+                    .filter(|language| !language.is_empty());
+                body = &body[pos + 1..];
+            }
+        }
+        MessageEntityKind::Pre { language }
+    };
+
+    builder.open("`", Some(kind));
+    builder.push_str(&unescape(body));
+    builder.close("`", None);
+    Ok(end + token.len())
+}
+
+/// Returns `true` if `chars[i]` is `c` that isn't escaped.
+fn is_unescaped(chars: &[char], i: usize, c: char) -> bool {
+    let backslashes =
+        chars[..i].iter().rev().take_while(|&&c| c == '\\').count();
+    chars[i] == c && backslashes % 2 == 0
+}
+
+/// Returns the index of the first unescaped occurrence of `pattern` in
+/// `chars` starting from `from`.
+fn find_unescaped(
+    chars: &[char],
+    from: usize,
+    pattern: &[char],
+) -> Option<usize> {
+    let mut i = from;
+    while i < chars.len() {
+        if chars[i] == '\\' {
+            i += 2;
+        } else if chars[i..].starts_with(pattern) {
+            return Some(i);
+        } else {
+            i += 1;
+        }
+    }
+    None
+}
+
+/// Removes backslashes escaping characters.
+fn unescape(chars: &[char]) -> String {
+    let mut res = String::with_capacity(chars.len());
+    let mut chars = chars.iter();
+    while let Some(&c) = chars.next() {
+        match c {
+            '\\' => res.push(chars.next().copied().unwrap_or('\\')),
+            c => res.push(c),
+        }
+    }
+    res
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -260,6 +454,7 @@ mod tests {
             last_name: None,
             username: Some("abcd".to_string()),
             language_code: None,
+            ..User::default()
         };
         assert_eq!(user_mention_or_link(&user_with_username), "@abcd");
         let user_without_username = User {
@@ -269,10 +464,54 @@ mod tests {
             last_name: None,
             username: None,
             language_code: None,
+            ..User::default()
         };
         assert_eq!(
             user_mention_or_link(&user_without_username),
             r#"[Name](tg://user/?id=123456789)"#
         )
     }
+
+    #[test]
+    fn test_parse_entities() {
+        let (text, entities) = parse_entities(concat!(
+            r"*中文* \*😀\* ```rust",
+            "\nfn``` [bob](tg://user?id=42) __\r_x_\r__",
+        ))
+        .unwrap();
+
+        assert_eq!(text, "中文 *😀* fn bob x");
+        assert_eq!(
+            entities,
+            vec![
+                MessageEntity::new(MessageEntityKind::Bold, 0, 2),
+                MessageEntity::new(
+                    MessageEntityKind::Pre { language: Some("rust".into()) },
+                    8,
+                    2
+                ),
+                MessageEntity::new(
+                    MessageEntityKind::TextMention {
+                        user: User { id: 42, ..User::default() }
+                    },
+                    11,
+                    3
+                ),
+                MessageEntity::new(MessageEntityKind::Italic, 15, 1),
+                MessageEntity::new(MessageEntityKind::Underline, 15, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_entities_errors() {
+        assert_eq!(
+            parse_entities("*foo"),
+            Err(ParseError::UnclosedEntity { token: "*".into() })
+        );
+        assert_eq!(
+            parse_entities("`foo"),
+            Err(ParseError::UnclosedEntity { token: "`".into() })
+        );
+    }
 }
@@ -1,6 +1,15 @@
 //! Some useful utilities.
 
+pub mod broadcast;
+pub mod callback_data;
 pub mod client_from_env;
 pub mod command;
+pub mod entities;
+pub mod file_id_cache;
+pub mod formatted;
 pub mod html;
+pub mod keyboard;
 pub mod markdown;
+pub mod paginator;
+pub mod split;
+pub mod text_builder;
@@ -0,0 +1,324 @@
+//! An inline keyboard split into pages.
+//!
+//! ## Examples
+//! ```no_run
+//! use std::sync::Arc;
+//! use teloxide::{
+//!     prelude::*,
+//!     types::{CallbackQuery, GuildRole, InlineKeyboardButton},
+//!     utils::paginator::Paginator,
+//! };
+//!
+//! # async fn run(bot: Arc<Bot>, roles: Vec<GuildRole>) {
+//! let paginator = Arc::new(
+//!     Paginator::new("roles", roles, |role: &GuildRole| {
+//!         InlineKeyboardButton::callback(
+//!             role.name.clone(),
+//!             format!("role:{}", role.id),
+//!         )
+//!     })
+//!     .expect("The ID is short")
+//!     .page_size(6)
+//!     .columns(2),
+//! );
+//!
+//! // Send `paginator.markup(0)` with a message, then:
+//! let handler = move |rx: DispatcherHandlerRx<CallbackQuery>| {
+//!     rx.for_each_concurrent(None, move |cx| {
+//!         let paginator = Arc::clone(&paginator);
+//!         async move {
+//!             if let Ok(false) = paginator.handle(&cx).await {
+//!                 // Not a navigation button, e.g. a role button.
+//!             }
+//!         }
+//!     })
+//! };
+//!
+//! Dispatcher::new(bot).callback_queries_handler(handler).dispatch().await;
+//! # }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::{fmt, sync::Arc};
+
+use crate::{
+    dispatching::UpdateWithCx,
+    requests::{Request, ResponseResult},
+    types::{CallbackQuery, InlineKeyboardButton, InlineKeyboardMarkup},
+    utils::callback_data::{CallbackData, CallbackDataError},
+};
+
+/// Callback data of navigation buttons: an ID of a paginator and a page to
+/// show, or `None` for the button showing the current page.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct PageCallback(String, Option<usize>);
+
+impl CallbackData for PageCallback {
+    const PREFIX: &'static str = "pg";
+}
+
+/// An inline keyboard showing `items` page by page with navigation buttons.
+///
+/// Navigation buttons carry the page in callback data, so the paginator
+/// doesn't keep any per-message state. Callback queries of navigation
+/// buttons are handled with [`Paginator::handle`].
+///
+/// See [the module-level documentation](crate::utils::paginator).
+pub struct Paginator<T> {
+    id: String,
+    items: Vec<T>,
+    render: Arc<dyn Fn(&T) -> InlineKeyboardButton + Send + Sync>,
+    page_size: usize,
+    columns: usize,
+    prev_label: String,
+    next_label: String,
+}
+
+impl<T> Paginator<T> {
+    pub const DEFAULT_PAGE_SIZE: usize = 10;
+
+    /// Creates a paginator of `items`, each rendered as a button by `render`.
+    ///
+    /// `id` distinguishes navigation buttons of this paginator from other
+    /// paginators of the bot, so it must be unique and short.
+    ///
+    /// Returns [`CallbackDataError::TooLong`] if `id` is too long to fit into
+    /// callback data of navigation buttons.
+    pub fn new<I, F>(
+        id: I,
+        items: Vec<T>,
+        render: F,
+    ) -> Result<Self, CallbackDataError>
+    where
+        I: Into<String>,
+        F: Fn(&T) -> InlineKeyboardButton + Send + Sync + 'static,
+    {
+        let id = id.into();
+        // The longest callback data this paginator can produce.
+        PageCallback(id.clone(), Some(usize::MAX)).encode()?;
+
+        Ok(Self {
+            id,
+            items,
+            render: Arc::new(render),
+            page_size: Self::DEFAULT_PAGE_SIZE,
+            columns: 1,
+            prev_label: "«".to_owned(),
+            next_label: "»".to_owned(),
+        })
+    }
+
+    /// A number of items on a page, at least 1.
+    pub fn page_size(mut self, val: usize) -> Self {
+        self.page_size = val.max(1);
+        self
+    }
+
+    /// A number of item buttons in a row, at least 1.
+    pub fn columns(mut self, val: usize) -> Self {
+        self.columns = val.max(1);
+        self
+    }
+
+    /// Labels of the buttons leading to the previous and the next page.
+    pub fn labels<P, N>(mut self, prev: P, next: N) -> Self
+    where
+        P: Into<String>,
+        N: Into<String>,
+    {
+        self.prev_label = prev.into();
+        self.next_label = next.into();
+        self
+    }
+
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// A number of pages, at least 1.
+    pub fn page_count(&self) -> usize {
+        ((self.items.len() + self.page_size - 1) / self.page_size).max(1)
+    }
+
+    /// Items on `page`, counting from 0.
+    pub fn page_items(&self, page: usize) -> &[T] {
+        let start = (page * self.page_size).min(self.items.len());
+        let end = (start + self.page_size).min(self.items.len());
+        &self.items[start..end]
+    }
+
+    /// Renders `page`, counting from 0.
+    ///
+    /// If `page` is out of range, the last page is rendered.
+    pub fn markup(&self, page: usize) -> InlineKeyboardMarkup {
+        let page = page.min(self.page_count() - 1);
+
+        let mut markup = InlineKeyboardMarkup::default();
+        for row in self.page_items(page).chunks(self.columns) {
+            markup = markup.append_row(
+                row.iter().map(|item| (self.render)(item)).collect(),
+            );
+        }
+
+        if self.page_count() > 1 {
+            let mut navigation = Vec::with_capacity(3);
+            if page > 0 {
+                navigation.push(self.button(&self.prev_label, Some(page - 1)));
+            }
+            navigation.push(
+                self.button(
+                    &format!("{}/{}", page + 1, self.page_count()),
+                    None,
+                ),
+            );
+            if page + 1 < self.page_count() {
+                navigation.push(self.button(&self.next_label, Some(page + 1)));
+            }
+            markup = markup.append_row(navigation);
+        }
+
+        markup
+    }
+
+    /// Returns `Some(page)` if `query` is sent by a navigation button of this
+    /// paginator, where `page` is `None` for the button showing the current
+    /// page.
+    pub fn parse(&self, query: &CallbackQuery) -> Option<Option<usize>> {
+        let PageCallback(id, page) =
+            PageCallback::decode(query.data.as_ref()?)?;
+        if id == self.id {
+            Some(page)
+        } else {
+            None
+        }
+    }
+
+    /// Handles a callback query of a navigation button: answers it and
+    /// shows the requested page in the message of the button.
+    ///
+    /// Returns `false` if the query isn't sent by a navigation button of this
+    /// paginator.
+    pub async fn handle(
+        &self,
+        cx: &UpdateWithCx<CallbackQuery>,
+    ) -> ResponseResult<bool> {
+        let page = match self.parse(&cx.update) {
+            Some(page) => page,
+            None => return Ok(false),
+        };
+
+        cx.answer().send().await?;
+        if let (Some(page), Some(edit)) = (page, cx.edit_origin_markup()) {
+            edit.reply_markup(self.markup(page)).send().await?;
+        }
+        Ok(true)
+    }
+
+    fn button(&self, text: &str, page: Option<usize>) -> InlineKeyboardButton {
+        let data = PageCallback(self.id.clone(), page)
+            .encode()
+            .expect("The length of the ID is checked in `Paginator::new`");
+        InlineKeyboardButton::callback(text.to_owned(), data)
+    }
+}
+
+impl<T> fmt::Debug for Paginator<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Paginator")
+            .field("id", &self.id)
+            .field("items", &self.items)
+            .field("page_size", &self.page_size)
+            .field("columns", &self.columns)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::InlineKeyboardButtonKind;
+
+    fn paginator(count: u32) -> Paginator<u32> {
+        Paginator::new("n", (0..count).collect(), |n: &u32| {
+            InlineKeyboardButton::callback(n.to_string(), n.to_string())
+        })
+        .unwrap()
+        .page_size(3)
+        .columns(2)
+    }
+
+    fn texts(markup: &InlineKeyboardMarkup) -> Vec<Vec<&str>> {
+        markup
+            .inline_keyboard
+            .iter()
+            .map(|row| row.iter().map(|b| b.text.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn pages() {
+        let paginator = paginator(7);
+        assert_eq!(paginator.page_count(), 3);
+
+        assert_eq!(
+            texts(&paginator.markup(0)),
+            vec![vec!["0", "1"], vec!["2"], vec!["1/3", "»"]]
+        );
+        assert_eq!(
+            texts(&paginator.markup(1)),
+            vec![vec!["3", "4"], vec!["5"], vec!["«", "2/3", "»"]]
+        );
+        assert_eq!(
+            texts(&paginator.markup(2)),
+            vec![vec!["6"], vec!["«", "3/3"]]
+        );
+        // Out of range pages are clamped.
+        assert_eq!(paginator.markup(10), paginator.markup(2));
+    }
+
+    #[test]
+    fn single_page() {
+        assert_eq!(texts(&paginator(2).markup(0)), vec![vec!["0", "1"]]);
+        assert_eq!(paginator(0).page_count(), 1);
+        assert!(paginator(0).markup(0).inline_keyboard.is_empty());
+    }
+
+    #[test]
+    fn long_id() {
+        let render = |n: &u32| {
+            InlineKeyboardButton::callback(n.to_string(), n.to_string())
+        };
+        assert!(Paginator::new("x".repeat(30), vec![1], render).is_ok());
+        match Paginator::new("x".repeat(50), vec![1], render) {
+            Err(CallbackDataError::TooLong { .. }) => {}
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn navigation_data() {
+        let paginator = paginator(7);
+        let markup = paginator.markup(1);
+        let navigation: Vec<_> = markup.inline_keyboard[2]
+            .iter()
+            .map(|button| match &button.kind {
+                InlineKeyboardButtonKind::CallbackData(data) => {
+                    PageCallback::decode(data).unwrap()
+                }
+                kind => panic!("unexpected button kind: {:?}", kind),
+            })
+            .collect();
+
+        assert_eq!(
+            navigation,
+            vec![
+                PageCallback("n".into(), Some(0)),
+                PageCallback("n".into(), None),
+                PageCallback("n".into(), Some(2)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,250 @@
+//! Splitting texts which are too long for one message.
+//!
+//! A text is split at the last paragraph, line, sentence or word boundary
+//! that fits into a message, and only inside of a word if there is no such
+//! boundary. Entities crossing a split are cut into parts, so markup is
+//! closed at the end of a part and reopened in the next one.
+//!
+//! See also [`Bot::send_long_message`], which sends all the parts.
+//!
+//! ## Examples
+//! ```
+//! use teloxide::utils::split::split_html;
+//!
+//! let parts = split_html("<b>Hello, world! How are you?</b>", 16).unwrap();
+//! assert_eq!(parts, vec!["<b>Hello, world!</b>", "<b>How are you?</b>"]);
+//! ```
+//!
+//! [`Bot::send_long_message`]: crate::Bot::send_long_message
+
+use std::ops::Range;
+
+use crate::{
+    types::MessageEntity,
+    utils::{entities::ParseError, html, markdown},
+};
+
+/// The maximum length of a text message in UTF-16 code units.
+pub const MAX_MESSAGE_LEN: usize = 4096;
+
+/// Splits a plain `text` into parts of at most `max_len` UTF-16 code units.
+///
+/// Whitespace between parts is dropped.
+///
+/// # Panics
+/// If `max_len` is zero.
+pub fn split_text(text: &str, max_len: usize) -> Vec<&str> {
+    split_ranges(text, max_len).into_iter().map(|range| &text[range]).collect()
+}
+
+/// Splits a plain `text` with `entities` into parts of at most `max_len`
+/// UTF-16 code units, moving entities into the parts they cover.
+///
+/// # Panics
+/// If `max_len` is zero.
+pub fn split_entities(
+    text: &str,
+    entities: &[MessageEntity],
+    max_len: usize,
+) -> Vec<(String, Vec<MessageEntity>)> {
+    split_ranges(text, max_len)
+        .into_iter()
+        .map(|range| {
+            let start = len_utf16(&text[..range.start]);
+            let end = start + len_utf16(&text[range.clone()]);
+            let entities = entities
+                .iter()
+                .filter_map(|entity| {
+                    let from = entity.offset.max(start);
+                    let to = (entity.offset + entity.length).min(end);
+                    if from >= to {
+                        return None;
+                    }
+                    let kind = entity.kind.clone();
+                    Some(MessageEntity::new(kind, from - start, to - from))
+                })
+                .collect();
+
+            (text[range].to_owned(), entities)
+        })
+        .collect()
+}
+
+/// Splits a text in the HTML message style into parts of at most `max_len`
+/// UTF-16 code units of a plain text.
+///
+/// Tags crossing a split are closed and reopened.
+///
+/// # Panics
+/// If `max_len` is zero.
+pub fn split_html(
+    html: &str,
+    max_len: usize,
+) -> Result<Vec<String>, ParseError> {
+    let (text, entities) = html::parse_entities(html)?;
+    Ok(split_entities(&text, &entities, max_len)
+        .into_iter()
+        .map(|(text, entities)| html::render_entities(&text, &entities))
+        .collect())
+}
+
+/// Splits a text in the Markdown V2 message style into parts of at most
+/// `max_len` UTF-16 code units of a plain text.
+///
+/// Entities crossing a split are closed and reopened.
+///
+/// # Panics
+/// If `max_len` is zero.
+pub fn split_markdown(
+    markdown: &str,
+    max_len: usize,
+) -> Result<Vec<String>, ParseError> {
+    let (text, entities) = markdown::parse_entities(markdown)?;
+    Ok(split_entities(&text, &entities, max_len)
+        .into_iter()
+        .map(|(text, entities)| markdown::render_entities(&text, &entities))
+        .collect())
+}
+
+fn len_utf16(s: &str) -> usize {
+    s.encode_utf16().count()
+}
+
+/// Byte ranges of parts of `text`.
+fn split_ranges(text: &str, max_len: usize) -> Vec<Range<usize>> {
+    assert!(max_len > 0, "`max_len` must be positive");
+
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    loop {
+        let rest = &text[start..];
+        if len_utf16(rest) <= max_len {
+            if !rest.is_empty() || ranges.is_empty() {
+                ranges.push(start..text.len());
+            }
+            return ranges;
+        }
+
+        let end = start + split_point(rest, max_len);
+        ranges.push(start..end);
+        start = end + (text[end..].len() - text[end..].trim_start().len());
+    }
+}
+
+/// The byte offset at which `text` longer than `max_len` is split.
+///
+/// It's never zero, so that splitting makes progress.
+fn split_point(text: &str, max_len: usize) -> usize {
+    let mut fits = 0;
+    let mut len = 0;
+    for (i, c) in text.char_indices() {
+        len += c.len_utf16();
+        if len > max_len {
+            break;
+        }
+        fits = i + c.len_utf8();
+    }
+    if fits == 0 {
+        // Not even one character fits.
+        return text.chars().next().map_or(0, char::len_utf8);
+    }
+    let window = &text[..fits];
+
+    let boundary = window
+        .rfind("\n\n")
+        .or_else(|| window.rfind('\n'))
+        .or_else(|| {
+            // The end of a sentence, with the punctuation.
+            window
+                .rmatch_indices(|c: char| c.is_whitespace())
+                .find(|&(i, _)| {
+                    window[..i].ends_with(&['.', '!', '?', '…'][..])
+                })
+                .map(|(i, _)| i)
+        })
+        .or_else(|| window.rfind(char::is_whitespace));
+
+    // Also, whitespace before a boundary is dropped.
+    match boundary.map(|i| window[..i].trim_end().len()) {
+        Some(end) if end > 0 => end,
+        // Either there's no boundary, or the window begins with it.
+        _ => fits,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MessageEntityKind;
+
+    #[test]
+    fn boundaries() {
+        assert_eq!(split_text("short", 10), vec!["short"]);
+        assert_eq!(split_text("", 10), vec![""]);
+        assert_eq!(
+            split_text("First paragraph.\nLine.\n\nSecond one.", 30),
+            vec!["First paragraph.\nLine.", "Second one."]
+        );
+        assert_eq!(
+            split_text("One sentence. Another one here", 20),
+            vec!["One sentence.", "Another one here"]
+        );
+        assert_eq!(
+            split_text("some words  here", 12),
+            vec!["some words", "here"]
+        );
+        assert_eq!(split_text("abcdefgh", 3), vec!["abc", "def", "gh"]);
+    }
+
+    #[test]
+    fn utf16() {
+        // Emojis are two code units long and are never cut in half.
+        assert_eq!(split_text("😀😀😀", 3), vec!["😀", "😀", "😀"]);
+        assert_eq!(split_text("😀a", 1), vec!["😀", "a"]);
+    }
+
+    #[test]
+    fn entities() {
+        let parts = split_entities(
+            "aaa bbb ccc",
+            &[
+                MessageEntity::new(MessageEntityKind::Bold, 2, 7),
+                MessageEntity::new(MessageEntityKind::Italic, 8, 3),
+            ],
+            4,
+        );
+
+        assert_eq!(
+            parts,
+            vec![
+                (
+                    "aaa".to_owned(),
+                    vec![MessageEntity::new(MessageEntityKind::Bold, 2, 1)]
+                ),
+                (
+                    "bbb".to_owned(),
+                    vec![MessageEntity::new(MessageEntityKind::Bold, 0, 3)]
+                ),
+                (
+                    "ccc".to_owned(),
+                    vec![
+                        MessageEntity::new(MessageEntityKind::Bold, 0, 1),
+                        MessageEntity::new(MessageEntityKind::Italic, 0, 3),
+                    ]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn markup() {
+        assert_eq!(
+            split_html("<b>bold <i>and italic</i></b> text", 12).unwrap(),
+            vec!["<b>bold <i>and</i></b>", "<b><i>italic</i></b> text"]
+        );
+        assert_eq!(
+            split_markdown("*bold _and italic_* text", 12).unwrap(),
+            vec!["*bold _and_*", "*_italic_* text"]
+        );
+    }
+}
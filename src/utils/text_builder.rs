@@ -0,0 +1,418 @@
+//! Building message text together with its [`MessageEntity`]s.
+//!
+//! Unlike [`utils::html`] and [`utils::markdown`], [`TextBuilder`] doesn't
+//! require a parse mode: it produces plain text and a list of entities with
+//! correct UTF-16 offsets, which are sent via [`SendMessage::entities`] or
+//! `caption_entities` of media requests.
+//!
+//! Offsets and lengths of entities are counted in UTF-16 code units (see
+//! [`MessageEntity`]), so they can't be computed with `str::len` or
+//! `chars().count()` once the text contains non-ASCII characters, e.g. "🦀"
+//! takes 2 code units, 1 `char` and 4 bytes.
+//!
+//! Styles can be nested by passing another builder instead of a string. The
+//! same text can also be rendered for a parse mode with [`TextBuilder::html`]
+//! or [`TextBuilder::markdown_v2`], which escape it automatically, and sent
+//! with [`SendMessage::formatted`].
+//!
+//! ## Examples
+//! ```
+//! use teloxide::{
+//!     types::{MessageEntity, MessageEntityKind},
+//!     utils::text_builder::TextBuilder,
+//! };
+//!
+//! let (text, entities) =
+//!     TextBuilder::new().plain("🦀 ").bold("Ferris").plain(" says hi").build();
+//!
+//! assert_eq!(text, "🦀 Ferris says hi");
+//! assert_eq!(
+//!     entities,
+//!     vec![MessageEntity::new(MessageEntityKind::Bold, 3, 6)]
+//! );
+//! ```
+//!
+//! Nested styles, rendered as HTML:
+//! ```
+//! use teloxide::{types::ParseMode, utils::text_builder::TextBuilder};
+//!
+//! let name = "<Ferris & co>";
+//! let text = TextBuilder::new()
+//!     .plain("Hello, ")
+//!     .bold(TextBuilder::new().plain(name).italic("!"))
+//!     .plain(" See ")
+//!     .link("the docs", "https://example.com")
+//!     .plain(".");
+//!
+//! let html = text.html();
+//! assert_eq!(
+//!     html.text(),
+//!     "Hello, <b>&lt;Ferris &amp; co&gt;<i>!</i></b> See <a \
+//!      href=\"https://example.com\">the docs</a>."
+//! );
+//! assert_eq!(html.parse_mode(), ParseMode::HTML);
+//!
+//! let markdown = text.markdown_v2();
+//! assert_eq!(
+//!     markdown.text(),
+//!     "Hello, *<Ferris & co\\>_\\!_* See [the docs](https://example.com)\\."
+//! );
+//! ```
+//!
+//! [`MessageEntity`]: crate::types::MessageEntity
+//! [`utils::html`]: crate::utils::html
+//! [`utils::markdown`]: crate::utils::markdown
+//! [`TextBuilder`]: crate::utils::text_builder::TextBuilder
+//! [`SendMessage::entities`]: crate::requests::SendMessage::entities
+//! [`TextBuilder::html`]: crate::utils::text_builder::TextBuilder::html
+//! [`TextBuilder::markdown_v2`]:
+//! crate::utils::text_builder::TextBuilder::markdown_v2
+//! [`SendMessage::formatted`]: crate::requests::SendMessage::formatted
+
+use crate::{
+    types::{GuildRole, MessageEntity, MessageEntityKind, ParseMode, User},
+    utils::{formatted::FormattedText, html, markdown},
+};
+
+/// A builder of message text and its entities.
+///
+/// See [the module-level documentation](crate::utils::text_builder).
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TextBuilder {
+    text: String,
+    entities: Vec<MessageEntity>,
+
+    /// A length of `text` in UTF-16 code units.
+    len_utf16: usize,
+}
+
+impl TextBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends text without an entity.
+    pub fn plain(mut self, s: &str) -> Self {
+        self.push(s);
+        self
+    }
+
+    /// Appends text and entities of `other`.
+    pub fn append<T>(mut self, other: T) -> Self
+    where
+        T: Into<TextBuilder>,
+    {
+        let other = other.into();
+        let offset = self.len_utf16;
+        self.entities.extend(other.entities.into_iter().map(|mut entity| {
+            entity.offset += offset;
+            entity
+        }));
+        self.text += &other.text;
+        self.len_utf16 += other.len_utf16;
+        self
+    }
+
+    /// Appends `inner` marked with an entity of the specified kind.
+    ///
+    /// An empty `inner` is appended without an entity.
+    pub fn entity<T>(mut self, inner: T, kind: MessageEntityKind) -> Self
+    where
+        T: Into<TextBuilder>,
+    {
+        let inner = inner.into();
+        if inner.len_utf16 > 0 {
+            let entity =
+                MessageEntity::new(kind, self.len_utf16, inner.len_utf16);
+            self.entities.push(entity);
+        }
+        self.append(inner)
+    }
+
+    pub fn bold<T>(self, inner: T) -> Self
+    where
+        T: Into<TextBuilder>,
+    {
+        self.entity(inner, MessageEntityKind::Bold)
+    }
+
+    pub fn italic<T>(self, inner: T) -> Self
+    where
+        T: Into<TextBuilder>,
+    {
+        self.entity(inner, MessageEntityKind::Italic)
+    }
+
+    pub fn underline<T>(self, inner: T) -> Self
+    where
+        T: Into<TextBuilder>,
+    {
+        self.entity(inner, MessageEntityKind::Underline)
+    }
+
+    pub fn strikethrough<T>(self, inner: T) -> Self
+    where
+        T: Into<TextBuilder>,
+    {
+        self.entity(inner, MessageEntityKind::Strikethrough)
+    }
+
+    /// Appends an inline code.
+    ///
+    /// Code cannot contain other styles.
+    pub fn code(self, s: &str) -> Self {
+        self.entity(s, MessageEntityKind::Code)
+    }
+
+    /// Appends a code block with an optional language.
+    pub fn pre(self, s: &str, language: Option<String>) -> Self {
+        self.entity(s, MessageEntityKind::Pre { language })
+    }
+
+    /// Appends `text` linked to `url`.
+    pub fn link<T>(self, text: T, url: &str) -> Self
+    where
+        T: Into<TextBuilder>,
+    {
+        self.entity(text, MessageEntityKind::TextLink { url: url.to_owned() })
+    }
+
+    /// Appends a mention of `user`.
+    ///
+    /// The text of the mention is `@username` or, if the user doesn't have a
+    /// username, the full name of the user.
+    pub fn user_mention(self, user: &User) -> Self {
+        let text = user.mention().unwrap_or_else(|| user.full_name());
+        self.user_mention_with_text(user, text)
+    }
+
+    /// Appends a mention of `user` with a custom text.
+    pub fn user_mention_with_text<T>(self, user: &User, text: T) -> Self
+    where
+        T: Into<TextBuilder>,
+    {
+        self.entity(text, MessageEntityKind::TextMention { user: user.clone() })
+    }
+
+    /// Appends a mention of `role` with the `@role_name` text.
+    pub fn role_mention(self, role: &GuildRole) -> Self {
+        self.role_mention_with_text(role.id, format!("@{}", role.name))
+    }
+
+    /// Appends a mention of a role with a custom text.
+    pub fn role_mention_with_text<T>(self, role_id: i64, text: T) -> Self
+    where
+        T: Into<TextBuilder>,
+    {
+        self.entity(text, MessageEntityKind::RoleMention { role_id })
+    }
+
+    /// Appends a mention of a guild channel with the `#channel_name` text.
+    pub fn channel_mention(self, channel_id: i64, channel_name: &str) -> Self {
+        self.entity(
+            format!("#{}", channel_name),
+            MessageEntityKind::ChannelMention { channel_id },
+        )
+    }
+
+    /// The text built so far.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The entities built so far.
+    pub fn entities(&self) -> &[MessageEntity] {
+        &self.entities
+    }
+
+    /// Returns `true` if nothing was appended yet.
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// A length of the text in UTF-16 code units, as counted by the platform
+    /// for message limits.
+    pub fn len_utf16(&self) -> usize {
+        self.len_utf16
+    }
+
+    /// Returns the text and its entities.
+    pub fn build(self) -> (String, Vec<MessageEntity>) {
+        (self.text, self.entities)
+    }
+
+    /// Renders the text for [`ParseMode::HTML`].
+    ///
+    /// [`ParseMode::HTML`]: crate::types::ParseMode::HTML
+    pub fn html(&self) -> FormattedText {
+        FormattedText::new(
+            html::render_entities(&self.text, &self.entities),
+            ParseMode::HTML,
+        )
+    }
+
+    /// Renders the text for [`ParseMode::MarkdownV2`].
+    ///
+    /// [`ParseMode::MarkdownV2`]: crate::types::ParseMode::MarkdownV2
+    pub fn markdown_v2(&self) -> FormattedText {
+        FormattedText::new(
+            markdown::render_entities(&self.text, &self.entities),
+            ParseMode::MarkdownV2,
+        )
+    }
+
+    /// Appends `s` and returns its length in UTF-16 code units.
+    fn push(&mut self, s: &str) -> usize {
+        let len = s.encode_utf16().count();
+        self.text.push_str(s);
+        self.len_utf16 += len;
+        len
+    }
+}
+
+impl From<&str> for TextBuilder {
+    fn from(s: &str) -> Self {
+        Self::new().plain(s)
+    }
+}
+
+impl From<String> for TextBuilder {
+    fn from(s: String) -> Self {
+        Self::new().plain(&s)
+    }
+}
+
+impl From<&String> for TextBuilder {
+    fn from(s: &String) -> Self {
+        Self::new().plain(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_offsets() {
+        let (text, entities) = TextBuilder::new()
+            .plain("Hello, ")
+            .bold("world")
+            .plain("! ")
+            .code("x = 1")
+            .build();
+
+        assert_eq!(text, "Hello, world! x = 1");
+        assert_eq!(
+            entities,
+            vec![
+                MessageEntity::new(MessageEntityKind::Bold, 7, 5),
+                MessageEntity::new(MessageEntityKind::Code, 14, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn utf16_offsets() {
+        // "😀" is 2 UTF-16 code units, "ф" and "中" are 1.
+        let (text, entities) = TextBuilder::new()
+            .plain("😀ф ")
+            .italic("中😀")
+            .link("ссылка", "https://example.com")
+            .build();
+
+        assert_eq!(text, "😀ф 中😀ссылка");
+        assert_eq!(
+            entities,
+            vec![
+                MessageEntity::new(MessageEntityKind::Italic, 4, 3),
+                MessageEntity::new(
+                    MessageEntityKind::TextLink {
+                        url: "https://example.com".to_string()
+                    },
+                    7,
+                    6
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn mentions() {
+        let user = User {
+            id: 42,
+            first_name: "Ferris".to_string(),
+            username: Some("ferris".to_string()),
+            ..User::default()
+        };
+        let role = GuildRole::new(7, "admins".to_string(), 0, 0, 0);
+
+        let builder = TextBuilder::new()
+            .user_mention(&user)
+            .plain(" ")
+            .role_mention(&role)
+            .plain(" ")
+            .channel_mention(3, "general");
+
+        assert_eq!(builder.text(), "@ferris @admins #general");
+        assert_eq!(
+            builder.entities(),
+            &[
+                MessageEntity::new(
+                    MessageEntityKind::TextMention { user },
+                    0,
+                    7
+                ),
+                MessageEntity::new(
+                    MessageEntityKind::RoleMention { role_id: 7 },
+                    8,
+                    7
+                ),
+                MessageEntity::new(
+                    MessageEntityKind::ChannelMention { channel_id: 3 },
+                    16,
+                    8
+                ),
+            ][..]
+        );
+    }
+
+    #[test]
+    fn empty_entity_is_skipped() {
+        let builder = TextBuilder::new().bold("");
+        assert!(builder.is_empty());
+        assert!(builder.entities().is_empty());
+    }
+
+    #[test]
+    fn nested_offsets() {
+        let builder = TextBuilder::new()
+            .plain("😀 ")
+            .bold(TextBuilder::new().plain("a").italic("b").code("c"));
+
+        assert_eq!(builder.text(), "😀 abc");
+        assert_eq!(
+            builder.entities(),
+            &[
+                MessageEntity::new(MessageEntityKind::Bold, 3, 3),
+                MessageEntity::new(MessageEntityKind::Italic, 4, 1),
+                MessageEntity::new(MessageEntityKind::Code, 5, 1),
+            ][..]
+        );
+    }
+
+    #[test]
+    fn escaping() {
+        let builder = TextBuilder::new()
+            .plain("1 < 2 * 3")
+            .plain(" ")
+            .code("a < b_c")
+            .bold("");
+
+        assert_eq!(
+            builder.html().text(),
+            "1 &lt; 2 * 3 <code>a &lt; b_c</code>"
+        );
+        assert_eq!(builder.markdown_v2().text(), "1 < 2 \\* 3 `a < b_c`");
+        assert_eq!(builder.entities().len(), 1);
+    }
+}